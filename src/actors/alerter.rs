@@ -1,29 +1,95 @@
 use crate::actors::*;
 use crate::alerts::*;
+use crate::config::{apply_templates, FlapDetection, DIGEST_ALERT_TYPES};
+use crate::duration;
 use crate::error::Error;
 use crate::openapi_client::models;
+use crate::silence::Silence;
 use chrono::offset::Utc;
 use chrono::DateTime;
 use hostname::get as get_hostname;
-use std::collections::HashMap;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
+use std::time::Duration;
 use sysinfo::SystemExt;
 
+/// How often the alerter checks for silences that have ended
+const SILENCE_CHECK_SEC: u64 = 30;
+/// How often the alerter checks for failed alerts that are due to be retried
+const DELIVERY_CHECK_SEC: u64 = 1;
+/// How long the alerter collects statuses before processing them as one batch
+const STATUS_BATCH_MS: u64 = 500;
+
 #[allow(dead_code)]
 pub struct AlerterActor {
     status_buffer: Vec<(models::Monitor, models::MonitorStatus)>,
+    /// How long statuses are collected before they are processed, so a parent and the monitors
+    /// that depend on it are looked at together whichever status arrives first
+    batch_window: Duration,
+    /// Alert state of each monitor, keyed by monitor name
     statuses: HashMap<String, MonitorState>,
+    /// ID of the source each monitor came from, keyed by monitor name
+    monitor_sources: HashMap<String, String>,
     api: Box<dyn AlertApi>,
     alerts: Vec<models::Alert>,
+    flap_detection: FlapDetection,
+    config_silences: Vec<Silence>,
+    /// Silences added through the control socket
+    runtime_silences: Vec<Silence>,
+    /// Silences that were active when last checked, keyed by silence ID
+    active_silences: HashMap<String, Silence>,
+    next_silence_id: usize,
+    timer: Addr<TimerActor>,
+    /// Alerts waiting for their escalation delay, keyed by timer UID
+    pending_escalations: HashMap<String, PendingEscalation>,
+    /// State changes waiting for the group window of their alert to close, keyed by timer UID
+    alert_groups: HashMap<String, AlertGroup>,
+    /// Alerts that failed to send and are retried
+    delivery: DeliveryQueue,
+    /// Whether the alerts have been loaded. Retries wait for them, as the alert of a spooled
+    /// retry may come from a source that has not loaded yet.
+    alerts_loaded: bool,
+}
+
+/// Which attempt at sending an alert this is, which decides what happens if it fails.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DeliveryAttempt {
+    First,
+    /// A retry of the failed alert with this ID in the delivery queue
+    Retry(u64),
+    /// The notice on the fallback alert that an alert could not be delivered. It is not retried.
+    Fallback,
+}
+
+/// An alert that is sent once its monitor has been DOWN for its `escalateAfter` delay.
+#[derive(Clone, Debug)]
+struct PendingEscalation {
+    monitor_name: String,
+    alert_key: String,
+    due: DateTime<Utc>,
 }
 
 impl AlerterActor {
-    pub fn new<A: AlertApi + 'static>(api: A) -> Self {
+    pub fn new<A: AlertApi + 'static>(api: A, timer: Addr<TimerActor>) -> Self {
         Self {
             status_buffer: vec![],
+            batch_window: Duration::from_millis(STATUS_BATCH_MS),
             statuses: HashMap::new(),
+            monitor_sources: HashMap::new(),
             api: Box::new(api),
             alerts: vec![],
+            flap_detection: FlapDetection::default(),
+            config_silences: vec![],
+            runtime_silences: vec![],
+            active_silences: HashMap::new(),
+            next_silence_id: 0,
+            timer,
+            pending_escalations: HashMap::new(),
+            alert_groups: HashMap::new(),
+            delivery: DeliveryQueue::new(),
+            alerts_loaded: false,
         }
     }
 }
@@ -33,109 +99,953 @@ impl AlerterActor {
         self.status_buffer
             .sort_by(|s1, s2| s1.1.timestamp.cmp(&s2.1.timestamp));
 
-        for (monitor, status) in self.status_buffer.iter() {
-            if !self.statuses.contains_key(&status.status_id) {
-                self.statuses
-                    .insert(status.status_id.clone(), MonitorState::new(monitor, status));
-            }
-            match self.statuses.get_mut(&status.monitor_name) {
-                Some(ref mut current_state) => {
-                    let is_down = status.status == models::MonitorStatusIndicator::DOWN;
-
-                    let state_changed = match (
-                        current_state.last_timestamp <= status.timestamp,
-                        current_state.last_status.clone(),
-                        status,
-                    ) {
-                        (true, previous, current) => previous.status != current.status,
-                        _ => false,
-                    };
-
-                    if state_changed || (is_down && current_state.is_new) {
-                        info!("Detected monitor state change (monitor_name={}, previous_statues={}, current_state={})",
-                            status.monitor_name, current_state.last_status.status, status.status);
-
-                        current_state.is_new = false;
-                        current_state.last_status = status.clone();
-
-                        perform_state_change_action(
-                            &mut *self.api,
-                            &current_state,
-                            ctx,
-                            &self.alerts,
-                        );
-                    }
+        let batch = std::mem::take(&mut self.status_buffer);
+        for (monitor, status) in batch.iter().cloned() {
+            self.check_silences(status.timestamp, ctx);
+            let silence = self.silence_for(&monitor, status.timestamp);
+            let parents_down = self.parents_down(&monitor, &batch);
+
+            let state = self
+                .statuses
+                .entry(monitor.name.clone())
+                .or_insert_with(|| MonitorState::new(&monitor, &status));
+
+            if status.timestamp < state.last_timestamp {
+                debug!(
+                    "Ignoring status older than the last one (monitor_name={})",
+                    monitor.name
+                );
+                continue;
+            }
+
+            if state.last_status.status != status.status {
+                info!("Detected monitor state change (monitor_name={}, previous_statues={}, current_state={})",
+                    monitor.name, state.last_status.status, status.status);
+            }
+
+            state.record(&monitor, &status);
+            state.parents_down = parents_down.clone();
+
+            let was_flapping = state.flapping;
+            state.detect_flapping(&self.flap_detection);
+            let flapping_started = state.flapping && !was_flapping;
+
+            let mut suppressed = false;
+            if flapping_started {
+                info!(
+                    "Monitor started flapping (monitor_name={}, state_change_percent={:.1})",
+                    monitor.name,
+                    state.state_change_percent.unwrap_or_default()
+                );
+            } else if was_flapping && !state.flapping {
+                info!(
+                    "Monitor stopped flapping (monitor_name={}, state_change_percent={:.1})",
+                    monitor.name,
+                    state.state_change_percent.unwrap_or_default()
+                );
+            } else if state.flapping {
+                debug!(
+                    "Suppressing alerts while monitor is flapping (monitor_name={})",
+                    monitor.name
+                );
+                suppressed = true;
+            }
+
+            if let (false, Some(silence_id)) = (suppressed, silence) {
+                debug!(
+                    "Suppressing alerts for silenced monitor (monitor_name={}, silence_id={})",
+                    monitor.name, silence_id
+                );
+                suppressed = true;
+            }
+
+            if !suppressed && !parents_down.is_empty() {
+                debug!(
+                    "Suppressing alerts for unreachable monitor (parent down) (monitor_name={}, parents_down={})",
+                    monitor.name,
+                    parents_down.join(", ")
+                );
+                suppressed = true;
+            }
+
+            // escalations wait while alerts are held back
+            let due = if suppressed {
+                DueAlerts::default()
+            } else {
+                due_alerts(&self.alerts, state, flapping_started, status.timestamp)
+            };
+            self.dispatch(&monitor.name, due, status.timestamp, ctx);
+        }
+
+        self.update_reachability();
+    }
+
+    /// Records which monitors are unreachable now that the batch has been processed, including
+    /// those whose parent recovered or went DOWN without a new status of their own.
+    fn update_reachability(&mut self) {
+        let mut monitor_names: Vec<String> = self.statuses.keys().cloned().collect();
+        monitor_names.sort();
+
+        for monitor_name in monitor_names {
+            let parents_down = match self.statuses.get(&monitor_name) {
+                Some(state) => self.parents_down(&state.monitor, &[]),
+                None => continue,
+            };
+            let state = match self.statuses.get_mut(&monitor_name) {
+                Some(state) => state,
+                None => continue,
+            };
+            if state.parents_down.is_empty() && !parents_down.is_empty() {
+                info!(
+                    "Monitor is unreachable (parent down) (monitor_name={}, parents_down={})",
+                    monitor_name,
+                    parents_down.join(", ")
+                );
+            } else if !state.parents_down.is_empty() && parents_down.is_empty() {
+                info!("Monitor is reachable again (monitor_name={})", monitor_name);
+            }
+            state.parents_down = parents_down;
+        }
+    }
+
+    /// Sends the alerts that are due, or adds them to their group, and updates the escalations
+    /// of the monitor.
+    fn dispatch(
+        &mut self,
+        monitor_name: &str,
+        due: DueAlerts,
+        now: DateTime<Utc>,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        for (alert_key, alert, payload) in due.ready {
+            self.delivery.supersede(&alert_key, &payload.monitor_name);
+            let window = match group_window(&alert) {
+                Some(w) => w,
+                None => {
+                    send_alert(
+                        &mut *self.api,
+                        &alert,
+                        &payload,
+                        DeliveryAttempt::First,
+                        ctx,
+                    );
+                    continue;
+                }
+            };
+
+            let uid = format!("group/{}", alert_key);
+            if !self.alert_groups.contains_key(&uid) {
+                debug!(
+                    "Collecting state changes for digest (alert={}, group_window={:?})",
+                    alert_key, window
+                );
+                self.timer.do_send(TimerOnce {
+                    uid: uid.clone(),
+                    recipient: ctx.address().recipient(),
+                    delay: window,
+                });
+            }
+            let group = self.alert_groups.entry(uid).or_insert_with(|| AlertGroup {
+                alert: alert.clone(),
+                payloads: vec![],
+            });
+            group.alert = alert;
+            group.payloads.push(payload);
+        }
+
+        self.update_escalations(monitor_name, due.waiting, now, ctx);
+    }
+
+    /// Sends the state changes collected for an alert once its group window closes. A single
+    /// change is sent as usual, and several as one digest.
+    fn send_group(&mut self, uid: &str, ctx: &mut <Self as Actor>::Context) {
+        let mut group = match self.alert_groups.remove(uid) {
+            Some(g) => g,
+            None => return,
+        };
+
+        let payload = if group.payloads.len() == 1 {
+            group.payloads.remove(0)
+        } else {
+            info!(
+                "Sending digest (alert={}, state_changes={})",
+                uid,
+                group.payloads.len()
+            );
+            AlertPayload::digest(group.payloads)
+        };
+        send_alert(
+            &mut *self.api,
+            &group.alert,
+            &payload,
+            DeliveryAttempt::First,
+            ctx,
+        );
+    }
+
+    /// Sends the failed alerts that are due to be retried, with the settings the alert has in the
+    /// current config.
+    fn retry_deliveries(&mut self, now: DateTime<Utc>, ctx: &mut <Self as Actor>::Context) {
+        if !self.alerts_loaded {
+            return;
+        }
+
+        for pending in self.delivery.take_due(now) {
+            let alert = match self
+                .alerts
+                .iter()
+                .find(|a| alert_key(a) == pending.alert_key)
+            {
+                Some(a) => a.clone(),
+                None => {
+                    warn!(
+                        "Not retrying alert that is no longer configured (alert={}, monitor_name={})",
+                        pending.alert_key, pending.payload.monitor_name
+                    );
+                    self.delivery
+                        .dead_letter(pending.id, "alert is no longer configured", now);
+                    continue;
+                }
+            };
+
+            info!(
+                "Retrying alert (alert={}, monitor_name={}, attempt={})",
+                pending.alert_key,
+                pending.payload.monitor_name,
+                pending.attempts + 1
+            );
+            send_alert(
+                &mut *self.api,
+                &alert,
+                &pending.payload,
+                DeliveryAttempt::Retry(pending.id),
+                ctx,
+            );
+        }
+    }
+
+    /// Records the result of sending an alert. A failed alert is queued to be retried, and once
+    /// it runs out of attempts the fallback alert is told about it.
+    fn delivery_finished(
+        &mut self,
+        alert: models::Alert,
+        payload: AlertPayload,
+        attempt: DeliveryAttempt,
+        result: Result<(), Error>,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        let err = match (result, attempt) {
+            (Ok(()), DeliveryAttempt::Retry(id)) => {
+                info!(
+                    "Retried alert was sent (alert_type={}, id={:?}, monitor_name={})",
+                    alert.type_, alert.id, payload.monitor_name
+                );
+                self.delivery.remove(id);
+                return;
+            }
+            (Ok(()), _) => return,
+            (Err(err), _) => err,
+        };
+
+        warn!(
+            "Failed to send alert (alert_type={}, id={:?}, monitor_name={}, error={})",
+            alert.type_, alert.id, payload.monitor_name, err
+        );
+
+        let id = match attempt {
+            DeliveryAttempt::First => None,
+            DeliveryAttempt::Retry(id) => Some(id),
+            // there is nowhere left to report it
+            DeliveryAttempt::Fallback => return,
+        };
+
+        // a command may have done part of its work before failing, so it is not run again
+        if alert.type_ == "exec" {
+            return;
+        }
+
+        let key = alert_key(&alert);
+        // a newer state change may have gone out while this one was being sent
+        let payload = match self.current_changes(&key, payload) {
+            Some(p) => p,
+            None => {
+                debug!(
+                    "Not retrying alert superseded by a newer state change (alert={})",
+                    key
+                );
+                if let Some(id) = id {
+                    self.delivery.remove(id);
+                }
+                return;
+            }
+        };
+
+        let exhausted = match self
+            .delivery
+            .failed(id, &alert, &key, &payload, &err, Utc::now())
+        {
+            Some(e) => e,
+            None => return,
+        };
+
+        error!(
+            "Giving up on alert after {} attempts (alert_type={}, id={:?}, monitor_name={}, error={})",
+            exhausted.attempts, alert.type_, alert.id, payload.monitor_name, exhausted.last_error
+        );
+
+        let fallback = match self.delivery.fallback_alert() {
+            Some(f) => f.clone(),
+            None => return,
+        };
+        let failure = format!(
+            "The {} alert for monitor {} could not be sent after {} attempts: {}",
+            alert.type_, payload.monitor_name, exhausted.attempts, exhausted.last_error
+        );
+        // the notice is always DOWN, so a failed recovery does not close an incident on the
+        // fallback
+        let mut notice = AlertPayload {
+            monitor_description: Some(failure.clone()),
+            delivery_failure: Some(failure),
+            digest: vec![],
+            ..payload
+        };
+        notice.status.status = models::MonitorStatusIndicator::DOWN;
+        send_alert(
+            &mut *self.api,
+            &fallback,
+            &notice,
+            DeliveryAttempt::Fallback,
+            ctx,
+        );
+    }
+
+    /// The state changes in the payload that are still the latest the alert has sent for their
+    /// monitor, as a DOWN that has been followed by its recovery is not worth retrying. A monitor
+    /// without a status yet, such as after a restart, keeps its change.
+    fn current_changes(&self, alert_key: &str, payload: AlertPayload) -> Option<AlertPayload> {
+        let is_current = |change: &AlertPayload| match self.statuses.get(&change.monitor_name) {
+            Some(state) => {
+                state.alerts_fired.contains_key(alert_key)
+                    == (change.status.status == models::MonitorStatusIndicator::DOWN)
+            }
+            None => true,
+        };
+
+        if payload.digest.is_empty() {
+            return Some(payload).filter(is_current);
+        }
+        let mut changes = payload.digest;
+        changes.retain(is_current);
+        match changes.len() {
+            0 => None,
+            1 => Some(changes.remove(0)),
+            _ => Some(AlertPayload::digest(changes)),
+        }
+    }
+
+    /// Sets a timer for each alert of the monitor that is waiting to escalate, and cancels the
+    /// timers of alerts that no longer wait, such as when the monitor has recovered.
+    fn update_escalations(
+        &mut self,
+        monitor_name: &str,
+        waiting: Vec<(String, DateTime<Utc>)>,
+        now: DateTime<Utc>,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        let waiting: HashMap<String, PendingEscalation> = waiting
+            .into_iter()
+            .map(|(alert_key, due)| {
+                (
+                    format!("escalation/{}/{}", monitor_name, alert_key),
+                    PendingEscalation {
+                        monitor_name: monitor_name.to_owned(),
+                        alert_key,
+                        due,
+                    },
+                )
+            })
+            .collect();
+
+        let cancelled: Vec<String> = self
+            .pending_escalations
+            .iter()
+            .filter(|(uid, pending)| {
+                pending.monitor_name == monitor_name && !waiting.contains_key(*uid)
+            })
+            .map(|(uid, _)| uid.clone())
+            .collect();
+        for uid in cancelled {
+            if let Some(pending) = self.pending_escalations.remove(&uid) {
+                info!(
+                    "Cancelled escalation (monitor_name={}, alert={})",
+                    monitor_name, pending.alert_key
+                );
+            }
+            self.timer.do_send(TimerCancel { uid });
+        }
+
+        for (uid, pending) in waiting {
+            if let Some(existing) = self.pending_escalations.get(&uid) {
+                if existing.due == pending.due {
+                    continue;
+                }
+            }
+
+            info!(
+                "Escalating alert if monitor stays DOWN (monitor_name={}, alert={}, due={})",
+                monitor_name, pending.alert_key, pending.due
+            );
+            self.timer.do_send(TimerOnce {
+                uid: uid.clone(),
+                recipient: ctx.address().recipient(),
+                delay: (pending.due - now).to_std().unwrap_or_default(),
+            });
+            self.pending_escalations.insert(uid, pending);
+        }
+    }
+
+    /// The names of the monitors this one depends on whose latest status is DOWN. While any of
+    /// them is DOWN the monitor is unreachable, and only the parent is alerted on. The latest
+    /// status of a parent in the batch counts, even if it is processed after this monitor.
+    fn parents_down(
+        &self,
+        monitor: &models::Monitor,
+        batch: &[(models::Monitor, models::MonitorStatus)],
+    ) -> Vec<String> {
+        let is_parent = |parent: &str, candidate: &models::Monitor| {
+            candidate.name != monitor.name
+                && (candidate.name == parent || candidate.id.as_deref() == Some(parent))
+        };
+        let mut parents_down: Vec<String> = monitor
+            .depends_on
+            .iter()
+            .flatten()
+            .filter_map(|parent| {
+                let in_batch = batch
+                    .iter()
+                    .rev()
+                    .find(|(candidate, _)| is_parent(parent, candidate))
+                    .map(|(candidate, status)| (candidate, status));
+                in_batch.or_else(|| {
+                    self.statuses
+                        .values()
+                        .find(|state| is_parent(parent, &state.monitor))
+                        .map(|state| (&state.monitor, &state.last_status))
+                })
+            })
+            .filter(|(_, status)| status.status == models::MonitorStatusIndicator::DOWN)
+            .map(|(parent, _)| parent.name.clone())
+            .collect();
+        parents_down.sort();
+        parents_down.dedup();
+        parents_down
+    }
+
+    /// All silences with their IDs. Silences in the config without an ID are identified by
+    /// their position.
+    fn silences(&self) -> Vec<(String, &Silence)> {
+        self.config_silences
+            .iter()
+            .enumerate()
+            .map(|(index, silence)| {
+                let id = match silence.id {
+                    Some(ref id) => id.clone(),
+                    None => format!("config#{}", index),
+                };
+                (id, silence)
+            })
+            .chain(
+                self.runtime_silences
+                    .iter()
+                    .map(|silence| (silence.id.clone().unwrap_or_default(), silence)),
+            )
+            .collect()
+    }
+
+    /// The ID of an active silence that covers the monitor, if any.
+    fn silence_for(&self, monitor: &models::Monitor, at: DateTime<Utc>) -> Option<String> {
+        self.silences()
+            .into_iter()
+            .find(|(_, silence)| silence.is_active(at) && silence.matches(monitor))
+            .map(|(id, _)| id)
+    }
+
+    /// Finds the silences that have ended since the last check. The monitors they covered are
+    /// looked at again, and each alert sends one digest of the monitors that are still DOWN
+    /// rather than a message per monitor.
+    fn check_silences(&mut self, now: DateTime<Utc>, ctx: &mut <Self as Actor>::Context) {
+        let active: HashMap<String, Silence> = self
+            .silences()
+            .into_iter()
+            .filter(|(_, silence)| silence.is_active(now))
+            .map(|(id, silence)| (id, silence.clone()))
+            .collect();
+
+        let mut ended: Vec<(String, Silence)> = self
+            .active_silences
+            .iter()
+            .filter(|(id, _)| !active.contains_key(*id))
+            .map(|(id, silence)| (id.clone(), silence.clone()))
+            .collect();
+        ended.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (id, silence) in active.iter() {
+            if !self.active_silences.contains_key(id) {
+                info!(
+                    "Silence started (silence_id={}, reason={})",
+                    id, silence.reason
+                );
+            }
+        }
+
+        self.active_silences = active;
+        self.runtime_silences
+            .retain(|silence| !silence.has_ended(now));
+
+        for (id, silence) in ended {
+            let mut still_down = vec![];
+            let mut ready: Vec<(String, models::Alert, AlertPayload)> = vec![];
+            let mut monitor_names: Vec<String> = self.statuses.keys().cloned().collect();
+            monitor_names.sort();
+
+            for monitor_name in monitor_names {
+                let state = match self.statuses.get_mut(&monitor_name) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                let still_silenced = self
+                    .active_silences
+                    .values()
+                    .any(|s| s.matches(&state.monitor));
+                if !silence.matches(&state.monitor)
+                    || still_silenced
+                    || state.flapping
+                    || !state.parents_down.is_empty()
+                {
+                    continue;
+                }
+
+                if state.last_status.status == models::MonitorStatusIndicator::DOWN {
+                    still_down.push(monitor_name.clone());
                 }
-                None => unreachable!(
-                    "Could not load status that was just inserted (monitor_name={})",
-                    status.monitor_name
-                ),
+                let due = due_alerts(&self.alerts, state, false, now);
+                ready.extend(due.ready);
+                self.update_escalations(&monitor_name, due.waiting, now, ctx);
+            }
+
+            info!(
+                "Silence ended (silence_id={}, reason={}, still_down=[{}])",
+                id,
+                silence.reason,
+                still_down.join(", ")
+            );
+            self.send_held_back(ready, ctx);
+        }
+    }
+
+    /// Sends the alerts held back by a silence that has ended. The payloads of each alert are
+    /// sent as one digest, except by alert types that cannot send a digest.
+    fn send_held_back(
+        &mut self,
+        ready: Vec<(String, models::Alert, AlertPayload)>,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        let mut groups: Vec<(String, AlertGroup)> = vec![];
+        for (alert_key, alert, payload) in ready {
+            self.delivery.supersede(&alert_key, &payload.monitor_name);
+            match groups.iter_mut().find(|(key, _)| *key == alert_key) {
+                Some((_, group)) => group.payloads.push(payload),
+                None => groups.push((
+                    alert_key,
+                    AlertGroup {
+                        alert,
+                        payloads: vec![payload],
+                    },
+                )),
             }
         }
 
-        self.status_buffer = vec![];
+        for (alert_key, group) in groups {
+            let payloads = if group.payloads.len() > 1
+                && DIGEST_ALERT_TYPES.contains(&group.alert.type_.as_str())
+            {
+                info!(
+                    "Sending digest of alerts held back by silence (alert={}, state_changes={})",
+                    alert_key,
+                    group.payloads.len()
+                );
+                vec![AlertPayload::digest(group.payloads)]
+            } else {
+                group.payloads
+            };
+
+            for payload in payloads {
+                send_alert(
+                    &mut *self.api,
+                    &group.alert,
+                    &payload,
+                    DeliveryAttempt::First,
+                    ctx,
+                );
+            }
+        }
     }
 }
 
-#[allow(dead_code)]
-fn perform_state_change_action(
-    api: &mut dyn AlertApi,
-    state: &MonitorState,
-    ctx: &mut <AlerterActor as Actor>::Context,
+/// The alerts of a monitor that are ready to send, and those waiting to escalate.
+#[derive(Default)]
+struct DueAlerts {
+    /// Alert key, alert and payload of each alert to send
+    ready: Vec<(String, models::Alert, AlertPayload)>,
+    /// Alert key and due time of each alert waiting for its escalation delay
+    waiting: Vec<(String, DateTime<Utc>)>,
+}
+
+/// The state changes collected for an alert during its group window.
+struct AlertGroup {
+    alert: models::Alert,
+    payloads: Vec<AlertPayload>,
+}
+
+/// Finds the alerts for the latest status of the monitor that have reached their threshold and
+/// escalation delay, are due to repeat, or recover, and marks them as sent. When the monitor has
+/// just started flapping, each alert sends a single notice instead.
+fn due_alerts(
     alerts: &[models::Alert],
-) {
-    let status = &state.last_status;
-    let payload = AlertPayload {
-        monitor_name: status.monitor_name.to_owned(),
-        //monitor_name: state.monitor.name.to_owned(),
-        status: status.clone(),
-        node_info: get_node_info(),
-    };
+    state: &mut MonitorState,
+    flapping_started: bool,
+    now: DateTime<Utc>,
+) -> DueAlerts {
+    let monitor = state.monitor.clone();
+    let status = state.last_status.clone();
+    let mut due = DueAlerts::default();
+
     for alert in alerts.iter() {
-        let alert_future = match alert.type_.as_ref() {
-            "email" => {
-                info!("Sending email for alert (id={:?})", alert.id);
-                api.send_email(
-                    &models::EmailAlertBody {
-                        from: alert.body.from.clone(),
-                        recipients: alert.body.recipients.clone(),
-                        host: alert.body.host.clone(),
-                        port: alert.body.port,
-                        tls_mode: alert.body.tls_mode,
-                        username: alert.body.username.clone(),
-                        password: alert.body.password.clone(),
+        if !alert.enabled || !alert_applies_to(alert, &monitor) {
+            continue;
+        }
+
+        let alert_key = alert_key(alert);
+        let fired = state.alerts_fired.get(&alert_key).cloned();
+
+        // a flapping monitor gets a single notice, sent as DOWN so that the recovery
+        // closes it once the monitor settles
+        let indicator = if flapping_started {
+            models::MonitorStatusIndicator::DOWN
+        } else {
+            status.status
+        };
+
+        let should_send = match (indicator, &fired) {
+            _ if flapping_started => true,
+            (models::MonitorStatusIndicator::DOWN, None)
+                if state.consecutive_down < down_threshold(alert) =>
+            {
+                false
+            }
+            (models::MonitorStatusIndicator::DOWN, None) => match escalate_after(alert) {
+                Some(delay) => {
+                    let due_at = state.down_since.unwrap_or(status.timestamp) + delay;
+                    if now < due_at {
+                        due.waiting.push((alert_key.clone(), due_at));
+                    }
+                    now >= due_at
+                }
+                None => true,
+            },
+            (models::MonitorStatusIndicator::DOWN, Some(fired)) => match repeat_interval(alert) {
+                Some(interval) => status.timestamp - fired.last_sent >= interval,
+                None => false,
+            },
+            (models::MonitorStatusIndicator::OK, Some(_)) => {
+                state.consecutive_ok >= recovery_threshold(alert)
+            }
+            (models::MonitorStatusIndicator::OK, None) => false,
+        };
+
+        if !should_send {
+            continue;
+        }
+
+        let (down_since, downtime) = match indicator {
+            models::MonitorStatusIndicator::DOWN => {
+                let down_since = fired
+                    .map(|f| f.down_since)
+                    .or(state.down_since)
+                    .unwrap_or(status.timestamp);
+                state.alerts_fired.insert(
+                    alert_key.clone(),
+                    FiredAlert {
+                        down_since,
+                        last_sent: status.timestamp,
                     },
-                    &payload,
-                )
+                );
+                (down_since, status.timestamp - down_since)
             }
-            "msTeamsMessage" => api.send_msteams_msg(
-                &models::MsTeamsAlertBody {
-                    url: alert.body.url.to_owned(),
+            models::MonitorStatusIndicator::OK => {
+                state.alerts_fired.remove(&alert_key);
+                // fired is always set for a recovery
+                let down_since = fired.map(|f| f.down_since).unwrap_or(status.timestamp);
+                let up_since = state.up_since.unwrap_or(status.timestamp);
+                (down_since, up_since - down_since)
+            }
+        };
+
+        info!(
+            "Sending alert (monitor_name={}, alert={}, status={}, flapping={}, consecutive_down={}, consecutive_ok={}, down_since={})",
+            monitor.name, alert_key, indicator, flapping_started, state.consecutive_down, state.consecutive_ok, down_since
+        );
+
+        let mut alert_status = status.clone();
+        alert_status.status = indicator;
+
+        let payload = AlertPayload {
+            monitor_name: monitor.name.clone(),
+            monitor_description: monitor.description.clone(),
+            status: alert_status,
+            node_info: get_node_info(),
+            down_since: Some(down_since),
+            downtime: Some(downtime.to_std().unwrap_or_default()),
+            flapping: flapping_started,
+            digest: vec![],
+            delivery_failure: None,
+        };
+
+        due.ready.push((alert_key, alert.clone(), payload));
+    }
+
+    due
+}
+
+/// Identifies an alert in the alert state of a monitor. Alerts without an ID are identified by
+/// their type and a hash of where they send to, so the key stays the same when other alerts are
+/// added or reordered, or when the alert's other settings, such as its templates, are edited.
+pub fn alert_key(alert: &models::Alert) -> String {
+    match alert.id {
+        Some(ref id) => id.clone(),
+        None => {
+            let body = &alert.body;
+            let destination = serde_json::json!([
+                body.url,
+                body.recipients,
+                body.channel,
+                body.routing_key,
+                body.api_key,
+                body.responders,
+                body.target,
+                body.path,
+                body.address,
+                body.command,
+                body.args,
+            ]);
+            let hash: String = Sha256::digest(destination.to_string().as_bytes())
+                .iter()
+                .take(8)
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            format!("{}#{}", alert.type_, hash)
+        }
+    }
+}
+
+/// Whether the alert covers the monitor. The monitors of an alert are listed by name or ID, and
+/// an alert without any monitors listed covers every monitor.
+fn alert_applies_to(alert: &models::Alert, monitor: &models::Monitor) -> bool {
+    alert.monitors.is_empty()
+        || alert
+            .monitors
+            .iter()
+            .any(|id| **id == monitor.name || monitor.id.as_deref() == Some(id.as_str()))
+}
+
+/// The number of DOWN results in a row before the alert is sent. At least 1.
+fn down_threshold(alert: &models::Alert) -> usize {
+    alert.threshold.max(1) as usize
+}
+
+/// How long after the last DOWN alert it is sent again while the monitor stays DOWN, if ever.
+fn repeat_interval(alert: &models::Alert) -> Option<chrono::Duration> {
+    match duration::alert_repeat_interval(alert) {
+        Ok(interval) => interval.and_then(|i| chrono::Duration::from_std(i).ok()),
+        Err(err) => {
+            error!("Not repeating alert: {}", err);
+            None
+        }
+    }
+}
+
+/// How long the monitor stays DOWN before the alert is sent, if it waits at all.
+fn escalate_after(alert: &models::Alert) -> Option<chrono::Duration> {
+    match duration::alert_escalate_after(alert) {
+        Ok(delay) => delay.and_then(|d| chrono::Duration::from_std(d).ok()),
+        Err(err) => {
+            error!("Not delaying alert: {}", err);
+            None
+        }
+    }
+}
+
+/// How long the alert collects state changes for before sending a digest, if it groups them.
+fn group_window(alert: &models::Alert) -> Option<Duration> {
+    match duration::alert_group_window(alert) {
+        Ok(Some(_)) if !DIGEST_ALERT_TYPES.contains(&alert.type_.as_str()) => {
+            error!(
+                "Not grouping alert. Only {} alerts can send a digest (type={})",
+                DIGEST_ALERT_TYPES.join(", "),
+                alert.type_
+            );
+            None
+        }
+        Ok(window) => window,
+        Err(err) => {
+            error!("Not grouping alert: {}", err);
+            None
+        }
+    }
+}
+
+/// The number of OK results in a row before the recovery is sent. At least 1.
+fn recovery_threshold(alert: &models::Alert) -> usize {
+    alert.recovery_threshold.unwrap_or(1).max(1) as usize
+}
+
+/// Sends the alert, and hands the result back to the alerter so a failure can be retried.
+fn send_alert(
+    api: &mut dyn AlertApi,
+    alert: &models::Alert,
+    payload: &AlertPayload,
+    attempt: DeliveryAttempt,
+    ctx: &mut <AlerterActor as Actor>::Context,
+) {
+    let alert_future = match alert.type_.as_ref() {
+        "email" => {
+            info!("Sending email for alert (id={:?})", alert.id);
+            api.send_email(
+                &models::EmailAlertBody {
+                    from: alert.body.from.clone(),
+                    recipients: alert.body.recipients.clone(),
+                    host: alert.body.host.clone(),
+                    port: alert.body.port,
+                    tls_mode: alert.body.tls_mode,
+                    username: alert.body.username.clone(),
+                    password: alert.body.password.clone(),
+                    subject_template: alert.body.subject_template.clone(),
+                    body_template: alert.body.body_template.clone(),
                 },
-                &payload,
-            ),
-            "webhook" => api.send_webhook(
-                &models::WebhookAlertBody {
-                    headers: alert.body.headers.clone(),
-                    url: alert.body.url.to_owned(),
+                payload,
+            )
+        }
+        "msTeamsMessage" => api.send_msteams_msg(
+            &models::MsTeamsAlertBody {
+                url: alert.body.url.to_owned(),
+                subject_template: alert.body.subject_template.clone(),
+                body_template: alert.body.body_template.clone(),
+            },
+            payload,
+        ),
+        "webhook" => api.send_webhook(
+            &models::WebhookAlertBody {
+                headers: alert.body.headers.clone(),
+                url: alert.body.url.to_owned(),
+                body_template: alert.body.body_template.clone(),
+                secret: alert.body.secret.clone(),
+                signature_header: alert.body.signature_header.clone(),
+            },
+            payload,
+        ),
+        "slack" => api.send_slack_msg(
+            &models::SlackAlertBody {
+                url: alert.body.url.to_owned(),
+                token: alert.body.token.clone(),
+                channel: alert.body.channel.clone(),
+                subject_template: alert.body.subject_template.clone(),
+                body_template: alert.body.body_template.clone(),
+            },
+            payload,
+        ),
+        "pagerduty" => api.send_pagerduty_event(
+            &models::PagerDutyAlertBody {
+                url: alert.body.url.to_owned(),
+                routing_key: alert.body.routing_key.clone(),
+                severity: alert.body.severity.clone(),
+                subject_template: alert.body.subject_template.clone(),
+                body_template: alert.body.body_template.clone(),
+            },
+            payload,
+        ),
+        "opsgenie" => api.send_opsgenie_alert(
+            &models::OpsgenieAlertBody {
+                url: alert.body.url.to_owned(),
+                api_key: alert.body.api_key.clone(),
+                priority: alert.body.priority.clone(),
+                responders: alert.body.responders.clone(),
+                subject_template: alert.body.subject_template.clone(),
+                body_template: alert.body.body_template.clone(),
+            },
+            payload,
+        ),
+        "log" => match alert.body.target {
+            Some(ref target) => api.send_log_record(
+                &models::LogAlertBody {
+                    target: target.clone(),
+                    path: alert.body.path.clone(),
+                    address: alert.body.address.clone(),
+                    max_size: alert.body.max_size,
+                    max_files: alert.body.max_files,
                 },
-                &payload,
+                payload,
             ),
-            "log" => {
-                return; // TODO
+            None => {
+                error!(
+                    "Log alert is misconfigured. It has no target (id={:?})",
+                    alert.id
+                );
+                return;
             }
-            _ => {
-                error!("Severe error. Unkown alert type (type={})", alert.type_);
+        },
+        "exec" => match alert.body.command {
+            Some(ref command) => api.run_exec_command(
+                &models::ExecAlertBody {
+                    command: command.clone(),
+                    args: alert.body.args.clone(),
+                    timeout: alert.body.timeout.clone(),
+                },
+                payload,
+            ),
+            None => {
+                error!(
+                    "Exec alert is misconfigured. It has no command (id={:?})",
+                    alert.id
+                );
                 return;
             }
-        };
+        },
+        _ => {
+            error!("Severe error. Unkown alert type (type={})", alert.type_);
+            return;
+        }
+    };
 
-        ctx.spawn(actix::fut::wrap_future::<_, AlerterActor>(alert_future).map(|_, _, _| ()));
-    }
+    let alert = alert.clone();
+    let payload = payload.clone();
+    ctx.spawn(
+        actix::fut::wrap_future::<_, AlerterActor>(alert_future).map(move |result, actor, ctx| {
+            actor.delivery_finished(alert, payload, attempt, result, ctx)
+        }),
+    );
 }
 
 impl Actor for AlerterActor {
     type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.run_interval(Duration::from_secs(SILENCE_CHECK_SEC), |this, ctx| {
+            this.check_silences(Utc::now(), ctx)
+        });
+        ctx.run_interval(Duration::from_secs(DELIVERY_CHECK_SEC), |this, ctx| {
+            this.retry_deliveries(Utc::now(), ctx)
+        });
+    }
 }
 
 impl Handler<StatusMsg> for AlerterActor {
@@ -146,29 +1056,137 @@ impl Handler<StatusMsg> for AlerterActor {
             "Received status update(s) (monitor_name={}, status={}",
             msg.monitor.name, msg.status.status
         );
-        self.status_buffer.push((msg.monitor, msg.status));
-        self.process_state_change(ctx);
+        if self.batch_window.as_millis() == 0 {
+            self.status_buffer.push((msg.monitor, msg.status));
+            self.process_state_change(ctx);
+        } else {
+            if self.status_buffer.is_empty() {
+                ctx.run_later(self.batch_window, |this, ctx| {
+                    this.process_state_change(ctx)
+                });
+            }
+            self.status_buffer.push((msg.monitor, msg.status));
+        }
 
         Ok(())
     }
 }
 
 impl MonitorState {
-    fn new(_monitor: &models::Monitor, last_status: &models::MonitorStatus) -> Self {
+    fn new(monitor: &models::Monitor, last_status: &models::MonitorStatus) -> Self {
         Self {
-            last_timestamp: Utc::now(), // TODO
+            monitor: monitor.clone(),
+            last_timestamp: last_status.timestamp,
             last_status: last_status.clone(),
-            //monitor: monitor.clone(),
-            is_new: true,
+            consecutive_down: 0,
+            consecutive_ok: 0,
+            down_since: None,
+            up_since: None,
+            alerts_fired: HashMap::new(),
+            history: VecDeque::new(),
+            state_change_percent: None,
+            flapping: false,
+            parents_down: vec![],
+        }
+    }
+
+    /// Counts the status towards the DOWN or OK streak of the monitor.
+    fn record(&mut self, monitor: &models::Monitor, status: &models::MonitorStatus) {
+        match status.status {
+            models::MonitorStatusIndicator::DOWN => {
+                if self.consecutive_down == 0 {
+                    self.down_since = Some(status.timestamp);
+                }
+                self.consecutive_down += 1;
+                self.consecutive_ok = 0;
+            }
+            models::MonitorStatusIndicator::OK => {
+                if self.consecutive_ok == 0 {
+                    self.up_since = Some(status.timestamp);
+                }
+                self.consecutive_ok += 1;
+                self.consecutive_down = 0;
+            }
+        }
+        self.monitor = monitor.clone();
+        self.last_timestamp = status.timestamp;
+        self.last_status = status.clone();
+        self.history.push_back(status.status);
+    }
+
+    /// Updates the flapping state from the results in the window. The monitor starts flapping
+    /// at the high threshold and stops below the low threshold, so it does not bounce in and
+    /// out of flapping.
+    fn detect_flapping(&mut self, settings: &FlapDetection) {
+        while self.history.len() > settings.window {
+            self.history.pop_front();
+        }
+
+        if !settings.enabled {
+            self.state_change_percent = None;
+            self.flapping = false;
+            return;
+        }
+
+        self.state_change_percent = state_change_percent(&self.history, settings.window);
+        if let Some(percent) = self.state_change_percent {
+            if !self.flapping && percent >= settings.high_threshold {
+                self.flapping = true;
+            } else if self.flapping && percent < settings.low_threshold {
+                self.flapping = false;
+            }
         }
     }
 }
 
+/// The weighted percentage of results that changed state, once the window is full. As in
+/// Nagios, the weights rise from 0.8 for the oldest change to 1.2 for the newest, so recent
+/// changes count for more.
+fn state_change_percent(
+    history: &VecDeque<models::MonitorStatusIndicator>,
+    window: usize,
+) -> Option<f64> {
+    if window < 3 || history.len() < window {
+        return None;
+    }
+
+    let changes = (history.len() - 1) as f64;
+    let weighted: f64 = (1..history.len())
+        .filter(|i| history[*i] != history[i - 1])
+        .map(|i| 0.8 + 0.4 * (i - 1) as f64 / (changes - 1.0))
+        .sum();
+
+    Some(100.0 * weighted / changes)
+}
+
 struct MonitorState {
+    monitor: models::Monitor,
     last_timestamp: DateTime<Utc>,
     last_status: models::MonitorStatus,
-    //monitor: models::Monitor,
-    is_new: bool,
+    /// DOWN results in a row, reset by an OK result
+    consecutive_down: usize,
+    /// OK results in a row, reset by a DOWN result
+    consecutive_ok: usize,
+    /// Start of the current DOWN streak
+    down_since: Option<DateTime<Utc>>,
+    /// Start of the current OK streak
+    up_since: Option<DateTime<Utc>>,
+    /// Alerts that have sent DOWN and have not yet sent the recovery, keyed by alert key
+    alerts_fired: HashMap<String, FiredAlert>,
+    /// The latest results, as many as the flap detection window
+    history: VecDeque<models::MonitorStatusIndicator>,
+    state_change_percent: Option<f64>,
+    flapping: bool,
+    /// The monitors it depends on that are DOWN, as of the latest batch of statuses
+    parents_down: Vec<String>,
+}
+
+#[derive(Clone)]
+struct FiredAlert {
+    /// When the outage the alert was sent for began
+    down_since: DateTime<Utc>,
+    /// When the DOWN alert was last sent, first or repeated
+    last_sent: DateTime<Utc>,
 }
 
 fn get_node_info() -> NodeInfo {
@@ -205,18 +1223,1404 @@ fn get_node_info() -> NodeInfo {
     }
 }
 
+impl Handler<MonitorRemoved> for AlerterActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: MonitorRemoved, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(source_id) = self.monitor_sources.get(&msg.monitor_name) {
+            if *source_id != msg.source_id {
+                debug!(
+                    "Monitor now belongs to another source. Keeping its alert state (monitor_name={}, source_id={})",
+                    msg.monitor_name, source_id
+                );
+                return Ok(());
+            }
+        }
+
+        debug!(
+            "Dropping alert state for monitor (monitor_name={})",
+            msg.monitor_name
+        );
+        self.monitor_sources.remove(&msg.monitor_name);
+        self.statuses.remove(&msg.monitor_name);
+        self.status_buffer
+            .retain(|(monitor, _)| monitor.name != msg.monitor_name);
+        Ok(())
+    }
+}
+
+impl Handler<MonitorUpdate> for AlerterActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: MonitorUpdate, _ctx: &mut Self::Context) -> Self::Result {
+        self.monitor_sources.insert(msg.monitor.name, msg.source_id);
+        Ok(())
+    }
+}
+
+impl Handler<CurrentConfig> for AlerterActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, config_msg: CurrentConfig, ctx: &mut Self::Context) -> Self::Result {
+        debug!("Handling latest config for alerter");
+        self.flap_detection = config_msg.config.flap_detection;
+        self.config_silences = config_msg.config.silences;
+        let mut alert_delivery = config_msg.config.alert_delivery;
+        if let Some(ref mut fallback_alert) = alert_delivery.fallback_alert {
+            apply_templates(&config_msg.config.templates, fallback_alert);
+        }
+        self.delivery.configure(alert_delivery);
+        self.check_silences(Utc::now(), ctx);
+        Ok(())
+    }
+}
+
+/// Adds a silence while the agent runs, and returns its ID. A silence with a duration but no
+/// schedule lasts that long from its start, or from now.
 #[derive(Clone, Debug, Message)]
-#[rtype(result = "()")]
-pub struct AlertUpdate {
-    pub uid: String,
-    pub alerts: Vec<models::Alert>,
+#[rtype(result = "Result<String, Error>")]
+pub struct AddSilence {
+    pub silence: Silence,
 }
 
-impl Handler<AlertUpdate> for AlerterActor {
-    type Result = ();
+impl Handler<AddSilence> for AlerterActor {
+    type Result = Result<String, Error>;
 
-    fn handle(&mut self, msg: AlertUpdate, _ctx: &mut Self::Context) -> Self::Result {
-        debug!("Handling alerts");
+    fn handle(&mut self, msg: AddSilence, ctx: &mut Self::Context) -> Self::Result {
+        let now = Utc::now();
+        let mut silence = msg.silence;
+
+        if silence.schedule.is_none() {
+            if let Some(length) = silence.duration.take() {
+                let length = chrono::Duration::from_std(duration::parse_duration(&length)?)
+                    .map_err(|_| Error::new(format!("Silence duration {} is too long", length)))?;
+                silence.end = Some(silence.start.unwrap_or(now) + length);
+            }
+        }
+        silence.validate()?;
+
+        let id = match silence.id {
+            Some(ref id) => id.clone(),
+            None => {
+                self.next_silence_id += 1;
+                format!("silence-{}", self.next_silence_id)
+            }
+        };
+        if self.silences().iter().any(|(existing, _)| *existing == id) {
+            return Err(Error::new(format!("Silence {} already exists", id)));
+        }
+        silence.id = Some(id.clone());
+
+        info!(
+            "Adding silence (silence_id={}, reason={})",
+            id, silence.reason
+        );
+        self.runtime_silences.push(silence);
+        self.check_silences(now, ctx);
+
+        Ok(id)
+    }
+}
+
+/// Removes a silence that was added while the agent runs. Anything it covered that is still
+/// DOWN gets its alert.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<(), Error>")]
+pub struct RemoveSilence {
+    pub id: String,
+}
+
+impl Handler<RemoveSilence> for AlerterActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: RemoveSilence, ctx: &mut Self::Context) -> Self::Result {
+        let count = self.runtime_silences.len();
+        self.runtime_silences
+            .retain(|silence| silence.id.as_deref() != Some(msg.id.as_str()));
+
+        if self.runtime_silences.len() == count {
+            let in_config = self.silences().iter().any(|(id, _)| *id == msg.id);
+            return Err(Error::new(if in_config {
+                format!("Silence {} is in the config and cannot be removed", msg.id)
+            } else {
+                format!("There is no silence {}", msg.id)
+            }));
+        }
+
+        info!("Removed silence (silence_id={})", msg.id);
+        self.check_silences(Utc::now(), ctx);
+        Ok(())
+    }
+}
+
+/// Asks for every silence and whether it is active.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Vec<SilenceStatus>")]
+pub struct GetSilences;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SilenceStatus {
+    #[serde(flatten)]
+    pub silence: Silence,
+    pub active: bool,
+    pub from_config: bool,
+}
+
+impl Handler<GetSilences> for AlerterActor {
+    type Result = Vec<SilenceStatus>;
+
+    fn handle(&mut self, _msg: GetSilences, _ctx: &mut Self::Context) -> Self::Result {
+        let now = Utc::now();
+        let config_count = self.config_silences.len();
+        self.silences()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (id, silence))| {
+                let mut silence = silence.clone();
+                silence.id = Some(id);
+                SilenceStatus {
+                    active: silence.is_active(now),
+                    from_config: index < config_count,
+                    silence,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Sends the digest of an alert whose group window has closed, or escalates an alert whose
+/// monitor has been DOWN for its delay unless its alerts are being held back.
+impl Handler<Timeout> for AlerterActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: Timeout, ctx: &mut Self::Context) -> Self::Result {
+        if self.alert_groups.contains_key(&msg.uid) {
+            self.send_group(&msg.uid, ctx);
+            return Ok(());
+        }
+
+        let pending = match self.pending_escalations.remove(&msg.uid) {
+            Some(p) => p,
+            None => {
+                debug!(
+                    "Ignoring timeout for escalation that is no longer pending (uid={})",
+                    msg.uid
+                );
+                return Ok(());
+            }
+        };
+
+        let (monitor, unreachable) = match self.statuses.get(&pending.monitor_name) {
+            Some(state) => (state.monitor.clone(), !state.parents_down.is_empty()),
+            None => return Ok(()),
+        };
+        if let Some(silence_id) = self.silence_for(&monitor, pending.due) {
+            debug!(
+                "Not escalating alert for silenced monitor (monitor_name={}, alert={}, silence_id={})",
+                monitor.name, pending.alert_key, silence_id
+            );
+            return Ok(());
+        }
+        if unreachable {
+            debug!(
+                "Not escalating alert for unreachable monitor (parent down) (monitor_name={}, alert={})",
+                monitor.name, pending.alert_key
+            );
+            return Ok(());
+        }
+
+        let state = match self.statuses.get_mut(&pending.monitor_name) {
+            Some(state) if !state.flapping => state,
+            _ => return Ok(()),
+        };
+        let due = due_alerts(&self.alerts, state, false, pending.due);
+        self.dispatch(&pending.monitor_name, due, pending.due, ctx);
+
+        Ok(())
+    }
+}
+
+/// Asks for the alert state of every monitor the alerter has seen.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Vec<MonitorAlertStatus>")]
+pub struct GetAlerterStatus;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MonitorAlertStatus {
+    pub monitor_name: String,
+    pub status: models::MonitorStatusIndicator,
+    pub flapping: bool,
+    /// Weighted percentage of recent results that changed state, once there are enough results
+    pub state_change_percent: Option<f64>,
+    pub consecutive_down: usize,
+    pub consecutive_ok: usize,
+    /// Alerts that have sent DOWN and have not yet sent the recovery
+    pub alerts_fired: Vec<String>,
+    /// The active silence that holds back the alerts of the monitor
+    pub silenced_by: Option<String>,
+    /// Alerts that are sent if the monitor stays DOWN until their escalation delay
+    pub pending_escalations: Vec<String>,
+    /// Whether the monitor is unreachable (parent down), so its alerts are held back
+    pub unreachable: bool,
+    /// The monitors it depends on that are DOWN
+    pub parents_down: Vec<String>,
+}
+
+impl Handler<GetAlerterStatus> for AlerterActor {
+    type Result = Vec<MonitorAlertStatus>;
+
+    fn handle(&mut self, _msg: GetAlerterStatus, _ctx: &mut Self::Context) -> Self::Result {
+        let mut statuses: Vec<MonitorAlertStatus> = self
+            .statuses
+            .iter()
+            .map(|(monitor_name, state)| {
+                let mut alerts_fired: Vec<String> = state.alerts_fired.keys().cloned().collect();
+                alerts_fired.sort();
+                let mut pending_escalations: Vec<String> = self
+                    .pending_escalations
+                    .values()
+                    .filter(|pending| pending.monitor_name == *monitor_name)
+                    .map(|pending| pending.alert_key.clone())
+                    .collect();
+                pending_escalations.sort();
+                MonitorAlertStatus {
+                    monitor_name: monitor_name.clone(),
+                    status: state.last_status.status,
+                    flapping: state.flapping,
+                    state_change_percent: state.state_change_percent,
+                    consecutive_down: state.consecutive_down,
+                    consecutive_ok: state.consecutive_ok,
+                    alerts_fired,
+                    silenced_by: self
+                        .active_silences
+                        .iter()
+                        .find(|(_, silence)| silence.matches(&state.monitor))
+                        .map(|(id, _)| id.clone()),
+                    pending_escalations,
+                    unreachable: !state.parents_down.is_empty(),
+                    parents_down: state.parents_down.clone(),
+                }
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.monitor_name.cmp(&b.monitor_name));
+        statuses
+    }
+}
+
+/// Asks for the alerts waiting to be retried.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "DeliveryStatus")]
+pub struct GetDeliveryStatus;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DeliveryStatus {
+    pub pending: Vec<PendingDeliveryStatus>,
+    /// How many alerts were given up on since the agent started
+    pub dead_lettered: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PendingDeliveryStatus {
+    pub id: u64,
+    pub alert_key: String,
+    pub alert_type: String,
+    pub monitor_name: String,
+    pub attempts: usize,
+    pub next_attempt: DateTime<Utc>,
+    pub last_error: String,
+}
+
+impl Handler<GetDeliveryStatus> for AlerterActor {
+    type Result = MessageResult<GetDeliveryStatus>;
+
+    fn handle(&mut self, _msg: GetDeliveryStatus, _ctx: &mut Self::Context) -> Self::Result {
+        MessageResult(DeliveryStatus {
+            pending: self
+                .delivery
+                .pending()
+                .iter()
+                .map(|pending| PendingDeliveryStatus {
+                    id: pending.id,
+                    alert_key: pending.alert_key.clone(),
+                    alert_type: pending.alert_type.clone(),
+                    monitor_name: pending.payload.monitor_name.clone(),
+                    attempts: pending.attempts,
+                    next_attempt: pending.next_attempt,
+                    last_error: pending.last_error.clone(),
+                })
+                .collect(),
+            dead_lettered: self.delivery.dead_lettered(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "()")]
+pub struct AlertUpdate {
+    pub uid: String,
+    pub alerts: Vec<models::Alert>,
+}
+
+impl Handler<AlertUpdate> for AlerterActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: AlertUpdate, _ctx: &mut Self::Context) -> Self::Result {
+        debug!("Handling alerts");
         self.alerts = msg.alerts;
+        self.alerts_loaded = true;
+
+        // forget alerts that no longer exist, so a new alert in their place starts afresh
+        let alert_keys: HashSet<String> = self.alerts.iter().map(alert_key).collect();
+        for state in self.statuses.values_mut() {
+            state.alerts_fired.retain(|key, _| alert_keys.contains(key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::actors::alerter::{alert_key, STATUS_BATCH_MS};
+    use crate::actors::*;
+    use crate::alerts::*;
+    use crate::api::ApiFuture;
+    use crate::config::{AlertDelivery, FlapDetection};
+    use crate::error::Error;
+    use crate::monitoring::{test_monitor, MonitorStatusBuilder};
+    use crate::openapi_client::models;
+    use crate::openapi_client::models::MonitorStatusIndicator::{DOWN, OK};
+    use crate::silence::Silence;
+    use chrono::{TimeZone, Utc};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Records the URL of the alert, the monitor and the status of every alert sent.
+    #[derive(Clone, Default)]
+    struct RecordingAlertApi {
+        sent: Arc<Mutex<Vec<(String, String, models::MonitorStatusIndicator)>>>,
+        payloads: Arc<Mutex<Vec<AlertPayload>>>,
+        /// How many of the next alerts fail to send. They are not recorded.
+        failures: Arc<Mutex<usize>>,
+    }
+
+    impl RecordingAlertApi {
+        fn record(&self, url: &Option<String>, payload: &AlertPayload) -> ApiFuture<()> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Box::pin(async { Err(Error::new("connection refused")) });
+            }
+            self.sent.lock().unwrap().push((
+                url.clone().unwrap_or_default(),
+                payload.monitor_name.clone(),
+                payload.status.status,
+            ));
+            self.payloads.lock().unwrap().push(payload.clone());
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    impl AlertApi for RecordingAlertApi {
+        fn send_msteams_msg(
+            &self,
+            alert: &models::MsTeamsAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&alert.url, payload)
+        }
+
+        fn send_email(
+            &self,
+            _alert: &models::EmailAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&None, payload)
+        }
+
+        fn send_webhook(
+            &self,
+            alert: &models::WebhookAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&alert.url, payload)
+        }
+
+        fn send_slack_msg(
+            &self,
+            alert: &models::SlackAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&alert.url, payload)
+        }
+
+        fn send_pagerduty_event(
+            &self,
+            alert: &models::PagerDutyAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&alert.url, payload)
+        }
+
+        fn send_opsgenie_alert(
+            &self,
+            alert: &models::OpsgenieAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&alert.url, payload)
+        }
+
+        fn send_log_record(
+            &self,
+            alert: &models::LogAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&alert.path, payload)
+        }
+
+        fn run_exec_command(
+            &self,
+            alert: &models::ExecAlertBody,
+            payload: &AlertPayload,
+        ) -> ApiFuture<()> {
+            self.record(&Some(alert.command.clone()), payload)
+        }
+    }
+
+    fn webhook(url: &str, threshold: isize, recovery_threshold: Option<isize>) -> models::Alert {
+        let mut body = models::AlertBody::new();
+        body.url = Some(url.to_owned());
+        let mut alert = models::Alert::new("webhook".to_owned(), vec![], threshold, true, body);
+        alert.recovery_threshold = recovery_threshold;
+        alert
+    }
+
+    fn routed(url: &str, monitors: &[&str]) -> models::Alert {
+        let mut alert = webhook(url, 1, None);
+        alert.monitors = monitors.iter().map(|m| m.to_string().into()).collect();
+        alert
+    }
+
+    /// An API whose next alerts fail to send.
+    fn failing_api(failures: usize) -> RecordingAlertApi {
+        let api = RecordingAlertApi::default();
+        *api.failures.lock().unwrap() = failures;
+        api
+    }
+
+    /// Sends the alerts, then a status for each (monitor name, status) pair one second apart,
+    /// and returns what was sent.
+    async fn run(
+        alerts: Vec<models::Alert>,
+        statuses: &[(&str, models::MonitorStatusIndicator)],
+    ) -> Vec<(String, String, models::MonitorStatusIndicator)> {
+        let (_, api) = run_recording(alerts, |_| {}, statuses).await;
+        let sent = api.sent;
+        let sent = sent.lock().unwrap().clone();
+        sent
+    }
+
+    async fn run_recording(
+        alerts: Vec<models::Alert>,
+        configure: impl FnOnce(&mut AlerterActor),
+        statuses: &[(&str, models::MonitorStatusIndicator)],
+    ) -> (Addr<AlerterActor>, RecordingAlertApi) {
+        run_with_api(RecordingAlertApi::default(), alerts, configure, statuses).await
+    }
+
+    /// Like `run_recording`, with the given API.
+    async fn run_with_api(
+        api: RecordingAlertApi,
+        alerts: Vec<models::Alert>,
+        configure: impl FnOnce(&mut AlerterActor),
+        statuses: &[(&str, models::MonitorStatusIndicator)],
+    ) -> (Addr<AlerterActor>, RecordingAlertApi) {
+        let mut alerter = alerter(api.clone());
+        configure(&mut alerter);
+        let addr = alerter.start();
+
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts,
+        })
+        .await
+        .unwrap();
+
+        for (second, (name, indicator)) in statuses.iter().enumerate() {
+            send_status(&addr, monitor(name), second as i64, *indicator).await;
+        }
+
+        (addr, api)
+    }
+
+    /// An alerter that processes each status as soon as it arrives, so a test can check what
+    /// was sent once the status has been sent.
+    fn alerter(api: RecordingAlertApi) -> AlerterActor {
+        let mut alerter = AlerterActor::new(api, TimerActor::new().start());
+        alerter.batch_window = Duration::from_secs(0);
+        alerter
+    }
+
+    /// A monitor with an ID, so alerts can list it by name or ID.
+    fn monitor(name: &str) -> models::Monitor {
+        let mut monitor = test_monitor(name);
+        monitor.id = Some(format!("id-{}", name));
+        monitor
+    }
+
+    /// Sends a status of the monitor, timestamped the given number of seconds into the test.
+    async fn send_status(
+        addr: &Addr<AlerterActor>,
+        monitor: models::Monitor,
+        second: i64,
+        indicator: models::MonitorStatusIndicator,
+    ) {
+        let builder = MonitorStatusBuilder::new(
+            &monitor.name,
+            models::MonitorType::HTTP,
+            Utc.timestamp_opt(1_600_000_000 + second, 0).unwrap(),
+        );
+        let status = match indicator {
+            OK => builder.ok("200", "200"),
+            DOWN => builder.down("200", "500"),
+        };
+        addr.send(StatusMsg { monitor, status })
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    fn sent(
+        url: &str,
+        name: &str,
+        status: models::MonitorStatusIndicator,
+    ) -> (String, String, models::MonitorStatusIndicator) {
+        (url.to_owned(), name.to_owned(), status)
+    }
+
+    #[actix_rt::test]
+    async fn fires_on_first_down_with_threshold_of_one() {
+        let result = run(vec![webhook("a", 1, None)], &[("web", DOWN), ("web", OK)]).await;
+
+        assert_eq!(result, vec![sent("a", "web", DOWN), sent("a", "web", OK)]);
+    }
+
+    #[actix_rt::test]
+    async fn treats_threshold_below_one_as_one() {
+        let result = run(
+            vec![webhook("a", 0, Some(-1))],
+            &[("web", DOWN), ("web", OK)],
+        )
+        .await;
+
+        assert_eq!(result, vec![sent("a", "web", DOWN), sent("a", "web", OK)]);
+    }
+
+    #[actix_rt::test]
+    async fn waits_for_consecutive_downs() {
+        let alerts = vec![webhook("a", 3, None)];
+
+        assert_eq!(
+            run(alerts.clone(), &[("web", DOWN), ("web", DOWN)]).await,
+            vec![]
+        );
+        assert_eq!(
+            run(
+                alerts,
+                &[("web", DOWN), ("web", DOWN), ("web", DOWN), ("web", DOWN)]
+            )
+            .await,
+            vec![sent("a", "web", DOWN)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn ok_resets_down_streak() {
+        let result = run(
+            vec![webhook("a", 2, None)],
+            &[("web", DOWN), ("web", OK), ("web", DOWN), ("web", DOWN)],
+        )
+        .await;
+
+        assert_eq!(result, vec![sent("a", "web", DOWN)]);
+    }
+
+    #[actix_rt::test]
+    async fn waits_for_consecutive_oks_before_recovery() {
+        let result = run(
+            vec![webhook("a", 1, Some(2))],
+            &[
+                ("web", DOWN),
+                ("web", OK),
+                ("web", DOWN),
+                ("web", OK),
+                ("web", OK),
+                ("web", OK),
+            ],
+        )
+        .await;
+
+        assert_eq!(result, vec![sent("a", "web", DOWN), sent("a", "web", OK)]);
+    }
+
+    #[actix_rt::test]
+    async fn no_recovery_without_down_alert() {
+        let result = run(
+            vec![webhook("a", 3, None)],
+            &[("web", DOWN), ("web", DOWN), ("web", OK)],
+        )
+        .await;
+
+        assert_eq!(result, vec![]);
+    }
+
+    #[actix_rt::test]
+    async fn keeps_alert_state_when_alerts_are_reordered() {
+        let (addr, api) =
+            run_recording(vec![webhook("a", 1, None)], |_| {}, &[("web", DOWN)]).await;
+
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts: vec![webhook("b", 1, None), webhook("a", 1, None)],
+        })
+        .await
+        .unwrap();
+        send_status(&addr, monitor("web"), 1, OK).await;
+
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![sent("a", "web", DOWN), sent("a", "web", OK)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn keeps_alert_state_when_alert_is_edited() {
+        let (addr, api) =
+            run_recording(vec![webhook("a", 1, None)], |_| {}, &[("web", DOWN)]).await;
+
+        let mut edited = webhook("a", 1, Some(1));
+        edited.repeat_interval = Some("1h".to_owned());
+        edited.body.body_template = Some("{\"text\": \"{{monitorName}}\"}".to_owned());
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts: vec![edited],
+        })
+        .await
+        .unwrap();
+        send_status(&addr, monitor("web"), 1, DOWN).await;
+        send_status(&addr, monitor("web"), 2, OK).await;
+
+        // the monitor is not paged again, and gets its recovery
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![sent("a", "web", DOWN), sent("a", "web", OK)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn keeps_state_of_monitor_removed_from_another_source() {
+        let (addr, _) = run_recording(vec![webhook("a", 1, None)], |_| {}, &[("web", DOWN)]).await;
+        addr.send(MonitorUpdate {
+            source_id: "api".to_owned(),
+            monitor: monitor("web"),
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+        for source_id in &["config", "api"] {
+            addr.send(MonitorRemoved {
+                source_id: source_id.to_string(),
+                monitor_name: "web".to_owned(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+            let status = addr.send(GetAlerterStatus).await.unwrap();
+            assert_eq!(status.is_empty(), *source_id == "api");
+        }
+    }
+
+    #[actix_rt::test]
+    async fn alerts_have_their_own_thresholds() {
+        let result = run(
+            vec![webhook("a", 1, None), webhook("b", 2, Some(2))],
+            &[("web", DOWN), ("web", DOWN), ("web", OK), ("web", OK)],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![
+                sent("a", "web", DOWN),
+                sent("b", "web", DOWN),
+                sent("a", "web", OK),
+                sent("b", "web", OK),
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn counts_each_monitor_separately() {
+        let result = run(
+            vec![webhook("a", 2, None)],
+            &[("web", DOWN), ("db", DOWN), ("web", DOWN), ("db", OK)],
+        )
+        .await;
+
+        assert_eq!(result, vec![sent("a", "web", DOWN)]);
+    }
+
+    #[actix_rt::test]
+    async fn routes_alerts_to_listed_monitors() {
+        let alerts = vec![
+            routed("all", &[]),
+            routed("by-name", &["web"]),
+            routed("by-id", &["id-db"]),
+            routed("several", &["web", "id-cache"]),
+            routed("unknown", &["nothing"]),
+        ];
+
+        let matrix = vec![
+            ("web", vec!["all", "by-name", "several"]),
+            ("db", vec!["all", "by-id"]),
+            ("cache", vec!["all", "several"]),
+            ("queue", vec!["all"]),
+        ];
+
+        for (monitor_name, urls) in matrix {
+            let result = run(alerts.clone(), &[(monitor_name, DOWN)]).await;
+            let expected: Vec<_> = urls
+                .into_iter()
+                .map(|url| sent(url, monitor_name, DOWN))
+                .collect();
+
+            assert_eq!(result, expected, "alerts for monitor {}", monitor_name);
+        }
+    }
+
+    #[actix_rt::test]
+    async fn skips_disabled_alerts() {
+        let mut disabled = routed("disabled", &["web"]);
+        disabled.enabled = false;
+
+        let result = run(
+            vec![disabled, routed("enabled", &["web"])],
+            &[("web", DOWN), ("web", OK)],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![sent("enabled", "web", DOWN), sent("enabled", "web", OK)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn repeats_down_alert_until_recovery() {
+        let mut alert = webhook("a", 1, None);
+        alert.repeat_interval = Some("2s".to_owned());
+
+        let result = run(
+            vec![alert],
+            &[
+                ("web", DOWN),
+                ("web", DOWN),
+                ("web", DOWN),
+                ("web", DOWN),
+                ("web", DOWN),
+                ("web", OK),
+                ("web", OK),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![
+                sent("a", "web", DOWN),
+                sent("a", "web", DOWN),
+                sent("a", "web", DOWN),
+                sent("a", "web", OK),
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn reports_outage_and_total_downtime() {
+        let mut alert = webhook("a", 2, Some(2));
+        alert.repeat_interval = Some("3s".to_owned());
+
+        let (_, api) = run_recording(
+            vec![alert],
+            |_| {},
+            &[
+                ("web", OK),
+                ("web", DOWN),
+                ("web", DOWN),
+                ("web", OK),
+                ("web", DOWN),
+                ("web", DOWN),
+                ("web", OK),
+                ("web", OK),
+            ],
+        )
+        .await;
+        let payloads = api.payloads.lock().unwrap();
+        let outages: Vec<_> = payloads
+            .iter()
+            .map(|p| {
+                (
+                    p.status.status,
+                    p.down_since.unwrap().timestamp() - 1_600_000_000,
+                    p.downtime.unwrap().as_secs(),
+                )
+            })
+            .collect();
+
+        // down from second 1, briefly OK at second 3, then recovered at second 6
+        assert_eq!(outages, vec![(DOWN, 1, 1), (DOWN, 1, 4), (OK, 1, 5)]);
+    }
+
+    fn flap_detection(window: usize) -> FlapDetection {
+        FlapDetection {
+            enabled: true,
+            window,
+            high_threshold: 50.0,
+            low_threshold: 25.0,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn sends_one_notice_while_flapping() {
+        let (addr, api) = run_recording(
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.flap_detection = flap_detection(5),
+            &[
+                ("web", OK),
+                ("web", DOWN),
+                ("web", OK),
+                ("web", DOWN),
+                ("web", OK),
+                ("web", DOWN),
+                ("web", OK),
+            ],
+        )
+        .await;
+
+        let status = addr.send(GetAlerterStatus).await.unwrap();
+        assert!(status[0].flapping);
+        assert_eq!(
+            status[0].alerts_fired,
+            vec![alert_key(&webhook("a", 1, None))]
+        );
+
+        // settles OK, with the changes in the window falling below the low threshold
+        for second in 7..10 {
+            send_status(&addr, monitor("web"), second, OK).await;
+        }
+
+        let status = addr.send(GetAlerterStatus).await.unwrap();
+        assert!(!status[0].flapping);
+
+        let payloads = api.payloads.lock().unwrap();
+        let notices: Vec<_> = payloads
+            .iter()
+            .map(|p| (p.status.status, p.flapping))
+            .collect();
+
+        assert_eq!(
+            notices,
+            vec![
+                (DOWN, false),
+                (OK, false),
+                (DOWN, false),
+                (DOWN, true),
+                (OK, false),
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn does_not_detect_flapping_when_disabled() {
+        let mut settings = flap_detection(3);
+        settings.enabled = false;
+
+        let (addr, api) = run_recording(
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.flap_detection = settings,
+            &[("web", DOWN), ("web", OK), ("web", DOWN), ("web", OK)],
+        )
+        .await;
+
+        assert_eq!(api.sent.lock().unwrap().len(), 4);
+        assert!(!addr.send(GetAlerterStatus).await.unwrap()[0].flapping);
+    }
+
+    fn silence(monitors: &[&str], start: i64, end: i64) -> Silence {
+        Silence {
+            id: None,
+            monitors: monitors.iter().map(|m| m.to_string()).collect(),
+            tags: vec![],
+            start: Some(Utc.timestamp_opt(1_600_000_000 + start, 0).unwrap()),
+            end: Some(Utc.timestamp_opt(1_600_000_000 + end, 0).unwrap()),
+            schedule: None,
+            duration: None,
+            reason: "Deploying".to_owned(),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn holds_back_alerts_while_silenced() {
+        let (addr, api) = run_recording(
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.config_silences = vec![silence(&["web"], 2, 5)],
+            &[
+                ("web", OK),
+                ("api", OK),
+                ("web", DOWN),
+                ("api", DOWN),
+                ("web", DOWN),
+                ("web", DOWN),
+                ("api", OK),
+            ],
+        )
+        .await;
+
+        let status = addr.send(GetAlerterStatus).await.unwrap();
+        assert_eq!(status[0].silenced_by, None);
+
+        // web is still DOWN when the silence ends at second 5, so its alert goes out then
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![
+                sent("a", "api", DOWN),
+                sent("a", "web", DOWN),
+                sent("a", "api", OK),
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn sends_recovery_held_back_by_silence() {
+        let (_, api) = run_recording(
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.config_silences = vec![silence(&[], 1, 3)],
+            &[("web", DOWN), ("web", OK), ("web", OK), ("web", OK)],
+        )
+        .await;
+
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![sent("a", "web", DOWN), sent("a", "web", OK)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn sends_one_digest_when_silence_ends() {
+        let (_, api) = run_recording(
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.config_silences = vec![silence(&[], 0, 4)],
+            &[
+                ("web", DOWN),
+                ("api", DOWN),
+                ("db", DOWN),
+                ("db", OK),
+                ("web", DOWN),
+            ],
+        )
+        .await;
+
+        let payloads = api.payloads.lock().unwrap().clone();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].status.status, DOWN);
+        assert_eq!(
+            payloads[0]
+                .digest
+                .iter()
+                .map(|p| p.monitor_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["api", "web"]
+        );
+    }
+
+    fn escalating(url: &str, delay: &str) -> models::Alert {
+        let mut alert = webhook(url, 1, None);
+        alert.escalate_after = Some(delay.to_owned());
+        alert
+    }
+
+    #[actix_rt::test]
+    async fn escalates_once_down_for_delay() {
+        let result = run(
+            vec![webhook("team", 1, None), escalating("oncall", "2s")],
+            &[("web", DOWN), ("web", DOWN), ("web", DOWN), ("web", OK)],
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            vec![
+                sent("team", "web", DOWN),
+                sent("oncall", "web", DOWN),
+                sent("team", "web", OK),
+                sent("oncall", "web", OK),
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn escalates_on_timer_without_new_status() {
+        let (addr, api) = run_recording(
+            vec![webhook("team", 1, None), escalating("oncall", "1s")],
+            |_| {},
+            &[("web", DOWN)],
+        )
+        .await;
+
+        let status = addr.send(GetAlerterStatus).await.unwrap();
+        assert_eq!(
+            status[0].pending_escalations,
+            vec![alert_key(&escalating("oncall", "1s"))]
+        );
+
+        // reloading the same alerts keeps the escalation going
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts: vec![webhook("team", 1, None), escalating("oncall", "1s")],
+        })
+        .await
+        .unwrap();
+
+        actix_rt::time::sleep(Duration::from_millis(1500)).await;
+
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![sent("team", "web", DOWN), sent("oncall", "web", DOWN)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn keeps_escalation_when_alert_is_edited() {
+        let (addr, api) =
+            run_recording(vec![escalating("oncall", "1s")], |_| {}, &[("web", DOWN)]).await;
+
+        let mut edited = escalating("oncall", "1s");
+        edited.repeat_interval = Some("1h".to_owned());
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts: vec![edited.clone()],
+        })
+        .await
+        .unwrap();
+        send_status(&addr, monitor("web"), 0, DOWN).await;
+
+        let status = addr.send(GetAlerterStatus).await.unwrap();
+        assert_eq!(status[0].pending_escalations, vec![alert_key(&edited)]);
+
+        actix_rt::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![sent("oncall", "web", DOWN)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn cancels_escalation_on_recovery() {
+        let (addr, api) = run_recording(
+            vec![webhook("team", 1, None), escalating("oncall", "1s")],
+            |_| {},
+            &[("web", DOWN), ("web", OK)],
+        )
+        .await;
+
+        actix_rt::time::sleep(Duration::from_millis(1500)).await;
+
+        let status = addr.send(GetAlerterStatus).await.unwrap();
+        assert!(status[0].pending_escalations.is_empty());
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![sent("team", "web", DOWN), sent("team", "web", OK)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn holds_back_alerts_while_parent_is_down() {
+        let api = RecordingAlertApi::default();
+        let addr = alerter(api.clone()).start();
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts: vec![webhook("a", 1, None)],
+        })
+        .await
+        .unwrap();
+
+        let statuses = [
+            ("switch", OK),
+            ("web", OK),
+            ("switch", DOWN),
+            ("web", DOWN),
+            ("switch", OK),
+            ("web", DOWN),
+            ("web", OK),
+        ];
+        for (second, (name, indicator)) in statuses.iter().enumerate() {
+            let mut monitor = monitor(name);
+            if *name == "web" {
+                monitor.depends_on = Some(vec!["switch".to_owned()]);
+            }
+            send_status(&addr, monitor, second as i64, *indicator).await;
+
+            if second == 3 {
+                let status = addr.send(GetAlerterStatus).await.unwrap();
+                assert!(status[1].unreachable);
+                assert_eq!(status[1].parents_down, vec!["switch".to_owned()]);
+            }
+        }
+
+        // web only alerts once the switch is back and it is still DOWN
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![
+                sent("a", "switch", DOWN),
+                sent("a", "switch", OK),
+                sent("a", "web", DOWN),
+                sent("a", "web", OK),
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn holds_back_alerts_when_child_status_arrives_before_parent() {
+        let api = RecordingAlertApi::default();
+        let addr = AlerterActor::new(api.clone(), TimerActor::new().start()).start();
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts: vec![webhook("a", 1, None)],
+        })
+        .await
+        .unwrap();
+
+        let mut web = monitor("web");
+        web.depends_on = Some(vec!["id-switch".to_owned()]);
+        send_status(&addr, monitor("switch"), 0, OK).await;
+        send_status(&addr, web.clone(), 0, OK).await;
+        actix_rt::time::sleep(Duration::from_millis(2 * STATUS_BATCH_MS)).await;
+
+        // the switch goes DOWN in the same batch, so web is unreachable either way
+        send_status(&addr, web, 1, DOWN).await;
+        send_status(&addr, monitor("switch"), 2, DOWN).await;
+        actix_rt::time::sleep(Duration::from_millis(2 * STATUS_BATCH_MS)).await;
+
+        assert_eq!(
+            api.sent.lock().unwrap().clone(),
+            vec![sent("a", "switch", DOWN)]
+        );
+        let status = addr.send(GetAlerterStatus).await.unwrap();
+        assert!(!status[0].unreachable);
+        assert!(status[1].unreachable);
+        assert_eq!(status[1].parents_down, vec!["switch".to_owned()]);
+    }
+
+    #[actix_rt::test]
+    async fn sends_digest_of_changes_in_group_window() {
+        let mut alert = webhook("a", 1, None);
+        alert.group_window = Some("1s".to_owned());
+
+        let (addr, api) = run_recording(
+            vec![alert],
+            |_| {},
+            &[("api", DOWN), ("web", DOWN), ("db", DOWN)],
+        )
+        .await;
+        actix_rt::time::sleep(Duration::from_millis(1500)).await;
+
+        // a single change in the next window goes out on its own
+        send_status(&addr, monitor("web"), 3, OK).await;
+        assert_eq!(api.sent.lock().unwrap().len(), 1);
+        actix_rt::time::sleep(Duration::from_millis(1500)).await;
+
+        let payloads = api.payloads.lock().unwrap();
+        let digests: Vec<Vec<String>> = payloads
+            .iter()
+            .map(|p| p.digest.iter().map(|d| d.monitor_name.clone()).collect())
+            .collect();
+        assert_eq!(
+            digests,
+            vec![
+                vec!["api".to_owned(), "web".to_owned(), "db".to_owned()],
+                vec![],
+            ]
+        );
+        assert_eq!(payloads[0].monitor_name, "3 monitors");
+        assert_eq!(payloads[1].monitor_name, "web");
+        assert_eq!(payloads[1].status.status, OK);
+    }
+
+    fn delivery(max_attempts: usize, fallback_alert: Option<models::Alert>) -> AlertDelivery {
+        AlertDelivery {
+            max_attempts,
+            initial_backoff: "1s".to_owned(),
+            max_backoff: "1s".to_owned(),
+            spool_path: None,
+            dead_letter_path: None,
+            fallback_alert,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn retries_failed_alert() {
+        let (addr, api) = run_with_api(
+            failing_api(1),
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.delivery.configure(delivery(3, None)),
+            &[("web", DOWN)],
+        )
+        .await;
+
+        assert!(api.sent.lock().unwrap().is_empty());
+        let status = addr.send(GetDeliveryStatus).await.unwrap();
+        assert_eq!(status.pending.len(), 1);
+        assert_eq!(status.pending[0].attempts, 1);
+        assert_eq!(status.pending[0].last_error, "connection refused");
+
+        actix_rt::time::sleep(Duration::from_millis(2500)).await;
+
+        assert_eq!(*api.sent.lock().unwrap(), vec![sent("a", "web", DOWN)]);
+        assert!(addr
+            .send(GetDeliveryStatus)
+            .await
+            .unwrap()
+            .pending
+            .is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn drops_retry_when_monitor_recovers() {
+        let (addr, api) = run_with_api(
+            failing_api(1),
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.delivery.configure(delivery(3, None)),
+            &[("web", DOWN), ("web", OK)],
+        )
+        .await;
+
+        actix_rt::time::sleep(Duration::from_millis(2500)).await;
+
+        // the recovery went out, and the failed DOWN alert is not sent after it
+        assert_eq!(*api.sent.lock().unwrap(), vec![sent("a", "web", OK)]);
+        assert!(addr
+            .send(GetDeliveryStatus)
+            .await
+            .unwrap()
+            .pending
+            .is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn retries_spooled_alerts_after_restart_once_alerts_load() {
+        let path = std::env::temp_dir().join(format!(
+            "schnooty-alerter-spool-{}.json",
+            std::process::id()
+        ));
+        let settings = AlertDelivery {
+            spool_path: Some(path.display().to_string()),
+            ..delivery(3, None)
+        };
+
+        let spooled = settings.clone();
+        let (addr, _) = run_with_api(
+            failing_api(2),
+            vec![webhook("a", 1, None), webhook("b", 1, None)],
+            |alerter| alerter.delivery.configure(spooled),
+            &[("web", DOWN)],
+        )
+        .await;
+        assert_eq!(addr.send(GetDeliveryStatus).await.unwrap().pending.len(), 2);
+
+        let api = failing_api(1);
+        let mut alerter = alerter(api.clone());
+        alerter.delivery.configure(settings);
+        let addr = alerter.start();
+
+        // the alerts of the retries have not loaded yet, so nothing is retried or dropped
+        actix_rt::time::sleep(Duration::from_millis(2500)).await;
+        assert!(api.sent.lock().unwrap().is_empty());
+        assert_eq!(addr.send(GetDeliveryStatus).await.unwrap().pending.len(), 2);
+
+        // b is gone from the reloaded alerts, so its retry is given up on. The monitor has no
+        // status since the restart, so a's retry is queued again when it fails
+        addr.send(AlertUpdate {
+            uid: "test".to_owned(),
+            alerts: vec![webhook("a", 1, None)],
+        })
+        .await
+        .unwrap();
+        actix_rt::time::sleep(Duration::from_millis(3500)).await;
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(*api.sent.lock().unwrap(), vec![sent("a", "web", DOWN)]);
+        let status = addr.send(GetDeliveryStatus).await.unwrap();
+        assert!(status.pending.is_empty());
+        assert_eq!(status.dead_lettered, 1);
+    }
+
+    #[actix_rt::test]
+    async fn sends_fallback_when_alert_runs_out_of_attempts() {
+        let (addr, api) = run_with_api(
+            failing_api(1),
+            vec![webhook("a", 1, None)],
+            |alerter| {
+                alerter
+                    .delivery
+                    .configure(delivery(1, Some(webhook("fallback", 1, None))))
+            },
+            &[("web", DOWN)],
+        )
+        .await;
+        actix_rt::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(
+            *api.sent.lock().unwrap(),
+            vec![sent("fallback", "web", DOWN)]
+        );
+        let status = addr.send(GetDeliveryStatus).await.unwrap();
+        assert!(status.pending.is_empty());
+        assert_eq!(status.dead_lettered, 1);
+
+        let payloads = api.payloads.lock().unwrap();
+        assert!(payloads[0]
+            .delivery_failure
+            .as_ref()
+            .unwrap()
+            .contains("connection refused"));
+        assert_eq!(
+            alert_title(&payloads[0]),
+            "Alert delivery failing for monitor web"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn sends_failed_recovery_to_fallback_as_down() {
+        let mut fallback = models::AlertBody::new();
+        fallback.url = Some("pagerduty".to_owned());
+        fallback.routing_key = Some("R0UT1NG".to_owned());
+        let fallback = models::Alert::new("pagerduty".to_owned(), vec![], 1, true, fallback);
+
+        let (addr, api) = run_recording(
+            vec![webhook("a", 1, None)],
+            |alerter| alerter.delivery.configure(delivery(1, Some(fallback))),
+            &[("web", DOWN)],
+        )
+        .await;
+
+        *api.failures.lock().unwrap() = 1;
+        send_status(&addr, monitor("web"), 1, OK).await;
+        actix_rt::time::sleep(Duration::from_millis(100)).await;
+
+        // the incident on the fallback is left open for someone to look at
+        assert_eq!(
+            *api.sent.lock().unwrap(),
+            vec![sent("a", "web", DOWN), sent("pagerduty", "web", DOWN)]
+        );
+        assert!(api.payloads.lock().unwrap()[1].delivery_failure.is_some());
     }
 }
@@ -1,22 +1,227 @@
 use crate::actors::*;
-use crate::config::Config;
+use crate::config::{
+    apply_templates, check_monitors, find_dependency_cycle, Config, MessageTemplates,
+    CONFIG_ALERTS_SOURCE_ID, CONFIG_MONITORS_SOURCE_ID,
+};
 use crate::error::Error;
+use crate::openapi_client::models;
+use std::collections::HashMap;
 
 pub struct ConfiguratorActor {
     monitor_recipients: Vec<Recipient<MonitorUpdate>>,
     alert_recipients: Vec<Recipient<AlertUpdate>>,
+    removal_recipients: Vec<Recipient<MonitorRemoved>>,
+    /// Latest monitors from each source, keyed by source ID
+    monitor_sources: HashMap<String, Vec<models::Monitor>>,
+    /// Latest alerts from each source, keyed by source ID
+    alert_sources: HashMap<String, Vec<models::Alert>>,
+    /// IDs of the monitor sources in the config, highest precedence first
+    monitor_source_order: Vec<String>,
+    /// IDs of the alert sources in the config, in the order their alerts are used
+    alert_source_order: Vec<String>,
+    /// Monitors that have been sent on, keyed by name, with the ID of their source
+    monitors: HashMap<String, (String, models::Monitor)>,
+    alerts: Option<Vec<models::Alert>>,
+    /// Templates for the alerts of each type that have none of their own
+    templates: HashMap<String, MessageTemplates>,
 }
 
 impl ConfiguratorActor {
     pub fn new(
         monitor_recipients: Vec<Recipient<MonitorUpdate>>,
         alert_recipients: Vec<Recipient<AlertUpdate>>,
+        removal_recipients: Vec<Recipient<MonitorRemoved>>,
     ) -> Self {
         Self {
             monitor_recipients,
             alert_recipients,
+            removal_recipients,
+            monitor_sources: HashMap::new(),
+            alert_sources: HashMap::new(),
+            monitor_source_order: vec![CONFIG_MONITORS_SOURCE_ID.to_owned()],
+            alert_source_order: vec![CONFIG_ALERTS_SOURCE_ID.to_owned()],
+            monitors: HashMap::new(),
+            alerts: None,
+            templates: HashMap::new(),
         }
     }
+
+    fn remove_monitor(&self, source_id: &str, monitor_name: &str) {
+        let monitor_removed = MonitorRemoved {
+            source_id: source_id.to_owned(),
+            monitor_name: monitor_name.to_owned(),
+        };
+        for removal_recipient in &self.removal_recipients {
+            if removal_recipient.do_send(monitor_removed.clone()).is_err() {
+                error!("There was an error delivering monitor removal");
+            }
+        }
+    }
+
+    /// Orders source IDs the way they are listed in the config. Sources that are not in the
+    /// config come last.
+    fn ordered_sources<'a, I: Iterator<Item = &'a String>>(
+        order: &[String],
+        source_ids: I,
+    ) -> Vec<&'a String> {
+        let mut source_ids: Vec<_> = source_ids.collect();
+        source_ids.sort_by_key(|id| {
+            (
+                order.iter().position(|o| o == *id).unwrap_or(order.len()),
+                id.to_string(),
+            )
+        });
+        source_ids
+    }
+
+    /// Merges the monitors from every source and sends on what changed since the last time.
+    fn apply_monitors(&mut self) {
+        let monitors = self.merge_monitors();
+        self.send_monitors(monitors);
+    }
+
+    /// The monitors from every source, keyed by name with the ID of their source. A monitor in
+    /// more than one source is taken from the one with the highest precedence.
+    fn merge_monitors(&self) -> HashMap<String, (String, models::Monitor)> {
+        let mut monitors: HashMap<String, (String, models::Monitor)> = HashMap::new();
+
+        for source_id in
+            Self::ordered_sources(&self.monitor_source_order, self.monitor_sources.keys())
+        {
+            for monitor in self.monitor_sources[source_id].iter() {
+                if let Some((existing_source_id, _)) = monitors.get(&monitor.name) {
+                    if existing_source_id == source_id {
+                        warn!(
+                            "Monitor is defined more than once in the same source. Using the first one (monitor_name={}, source_id={})",
+                            monitor.name, source_id
+                        );
+                        continue;
+                    }
+                    error!(
+                        "Monitor is defined in more than one source. Using the one from {} (monitor_name={}, ignored_source_id={})",
+                        existing_source_id, monitor.name, source_id
+                    );
+                    continue;
+                }
+                monitors.insert(monitor.name.clone(), (source_id.clone(), monitor.clone()));
+            }
+        }
+
+        monitors
+    }
+
+    /// The names of the monitors from the source that another source also defines.
+    fn duplicate_monitors(&self, source_id: &str) -> Vec<String> {
+        let mut duplicates: Vec<String> = self
+            .monitor_sources
+            .get(source_id)
+            .into_iter()
+            .flatten()
+            .filter(|monitor| {
+                self.monitor_sources
+                    .iter()
+                    .filter(|(other_id, _)| other_id.as_str() != source_id)
+                    .any(|(_, others)| others.iter().any(|other| other.name == monitor.name))
+            })
+            .map(|monitor| monitor.name.clone())
+            .collect();
+        duplicates.sort();
+        duplicates.dedup();
+        duplicates
+    }
+
+    /// Sends on the merged monitors that changed since the last time, and removes those that
+    /// are gone.
+    fn send_monitors(&mut self, mut monitors: HashMap<String, (String, models::Monitor)>) {
+        // a cycle can span sources, so it is only found once they are merged. The dependencies
+        // of the monitors in it are dropped until it is fixed
+        loop {
+            let merged: Vec<&models::Monitor> = monitors.values().map(|(_, m)| m).collect();
+            let cycle = match find_dependency_cycle(&merged) {
+                Some(c) => c,
+                None => break,
+            };
+            error!(
+                "Ignoring the dependencies of monitors that form a cycle (monitors={})",
+                cycle.join(" -> ")
+            );
+            for monitor_name in cycle {
+                if let Some((_, monitor)) = monitors.get_mut(&monitor_name) {
+                    monitor.depends_on = None;
+                }
+            }
+        }
+
+        // disabled monitors are never scheduled
+        monitors.retain(|_, (_, monitor)| monitor.enabled);
+
+        // monitors no longer in any source are stopped
+        for (monitor_name, (source_id, _)) in self.monitors.iter() {
+            if !monitors.contains_key(monitor_name) {
+                info!(
+                    "Removing monitor (monitor_name={}, source_id={})",
+                    monitor_name, source_id
+                );
+                self.remove_monitor(source_id, monitor_name);
+            }
+        }
+
+        // only new or changed monitors are sent on, so unchanged monitors keep their schedule
+        for (monitor_name, (source_id, monitor)) in monitors.iter() {
+            if self.monitors.get(monitor_name) == Some(&(source_id.clone(), monitor.clone())) {
+                continue;
+            }
+
+            debug!(
+                "Updating monitor (monitor_name={}, source_id={})",
+                monitor_name, source_id
+            );
+
+            // build the monitor config message
+            let monitor_update = MonitorUpdate {
+                source_id: source_id.clone(),
+                monitor: monitor.clone(),
+            };
+            for monitor_recipient in &self.monitor_recipients {
+                if monitor_recipient.do_send(monitor_update.clone()).is_err() {
+                    error!("There was an error delivering monitors");
+                }
+            }
+        }
+
+        self.monitors = monitors;
+    }
+
+    /// Merges the alerts from every source and sends them on if they changed.
+    fn apply_alerts(&mut self) {
+        let alerts: Vec<models::Alert> =
+            Self::ordered_sources(&self.alert_source_order, self.alert_sources.keys())
+                .into_iter()
+                .flat_map(|source_id| self.alert_sources[source_id].iter().cloned())
+                .map(|mut alert| {
+                    apply_templates(&self.templates, &mut alert);
+                    alert
+                })
+                .collect();
+
+        if self.alerts.as_ref() == Some(&alerts) {
+            debug!("Alerts are unchanged");
+            return;
+        }
+
+        let alert_update = AlertUpdate {
+            uid: CONFIG_ALERTS_SOURCE_ID.to_owned(),
+            alerts: alerts.clone(),
+        };
+
+        for alert_recipient in &self.alert_recipients {
+            if alert_recipient.do_send(alert_update.clone()).is_err() {
+                error!("There was an error delivering alerts");
+            }
+        }
+
+        self.alerts = Some(alerts);
+    }
 }
 
 impl Actor for ConfiguratorActor {
@@ -39,34 +244,467 @@ impl Handler<CurrentConfig> for ConfiguratorActor {
     fn handle(&mut self, config_msg: CurrentConfig, _ctx: &mut Context<Self>) -> Self::Result {
         debug!("Handling latest config");
 
-        // create a unique identifier for these fixed monitors
-        let monitors_uid = format!("config://monitors"); // yes it is a URI
-        let alerts_uid = format!("config://alerts"); // yes it is a URI
-
-        for monitor_recipient in &self.monitor_recipients {
-            for monitor in config_msg.config.monitors.iter() {
-                // build the monitor config message
-                let monitor_update = MonitorUpdate {
-                    source_id: monitors_uid.clone(),
-                    monitor: monitor.clone(),
-                };
-                if let Err(_) = monitor_recipient.do_send(monitor_update.clone()) {
-                    error!("There was an error delivering monitors");
-                }
-            }
+        let config = config_msg.config;
+
+        self.monitor_source_order = vec![CONFIG_MONITORS_SOURCE_ID.to_owned()];
+        self.monitor_source_order.extend(
+            config
+                .resolve_monitor_sources()?
+                .into_iter()
+                .map(|(source_id, _)| source_id),
+        );
+        self.alert_source_order = vec![CONFIG_ALERTS_SOURCE_ID.to_owned()];
+        self.alert_source_order.extend(
+            config
+                .resolve_alert_sources()?
+                .into_iter()
+                .map(|(source_id, _)| source_id),
+        );
+
+        // sources that were removed from the config no longer contribute anything
+        let monitor_source_order = &self.monitor_source_order;
+        self.monitor_sources
+            .retain(|source_id, _| monitor_source_order.contains(source_id));
+        let alert_source_order = &self.alert_source_order;
+        self.alert_sources
+            .retain(|source_id, _| alert_source_order.contains(source_id));
+
+        self.templates = config.templates;
+        self.monitor_sources
+            .insert(CONFIG_MONITORS_SOURCE_ID.to_owned(), config.monitors);
+        self.alert_sources
+            .insert(CONFIG_ALERTS_SOURCE_ID.to_owned(), config.alerts);
+
+        self.apply_monitors();
+        self.apply_alerts();
+
+        Ok(())
+    }
+}
+
+/// The latest monitors from a source such as the Schnooty API.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<(), Error>")]
+pub struct MonitorSourceUpdate {
+    pub source_id: String,
+    pub monitors: Vec<models::Monitor>,
+}
+
+impl Handler<MonitorSourceUpdate> for ConfiguratorActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: MonitorSourceUpdate, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!(
+            "Handling {} monitor(s) from source (source_id={})",
+            msg.monitors.len(),
+            msg.source_id
+        );
+
+        if !self.monitor_source_order.contains(&msg.source_id) {
+            debug!(
+                "Ignoring monitors from a source that is not configured (source_id={})",
+                msg.source_id
+            );
+            return Ok(());
         }
 
-        let alert_update = AlertUpdate {
-            uid: alerts_uid,
-            alerts: config_msg.config.alerts,
-        };
+        // monitors that would not load from the config, or whose dependencies would form a
+        // cycle, are rejected and the source keeps its previous monitors
+        if let Err(err) = check_monitors(&msg.source_id, &msg.monitors) {
+            error!(
+                "Rejecting monitors from source (source_id={}, error_msg={})",
+                msg.source_id, err
+            );
+            return Err(Error::new(format!(
+                "Monitors from {} were rejected: {}",
+                msg.source_id, err
+            )));
+        }
 
-        for alert_recipient in &self.alert_recipients {
-            if let Err(_) = alert_recipient.do_send(alert_update.clone()) {
-                error!("There was an error delivering alerts");
-            }
+        let previous = self
+            .monitor_sources
+            .insert(msg.source_id.clone(), msg.monitors);
+        let monitors = self.merge_monitors();
+        let merged: Vec<&models::Monitor> = monitors.values().map(|(_, m)| m).collect();
+        if let Some(cycle) = find_dependency_cycle(&merged) {
+            let cycle = cycle.join(" -> ");
+            error!(
+                "Rejecting monitors from source. Their dependencies form a cycle (source_id={}, monitors={})",
+                msg.source_id, cycle
+            );
+            match previous {
+                Some(previous) => self.monitor_sources.insert(msg.source_id.clone(), previous),
+                None => self.monitor_sources.remove(&msg.source_id),
+            };
+            return Err(Error::new(format!(
+                "Monitors from {} were rejected. Their dependencies form a cycle: {}",
+                msg.source_id, cycle
+            )));
+        }
+
+        self.send_monitors(monitors);
+
+        // the monitors are still used from the source with the highest precedence
+        let duplicates = self.duplicate_monitors(&msg.source_id);
+        if !duplicates.is_empty() {
+            return Err(Error::new(format!(
+                "Monitors from {} are defined in more than one source, and only the one from the source with the highest precedence is used: {}",
+                msg.source_id,
+                duplicates.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// The latest alerts from a source such as the Schnooty API.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<(), Error>")]
+pub struct AlertSourceUpdate {
+    pub source_id: String,
+    pub alerts: Vec<models::Alert>,
+}
+
+impl Handler<AlertSourceUpdate> for ConfiguratorActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: AlertSourceUpdate, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!(
+            "Handling {} alert(s) from source (source_id={})",
+            msg.alerts.len(),
+            msg.source_id
+        );
+
+        if !self.alert_source_order.contains(&msg.source_id) {
+            debug!(
+                "Ignoring alerts from a source that is not configured (source_id={})",
+                msg.source_id
+            );
+            return Ok(());
         }
 
+        self.alert_sources.insert(msg.source_id, msg.alerts);
+        self.apply_alerts();
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::MonitorSource;
+    use crate::monitoring::test_monitor;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    const INLINE_SOURCE_ID: &str = "inline://monitors/0";
+
+    /// Records every update and removal it gets as a line of text.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Actor for Recorder {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<MonitorUpdate> for Recorder {
+        type Result = Result<(), Error>;
+
+        fn handle(&mut self, msg: MonitorUpdate, _ctx: &mut Self::Context) -> Self::Result {
+            self.events.lock().unwrap().push(format!(
+                "update {} {} from {}",
+                msg.monitor.name, msg.monitor.period, msg.source_id
+            ));
+            Ok(())
+        }
+    }
+
+    impl Handler<MonitorRemoved> for Recorder {
+        type Result = Result<(), Error>;
+
+        fn handle(&mut self, msg: MonitorRemoved, _ctx: &mut Self::Context) -> Self::Result {
+            self.events.lock().unwrap().push(format!(
+                "remove {} from {}",
+                msg.monitor_name, msg.source_id
+            ));
+            Ok(())
+        }
+    }
+
+    impl Handler<AlertUpdate> for Recorder {
+        type Result = ();
+
+        fn handle(&mut self, msg: AlertUpdate, _ctx: &mut Self::Context) -> Self::Result {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("alerts {}", msg.alerts.len()));
+        }
+    }
+
+    fn start() -> (Addr<ConfiguratorActor>, Recorder) {
+        let recorder = Recorder::default();
+        let addr = recorder.clone().start();
+        let configurator = ConfiguratorActor::new(
+            vec![addr.clone().recipient()],
+            vec![addr.clone().recipient()],
+            vec![addr.recipient()],
+        );
+        (configurator.start(), recorder)
+    }
+
+    fn monitor(name: &str, period: &str) -> models::Monitor {
+        let mut monitor = test_monitor(name);
+        monitor.period = period.to_owned();
+        monitor
+    }
+
+    /// A config with the monitors and alerts, and an inline monitor source.
+    fn config(monitors: Vec<models::Monitor>, alerts: Vec<models::Alert>) -> CurrentConfig {
+        let mut config: Config = serde_yaml::from_str("monitors: []").unwrap();
+        config.monitors = monitors;
+        config.alerts = alerts;
+        config.monitor_sources = vec![MonitorSource::Inline { monitors: vec![] }];
+        CurrentConfig { config }
+    }
+
+    /// The events recorded since the last call, sorted as monitors are sent on in no order.
+    async fn events(recorder: &Recorder) -> Vec<String> {
+        actix_rt::time::sleep(Duration::from_millis(50)).await;
+        let mut events = std::mem::take(&mut *recorder.events.lock().unwrap());
+        events.sort();
+        events
+    }
+
+    #[actix_rt::test]
+    async fn sends_only_what_changed_on_reload() {
+        let (addr, recorder) = start();
+        let alert = |url: &str| {
+            let mut body = models::AlertBody::new();
+            body.url = Some(url.to_owned());
+            models::Alert::new("webhook".to_owned(), vec![], 1, true, body)
+        };
+
+        addr.send(config(
+            vec![monitor("web", "1m"), monitor("api", "1m")],
+            vec![alert("a")],
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(events(&recorder).await.len(), 3);
+
+        // reloading the same config sends nothing
+        addr.send(config(
+            vec![monitor("web", "1m"), monitor("api", "1m")],
+            vec![alert("a")],
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(events(&recorder).await.is_empty());
+
+        addr.send(config(
+            vec![monitor("web", "5m"), monitor("api", "1m")],
+            vec![alert("a"), alert("b")],
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            events(&recorder).await,
+            vec!["alerts 2", "update web 5m from config://monitors"]
+        );
+
+        // a disabled monitor is stopped
+        let mut disabled = monitor("api", "1m");
+        disabled.enabled = false;
+        addr.send(config(
+            vec![monitor("web", "5m"), disabled],
+            vec![alert("a"), alert("b")],
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            events(&recorder).await,
+            vec!["remove api from config://monitors"]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn rejects_source_update_with_dependency_cycle() {
+        let (addr, recorder) = start();
+        let depending = |name: &str, parent: &str| {
+            let mut monitor = monitor(name, "1m");
+            monitor.depends_on = Some(vec![parent.to_owned()]);
+            monitor
+        };
+
+        addr.send(config(vec![depending("api", "db")], vec![]))
+            .await
+            .unwrap()
+            .unwrap();
+        addr.send(MonitorSourceUpdate {
+            source_id: INLINE_SOURCE_ID.to_owned(),
+            monitors: vec![monitor("db", "1m")],
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        events(&recorder).await;
+
+        let result = addr
+            .send(MonitorSourceUpdate {
+                source_id: INLINE_SOURCE_ID.to_owned(),
+                monitors: vec![depending("db", "api"), monitor("web", "1m")],
+            })
+            .await
+            .unwrap();
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("dependencies form a cycle"));
+        assert!(events(&recorder).await.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn rejects_source_update_with_invalid_monitor() {
+        let (addr, recorder) = start();
+
+        addr.send(config(vec![], vec![])).await.unwrap().unwrap();
+        addr.send(MonitorSourceUpdate {
+            source_id: INLINE_SOURCE_ID.to_owned(),
+            monitors: vec![monitor("db", "1m")],
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        events(&recorder).await;
+
+        let mut no_timeout = monitor("web", "1m");
+        no_timeout.timeout = "0s".to_owned();
+        for monitors in vec![
+            vec![monitor("db", "2m"), monitor("web", "soon")],
+            vec![monitor("db", "2m"), no_timeout],
+            vec![monitor("db", "2m"), monitor("db", "3m")],
+        ] {
+            let result = addr
+                .send(MonitorSourceUpdate {
+                    source_id: INLINE_SOURCE_ID.to_owned(),
+                    monitors,
+                })
+                .await
+                .unwrap();
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .starts_with("Monitors from inline://monitors/0 were rejected"));
+        }
+
+        // the source keeps its previous monitors
+        assert!(events(&recorder).await.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn prefers_monitors_from_config_then_earlier_sources() {
+        let (addr, recorder) = start();
+
+        let mut current = config(vec![monitor("web", "1m")], vec![]);
+        current
+            .config
+            .monitor_sources
+            .push(MonitorSource::Inline { monitors: vec![] });
+        addr.send(current).await.unwrap().unwrap();
+        let result = addr
+            .send(MonitorSourceUpdate {
+                source_id: "inline://monitors/1".to_owned(),
+                monitors: vec![monitor("web", "2m"), monitor("db", "2m")],
+            })
+            .await
+            .unwrap();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .ends_with("precedence is used: web"));
+        // the first source takes db over from the second once it loads
+        let result = addr
+            .send(MonitorSourceUpdate {
+                source_id: INLINE_SOURCE_ID.to_owned(),
+                monitors: vec![monitor("web", "3m"), monitor("db", "3m")],
+            })
+            .await
+            .unwrap();
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .ends_with("precedence is used: db, web"));
+        assert_eq!(
+            events(&recorder).await,
+            vec![
+                "alerts 0",
+                "update db 2m from inline://monitors/1",
+                "update db 3m from inline://monitors/0",
+                "update web 1m from config://monitors",
+            ]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn removes_monitors_no_source_has_on_reload() {
+        let (addr, recorder) = start();
+
+        addr.send(config(
+            vec![monitor("web", "1m"), monitor("api", "1m")],
+            vec![],
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        addr.send(MonitorSourceUpdate {
+            source_id: INLINE_SOURCE_ID.to_owned(),
+            monitors: vec![monitor("db", "1m")],
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            events(&recorder).await,
+            vec![
+                "alerts 0",
+                "update api 1m from config://monitors",
+                "update db 1m from inline://monitors/0",
+                "update web 1m from config://monitors",
+            ]
+        );
+
+        addr.send(config(vec![monitor("web", "1m")], vec![]))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            events(&recorder).await,
+            vec!["remove api from config://monitors"]
+        );
+
+        // a monitor that moves to another source is updated, not removed
+        let result = addr
+            .send(MonitorSourceUpdate {
+                source_id: INLINE_SOURCE_ID.to_owned(),
+                monitors: vec![monitor("db", "1m"), monitor("web", "1m")],
+            })
+            .await
+            .unwrap();
+        assert!(result.is_err());
+        addr.send(config(vec![], vec![])).await.unwrap().unwrap();
+        assert_eq!(
+            events(&recorder).await,
+            vec!["update web 1m from inline://monitors/0"]
+        );
+    }
+}
@@ -1,22 +1,40 @@
+use crate::actors::MonitorRemoved;
+use crate::duration::monitor_timeout;
 use crate::error::Error;
-use crate::monitoring::Monitoring;
+use crate::monitoring::{MonitorStatusBuilder, Monitoring};
 use crate::openapi_client::models;
 use actix::prelude::*;
 use chrono::prelude::*;
-use futures::stream::FuturesUnordered;
-use std::collections::HashSet;
+use futures::future::{self, AbortHandle, Abortable};
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub struct ExecutorActor<M> {
     monitoring: M,
-    busy_monitors: HashSet<String>,
+    /// Monitors that are running, with the handle that cancels each run
+    busy_monitors: HashMap<String, AbortHandle>,
+    queued_monitors: VecDeque<models::Monitor>,
+    max_concurrent: usize,
+    skipped_still_running: usize,
     recipients: Vec<Recipient<StatusMsg>>,
 }
 
 impl<M: Monitoring> ExecutorActor<M> {
-    pub fn new(monitoring: M, recipients: Vec<Recipient<StatusMsg>>) -> Self {
+    pub fn new(
+        monitoring: M,
+        recipients: Vec<Recipient<StatusMsg>>,
+        max_concurrent: usize,
+    ) -> Self {
         Self {
             monitoring,
-            busy_monitors: HashSet::new(),
+            busy_monitors: HashMap::new(),
+            queued_monitors: VecDeque::new(),
+            max_concurrent: std::cmp::max(max_concurrent, 1),
+            skipped_still_running: 0,
             recipients,
         }
     }
@@ -24,7 +42,14 @@ impl<M: Monitoring> ExecutorActor<M> {
 
 pub struct ExecReport {
     pub monitors_started: Vec<String>,
+    /// Monitors that were not run because a previous run is still in progress or waiting.
     pub monitors_ignored: Vec<String>,
+    /// Monitors that were not run because they are disabled.
+    pub monitors_disabled: Vec<String>,
+    /// Monitors waiting for a free slot in the concurrency pool.
+    pub monitors_queued: Vec<String>,
+    /// Total number of runs skipped because the monitor was still running.
+    pub skipped_still_running: usize,
 }
 
 impl ExecReport {
@@ -32,6 +57,9 @@ impl ExecReport {
         Self {
             monitors_started: vec![],
             monitors_ignored: vec![],
+            monitors_disabled: vec![],
+            monitors_queued: vec![],
+            skipped_still_running: 0,
         }
     }
 }
@@ -54,7 +82,85 @@ impl<M: Send + Unpin + 'static> Actor for ExecutorActor<M> {
     }
 }
 
-impl<M: Send + Unpin + 'static> StreamHandler<(models::Monitor, models::MonitorStatus)>
+impl<M: Monitoring + Send + Unpin + 'static> ExecutorActor<M> {
+    fn start_monitors(&mut self, monitors: Vec<models::Monitor>, ctx: &mut Context<Self>) {
+        if monitors.is_empty() {
+            return;
+        }
+
+        let monitor_futures = FuturesUnordered::new();
+        for monitor in monitors.into_iter() {
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            self.busy_monitors
+                .insert(monitor.name.clone(), abort_handle);
+
+            let monitor_copy = monitor.clone();
+            let timeout = match monitor_timeout(&monitor_copy) {
+                Ok(t) => t,
+                Err(err) => {
+                    error!("{}. Using default of {:?}", err, DEFAULT_TIMEOUT);
+                    DEFAULT_TIMEOUT
+                }
+            };
+            let fut = actix::clock::timeout(timeout, self.monitoring.monitor(&monitor_copy));
+
+            let monitor_name = monitor_copy.name.clone();
+            let monitor_type = monitor.type_;
+            let status_id = monitor.name;
+
+            let run = Box::pin(async move {
+                let timestamp = Utc::now();
+                let status = match fut.await {
+                    Ok(Ok(s)) => s,
+                    Ok(Err(err)) => {
+                        models::MonitorStatus {
+                            monitor_name,
+                            monitor_type,
+                            status: models::MonitorStatusIndicator::DOWN,
+                            status_id,
+                            timestamp,
+                            expires_at: timestamp + chrono::Duration::days(1), // TODO
+                            expected_result: "Expected to be able to start monitor".to_string(),
+                            actual_result: format!("Starting monitor failed: {}", err),
+                            description: format!("Monitor of type {}", monitor_copy.type_),
+                            session: None,
+                            log: Vec::new(),
+                        }
+                    }
+                    Err(_) => {
+                        // the monitor future is dropped by the timeout, which cancels it
+                        warn!(
+                            "Monitor timed out (monitor_name={}, timeout={:?})",
+                            monitor_name, timeout
+                        );
+                        timed_out_status(&monitor_copy, timestamp, timeout)
+                    }
+                };
+                (monitor_copy, status)
+            });
+            monitor_futures.push(Abortable::new(run, abort_registration));
+        }
+
+        // a run that was cancelled because its monitor was removed has no status
+        ctx.add_stream(monitor_futures.filter_map(|result| future::ready(result.ok())));
+    }
+
+    fn start_queued_monitors(&mut self, ctx: &mut Context<Self>) {
+        let mut monitors = vec![];
+        while self.busy_monitors.len() + monitors.len() < self.max_concurrent {
+            match self.queued_monitors.pop_front() {
+                Some(monitor) => {
+                    debug!("Starting queued monitor (monitor_name={})", monitor.name);
+                    monitors.push(monitor);
+                }
+                None => break,
+            }
+        }
+        self.start_monitors(monitors, ctx);
+    }
+}
+
+impl<M: Monitoring + Send + Unpin + 'static> StreamHandler<(models::Monitor, models::MonitorStatus)>
     for ExecutorActor<M>
 {
     fn handle(
@@ -66,6 +172,7 @@ impl<M: Send + Unpin + 'static> StreamHandler<(models::Monitor, models::MonitorS
             "Got monitor status (monitor={}, status={})",
             monitor.name, status.status
         );
+        self.busy_monitors.remove(&monitor.name);
         for r in self.recipients.iter() {
             ctx.spawn(
                 actix::fut::wrap_future(r.send(StatusMsg {
@@ -78,6 +185,7 @@ impl<M: Send + Unpin + 'static> StreamHandler<(models::Monitor, models::MonitorS
                 }),
             );
         }
+        self.start_queued_monitors(ctx);
     }
 }
 
@@ -85,64 +193,298 @@ impl<M: Monitoring + Send + Unpin + 'static> Handler<ExecuteBatch> for ExecutorA
     type Result = Result<ExecReport, Error>;
 
     fn handle(&mut self, msg: ExecuteBatch, ctx: &mut Context<Self>) -> Self::Result {
+        let mut report = ExecReport::new();
+
         if msg.monitors.is_empty() {
-            return Ok(ExecReport {
-                monitors_started: vec![],
-                monitors_ignored: vec![],
-            });
+            report.skipped_still_running = self.skipped_still_running;
+            return Ok(report);
         }
 
         debug!("Executing batch of {} monitors", msg.monitors.len());
 
-        let monitor_futures = FuturesUnordered::new();
-        let mut report = ExecReport::new();
+        let mut monitors = vec![];
         for monitor in msg.monitors.into_iter() {
-            let monitor_name = &monitor.name;
-            if !self.busy_monitors.contains(monitor_name) {
-                let monitor_copy = monitor.clone();
-                let fut = self.monitoring.monitor(&monitor_copy);
-
-                let monitor_name = monitor_copy.name.clone();
-                let monitor_type = monitor.type_;
-                let status_id = monitor.name;
-
-                report.monitors_started.push(monitor_name.to_owned());
-                monitor_futures.push(Box::pin(async move {
-                    let timestamp = Utc::now();
-                    let status = match fut.await {
-                        Ok(s) => s,
-                        Err(err) => {
-                            models::MonitorStatus {
-                                monitor_name,
-                                monitor_type,
-                                status: models::MonitorStatusIndicator::DOWN,
-                                status_id,
-                                timestamp,
-                                expires_at: timestamp + chrono::Duration::days(1), // TODO
-                                expected_result: "Expected to be able to start monitor".to_string(),
-                                actual_result: format!("Starting monitor failed: {}", err),
-                                description: format!("Monitor of type {}", monitor_copy.type_),
-                                session: None,
-                                log: Vec::new(),
-                            }
-                        }
-                    };
-                    (monitor_copy, status)
-                }));
+            let monitor_name = monitor.name.clone();
+            let is_queued = self.queued_monitors.iter().any(|m| m.name == monitor_name);
+            if !monitor.enabled {
+                debug!(
+                    "Monitor is disabled. Skipping it (monitor_name={})",
+                    monitor_name
+                );
+                report.monitors_disabled.push(monitor_name);
+            } else if self.busy_monitors.contains_key(&monitor_name) || is_queued {
+                debug!(
+                    "Monitor is still running. Skipping it (monitor_name={})",
+                    monitor_name
+                );
+                self.skipped_still_running += 1;
+                report.monitors_ignored.push(monitor_name);
+            } else if self.busy_monitors.len() + monitors.len() >= self.max_concurrent {
+                debug!(
+                    "Concurrency limit of {} reached. Queueing monitor (monitor_name={})",
+                    self.max_concurrent, monitor_name
+                );
+                self.queued_monitors.push_back(monitor);
+                report.monitors_queued.push(monitor_name);
             } else {
-                report.monitors_ignored.push(monitor_name.to_owned());
+                monitors.push(monitor);
+                report.monitors_started.push(monitor_name);
             }
         }
 
-        ctx.add_stream(monitor_futures);
+        self.start_monitors(monitors, ctx);
+
+        report.skipped_still_running = self.skipped_still_running;
+
+        debug!(
+            "Executed batch (started={}, queued={}, skipped={}, disabled={})",
+            report.monitors_started.len(),
+            report.monitors_queued.len(),
+            report.monitors_ignored.len(),
+            report.monitors_disabled.len()
+        );
 
         Ok(report)
     }
 }
 
+impl<M: Monitoring + Send + Unpin + 'static> Handler<MonitorRemoved> for ExecutorActor<M> {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: MonitorRemoved, ctx: &mut Context<Self>) -> Self::Result {
+        self.queued_monitors
+            .retain(|monitor| monitor.name != msg.monitor_name);
+
+        if let Some(run) = self.busy_monitors.remove(&msg.monitor_name) {
+            debug!(
+                "Cancelling run of removed monitor (monitor_name={})",
+                msg.monitor_name
+            );
+            run.abort();
+            self.start_queued_monitors(ctx);
+        }
+
+        Ok(())
+    }
+}
+
+#[allow(unused_must_use)]
+fn timed_out_status(
+    monitor: &models::Monitor,
+    timestamp: DateTime<Utc>,
+    timeout: Duration,
+) -> models::MonitorStatus {
+    let mut builder = MonitorStatusBuilder::new(&monitor.name, monitor.type_, timestamp)
+        .description(format!("Monitor of type {}", monitor.type_));
+
+    writeln!(
+        builder,
+        "Monitor did not complete within {:?} and was cancelled",
+        timeout
+    );
+
+    builder.down(
+        format!("Monitor completes within {:?}", timeout),
+        format!("Monitor timed out after {:?}", timeout),
+    )
+}
+
 #[derive(Clone, Debug, Message)]
 #[rtype(result = "Result<(), Error>")]
 pub struct StatusMsg {
     pub monitor: models::Monitor,
     pub status: models::MonitorStatus,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::monitoring::{test_monitor, MonitorFuture};
+    use std::sync::{Arc, Mutex};
+
+    /// Monitors that are OK once the delay has passed.
+    struct SleepingMonitoring {
+        delay: Duration,
+    }
+
+    impl Monitoring for SleepingMonitoring {
+        fn monitor(&mut self, monitor: &models::Monitor) -> MonitorFuture {
+            let delay = self.delay;
+            let builder = MonitorStatusBuilder::new(&monitor.name, monitor.type_, Utc::now());
+            Box::pin(async move {
+                actix_rt::time::sleep(delay).await;
+                Ok(builder.ok("200", "200"))
+            })
+        }
+    }
+
+    /// Records the monitor name and status of every status it gets.
+    #[derive(Clone, Default)]
+    struct StatusCollector {
+        statuses: Arc<Mutex<Vec<(String, models::MonitorStatusIndicator)>>>,
+    }
+
+    impl Actor for StatusCollector {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<StatusMsg> for StatusCollector {
+        type Result = Result<(), Error>;
+
+        fn handle(&mut self, msg: StatusMsg, _ctx: &mut Self::Context) -> Self::Result {
+            self.statuses
+                .lock()
+                .unwrap()
+                .push((msg.monitor.name, msg.status.status));
+            Ok(())
+        }
+    }
+
+    fn monitor(name: &str, timeout: &str) -> models::Monitor {
+        let mut monitor = test_monitor(name);
+        monitor.timeout = timeout.to_owned();
+        monitor
+    }
+
+    fn start(
+        delay_ms: u64,
+        max_concurrent: usize,
+    ) -> (Addr<ExecutorActor<SleepingMonitoring>>, StatusCollector) {
+        let collector = StatusCollector::default();
+        let executor = ExecutorActor::new(
+            SleepingMonitoring {
+                delay: Duration::from_millis(delay_ms),
+            },
+            vec![collector.clone().start().recipient()],
+            max_concurrent,
+        );
+        (executor.start(), collector)
+    }
+
+    fn names(collector: &StatusCollector) -> Vec<String> {
+        let statuses = collector.statuses.lock().unwrap();
+        statuses.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    #[actix_rt::test]
+    async fn drops_removed_monitors_from_queue_and_busy_set() {
+        let (addr, collector) = start(300, 1);
+
+        let report = addr
+            .send(ExecuteBatch {
+                monitors: vec![
+                    monitor("web", "10s"),
+                    monitor("api", "10s"),
+                    monitor("db", "10s"),
+                ],
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.monitors_started, vec!["web".to_owned()]);
+        assert_eq!(report.monitors_queued.len(), 2);
+
+        for monitor_name in &["web", "api"] {
+            addr.send(MonitorRemoved {
+                source_id: "config".to_owned(),
+                monitor_name: monitor_name.to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        }
+
+        // db starts in the slot web had, and web can be run again straight away
+        let report = addr
+            .send(ExecuteBatch {
+                monitors: vec![monitor("web", "10s")],
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.monitors_queued, vec!["web".to_owned()]);
+
+        actix_rt::time::sleep(Duration::from_millis(800)).await;
+        assert_eq!(names(&collector), vec!["db".to_owned(), "web".to_owned()]);
+    }
+
+    #[actix_rt::test]
+    async fn reports_down_when_monitor_times_out() {
+        let (addr, collector) = start(1000, 4);
+
+        addr.send(ExecuteBatch {
+            monitors: vec![monitor("slow", "100ms"), monitor("fast", "5s")],
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        actix_rt::time::sleep(Duration::from_millis(300)).await;
+
+        // the slow monitor is cancelled at its timeout, while the other keeps running
+        assert_eq!(
+            *collector.statuses.lock().unwrap(),
+            vec![("slow".to_owned(), models::MonitorStatusIndicator::DOWN)]
+        );
+
+        actix_rt::time::sleep(Duration::from_millis(1000)).await;
+        assert_eq!(
+            names(&collector),
+            vec!["slow".to_owned(), "fast".to_owned()]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn queues_monitors_over_max_concurrent() {
+        let (addr, collector) = start(200, 2);
+
+        let report = addr
+            .send(ExecuteBatch {
+                monitors: vec![
+                    monitor("web", "5s"),
+                    monitor("api", "5s"),
+                    monitor("db", "5s"),
+                ],
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            report.monitors_started,
+            vec!["web".to_owned(), "api".to_owned()]
+        );
+        assert_eq!(report.monitors_queued, vec!["db".to_owned()]);
+
+        // a monitor that is running or queued is skipped, and a disabled one is never run
+        let mut disabled = monitor("cache", "5s");
+        disabled.enabled = false;
+        let report = addr
+            .send(ExecuteBatch {
+                monitors: vec![monitor("web", "5s"), monitor("db", "5s"), disabled],
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            report.monitors_ignored,
+            vec!["web".to_owned(), "db".to_owned()]
+        );
+        assert_eq!(report.monitors_disabled, vec!["cache".to_owned()]);
+        assert_eq!(report.skipped_still_running, 2);
+
+        // finished monitors free their slots for the queue, and can run again
+        actix_rt::time::sleep(Duration::from_millis(300)).await;
+        let report = addr
+            .send(ExecuteBatch {
+                monitors: vec![monitor("web", "5s")],
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.monitors_started, vec!["web".to_owned()]);
+
+        actix_rt::time::sleep(Duration::from_millis(400)).await;
+        let mut names = names(&collector);
+        names.sort();
+        assert_eq!(names, vec!["api", "db", "web", "web"]);
+    }
+}
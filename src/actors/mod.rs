@@ -4,7 +4,7 @@ mod alerter;
 mod api;
 mod configurator;
 mod executor;
-mod file;
+mod monitor_source;
 mod scheduler;
 mod session;
 mod timer;
@@ -14,7 +14,7 @@ pub use alerter::*;
 pub use api::*;
 pub use configurator::*;
 pub use executor::*;
-pub use file::*;
+pub use monitor_source::*;
 pub use scheduler::*;
 pub use session::*;
 pub use timer::*;
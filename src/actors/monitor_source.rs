@@ -1,9 +1,344 @@
-use actix::Handler;
-use crate::error::Error;
+use crate::actors::*;
 use crate::api::ReadApi;
+use crate::error::Error;
+use crate::openapi_client::models;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::Duration;
 
-#[derive(Clone, Debug, Message)]
-#[rtype(result = "Result<(), Error>")]
+/// Periodically loads monitors and alerts through a `ReadApi` and sends them on tagged with
+/// the ID of the source. If loading fails, the last set that loaded successfully stays active.
 pub struct MonitorSourceActor {
+    source_id: String,
     api: Box<dyn ReadApi>,
+    period: Duration,
+    timer: Addr<TimerActor>,
+    monitor_recipients: Vec<Recipient<MonitorSourceUpdate>>,
+    alert_recipients: Vec<Recipient<AlertSourceUpdate>>,
+    cache_path: Option<String>,
+    cache: SourceCache,
+    is_loading: bool,
+}
+
+/// The last monitors and alerts that loaded successfully. Kept on disk so they survive restarts.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct SourceCache {
+    #[serde(default)]
+    monitors: Option<Vec<models::Monitor>>,
+    #[serde(default)]
+    alerts: Option<Vec<models::Alert>>,
+}
+
+impl MonitorSourceActor {
+    pub fn new<A: ReadApi + 'static>(
+        source_id: String,
+        api: A,
+        period: Duration,
+        timer: Addr<TimerActor>,
+        monitor_recipients: Vec<Recipient<MonitorSourceUpdate>>,
+        alert_recipients: Vec<Recipient<AlertSourceUpdate>>,
+    ) -> Self {
+        Self {
+            source_id,
+            api: Box::new(api),
+            period,
+            timer,
+            monitor_recipients,
+            alert_recipients,
+            cache_path: None,
+            cache: Default::default(),
+            is_loading: false,
+        }
+    }
+
+    /// Keeps a copy of the last monitors and alerts that loaded successfully at this path.
+    pub fn cache_path(mut self, path: Option<String>) -> Self {
+        self.cache_path = path;
+        self
+    }
+
+    fn load_cache(&mut self) {
+        let path = match self.cache_path {
+            Some(ref p) => p.clone(),
+            None => return,
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(err) => {
+                debug!("No cached monitors or alerts loaded from {}: {}", path, err);
+                return;
+            }
+        };
+
+        match serde_json::from_str::<SourceCache>(&contents) {
+            Ok(cache) => {
+                info!(
+                    "Using cached monitors and alerts until source is loaded (source_id={}, path={})",
+                    self.source_id, path
+                );
+                self.send_updates(cache.monitors, cache.alerts);
+            }
+            Err(err) => warn!("Ignoring unreadable cache at {}: {}", path, err),
+        }
+    }
+
+    fn save_cache(&self) {
+        let path = match self.cache_path {
+            Some(ref p) => p,
+            None => return,
+        };
+
+        let result = serde_json::to_string(&self.cache)
+            .map_err(Error::from)
+            .and_then(|contents| fs::write(path, contents).map_err(Error::from));
+
+        if let Err(err) = result {
+            error!("Failed to cache monitors and alerts at {}: {}", path, err);
+        }
+    }
+
+    fn send_updates(
+        &mut self,
+        monitors: Option<Vec<models::Monitor>>,
+        alerts: Option<Vec<models::Alert>>,
+    ) {
+        if let Some(monitors) = monitors {
+            let update = MonitorSourceUpdate {
+                source_id: self.source_id.clone(),
+                monitors: monitors.clone(),
+            };
+            for recipient in self.monitor_recipients.iter() {
+                if let Err(err) = recipient.do_send(update.clone()) {
+                    error!("Error delivering monitors: {}", err);
+                }
+            }
+            self.cache.monitors = Some(monitors);
+        }
+
+        if let Some(alerts) = alerts {
+            let update = AlertSourceUpdate {
+                source_id: self.source_id.clone(),
+                alerts: alerts.clone(),
+            };
+            for recipient in self.alert_recipients.iter() {
+                if let Err(err) = recipient.do_send(update.clone()) {
+                    error!("Error delivering alerts: {}", err);
+                }
+            }
+            self.cache.alerts = Some(alerts);
+        }
+    }
+}
+
+impl Actor for MonitorSourceActor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        self.load_cache();
+
+        if let Err(err) = self.timer.try_send(TimerSpec {
+            uid: self.source_id.clone(),
+            recipient: ctx.address().recipient(),
+            period: self.period,
+        }) {
+            error!(
+                "Failed to activate timer for source {}: {}",
+                self.source_id, err
+            );
+        }
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        warn!("MonitorSourceActor stopped (source_id={})", self.source_id);
+    }
+}
+
+/// Stops loading from the source, such as when it is removed from the config.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<(), Error>")]
+pub struct StopSource;
+
+impl Handler<StopSource> for MonitorSourceActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, _msg: StopSource, ctx: &mut Context<Self>) -> Self::Result {
+        info!("Stopping source (source_id={})", self.source_id);
+
+        self.timer.do_send(TimerCancel {
+            uid: self.source_id.clone(),
+        });
+        ctx.stop();
+
+        Ok(())
+    }
+}
+
+impl Handler<Timeout> for MonitorSourceActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, _msg: Timeout, ctx: &mut Context<Self>) -> Self::Result {
+        if self.is_loading {
+            debug!(
+                "Still loading from source. Skipping (source_id={})",
+                self.source_id
+            );
+            return Ok(());
+        }
+
+        debug!("Loading from source (source_id={})", self.source_id);
+
+        let monitors_future = if self.monitor_recipients.is_empty() {
+            None
+        } else {
+            Some(self.api.get_monitors())
+        };
+
+        let alerts_future = if self.alert_recipients.is_empty() {
+            None
+        } else {
+            Some(self.api.get_alerts())
+        };
+
+        let load_future = async move {
+            let monitors = match monitors_future {
+                Some(f) => Some(f.await),
+                None => None,
+            };
+            let alerts = match alerts_future {
+                Some(f) => Some(f.await),
+                None => None,
+            };
+            (monitors, alerts)
+        };
+
+        self.is_loading = true;
+
+        ctx.spawn(actix::fut::wrap_future::<_, Self>(load_future).map(
+            |(monitors, alerts), this, _ctx| {
+                this.is_loading = false;
+
+                let monitors = match monitors {
+                    Some(Ok(m)) => Some(m),
+                    Some(Err(err)) => {
+                        error!(
+                            "Failed to load monitors. Keeping the last loaded monitors (source_id={}): {}",
+                            this.source_id, err
+                        );
+                        None
+                    }
+                    None => None,
+                };
+
+                let alerts = match alerts {
+                    Some(Ok(a)) => Some(a),
+                    Some(Err(err)) => {
+                        error!(
+                            "Failed to load alerts. Keeping the last loaded alerts (source_id={}): {}",
+                            this.source_id, err
+                        );
+                        None
+                    }
+                    None => None,
+                };
+
+                if monitors.is_none() && alerts.is_none() {
+                    return;
+                }
+
+                this.send_updates(monitors, alerts);
+                this.save_cache();
+            },
+        ));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::{FileApi, MemoryApi};
+    use crate::monitoring::test_monitor;
+    use std::sync::{Arc, Mutex};
+
+    const SOURCE_ID: &str = "file://monitors.yaml";
+
+    /// Records the names of the monitors in every update it gets.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        updates: Arc<Mutex<Vec<Vec<String>>>>,
+    }
+
+    impl Actor for Recorder {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<MonitorSourceUpdate> for Recorder {
+        type Result = Result<(), Error>;
+
+        fn handle(&mut self, msg: MonitorSourceUpdate, _ctx: &mut Self::Context) -> Self::Result {
+            self.updates.lock().unwrap().push(
+                msg.monitors
+                    .into_iter()
+                    .map(|monitor| monitor.name)
+                    .collect(),
+            );
+            Ok(())
+        }
+    }
+
+    /// Starts a source with the API and cache, and returns the updates it sent once it loaded.
+    async fn load<A: ReadApi + 'static>(api: A, cache_path: &str) -> Vec<Vec<String>> {
+        let recorder = Recorder::default();
+        let source = MonitorSourceActor::new(
+            SOURCE_ID.to_owned(),
+            api,
+            Duration::from_secs(3600),
+            TimerActor::new().start(),
+            vec![recorder.clone().start().recipient()],
+            vec![],
+        )
+        .cache_path(Some(cache_path.to_owned()))
+        .start();
+
+        actix_rt::time::sleep(Duration::from_millis(100)).await;
+        source.send(StopSource).await.unwrap().unwrap();
+        let updates = recorder.updates.lock().unwrap().clone();
+        updates
+    }
+
+    #[actix_rt::test]
+    async fn uses_cached_monitors_when_source_fails_to_load() {
+        let path = std::env::temp_dir()
+            .join(format!("schnooty-source-cache-{}", std::process::id()))
+            .to_str()
+            .unwrap()
+            .to_owned();
+        let _ = fs::remove_file(&path);
+
+        // nothing is cached yet, so only the loaded monitors are sent
+        let api = MemoryApi::new(
+            vec![test_monitor("website"), test_monitor("database")],
+            vec![],
+        );
+        assert_eq!(load(api, &path).await, vec![vec!["website", "database"]]);
+
+        // after a restart, the cached monitors are used and kept when loading fails
+        let api = FileApi::new("/nonexistent/monitors.yaml");
+        assert_eq!(load(api, &path).await, vec![vec!["website", "database"]]);
+        let cache: SourceCache = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(cache.monitors.unwrap().len(), 2);
+
+        // a newer load replaces the cache
+        let api = MemoryApi::new(vec![test_monitor("website")], vec![]);
+        assert_eq!(
+            load(api, &path).await,
+            vec![vec!["website", "database"], vec!["website"]]
+        );
+        let api = FileApi::new("/nonexistent/monitors.yaml");
+        assert_eq!(load(api, &path).await, vec![vec!["website"]]);
+
+        let _ = fs::remove_file(&path);
+    }
 }
@@ -1,11 +1,11 @@
 use crate::actors::TimerActor;
 use crate::actors::*;
+use crate::duration::monitor_period;
 use crate::error::Error;
 use crate::openapi_client::models;
 use actix::clock::Instant;
 use log::*;
 use std::collections::HashMap;
-use std::time;
 
 pub struct SchedulerActor {
     monitors: HashMap<String, MonitorContainer>,
@@ -15,6 +15,7 @@ pub struct SchedulerActor {
 
 struct MonitorContainer {
     //uid: String,
+    source_id: String,
     monitor: models::Monitor,
 }
 
@@ -44,24 +45,60 @@ pub struct MonitorUpdate {
     pub monitor: models::Monitor,
 }
 
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<(), Error>")]
+pub struct MonitorRemoved {
+    /// Uniquely identifies the set of monitors this monitor belonged to
+    pub source_id: String,
+    pub monitor_name: String,
+}
+
+impl SchedulerActor {
+    fn remove_monitor(&mut self, monitor_name: &str) {
+        let uid = monitor_uid(monitor_name);
+
+        if self.monitors.remove(&uid).is_some() {
+            debug!("Removing monitor (monitor_name={})", monitor_name);
+            self.timer.do_send(TimerCancel { uid });
+        }
+    }
+}
+
 impl Handler<MonitorUpdate> for SchedulerActor {
     type Result = Result<(), Error>;
 
     fn handle(&mut self, msg: MonitorUpdate, ctx: &mut Context<Self>) -> Self::Result {
-        let uid = format!("https://api.schnooty.com/monitors/{}", msg.monitor.name);
+        let uid = monitor_uid(&msg.monitor.name);
 
         debug!("Handling monitor update");
 
+        if !msg.monitor.enabled {
+            info!(
+                "Monitor is disabled. It will not be run (monitor_name={})",
+                msg.monitor.name
+            );
+            self.remove_monitor(&msg.monitor.name);
+            return Ok(());
+        }
+
+        let period = match monitor_period(&msg.monitor) {
+            Ok(p) => p,
+            Err(err) => {
+                error!("Not scheduling monitor: {}", err);
+                return Err(err);
+            }
+        };
+
         self.monitors.insert(
             uid.clone(),
             MonitorContainer {
                 //uid: uid.clone(),
+                source_id: msg.source_id.clone(),
                 monitor: msg.monitor.clone(),
             },
         );
 
         let recipient = ctx.address().recipient();
-        let period = get_duration(&msg.monitor);
 
         self.timer.do_send(TimerSpec {
             uid,
@@ -79,13 +116,8 @@ impl Handler<Timeout> for SchedulerActor {
     fn handle(&mut self, msg: Timeout, _ctx: &mut Context<Self>) -> Self::Result {
         debug!("Waking up scheduler");
 
-        match self
-            .monitors
-            .iter()
-            .filter(|(ref uid, _)| *uid == &msg.uid)
-            .next()
-        {
-            Some((_, ref container)) => {
+        match self.monitors.get(&msg.uid) {
+            Some(container) => {
                 let message = ExecuteBatch {
                     monitors: vec![container.monitor.clone()],
                 };
@@ -104,40 +136,31 @@ impl Handler<Timeout> for SchedulerActor {
     }
 }
 
-fn get_duration(monitor: &models::Monitor) -> time::Duration {
-    let mut period_millis = to_milliseconds(&monitor.period);
-    if period_millis <= 0 {
-        warn!("Unusual period for monitor (id={:?})", monitor.id);
-        period_millis = 1000;
-    }
-    time::Duration::from_millis(period_millis as u64)
-}
+impl Handler<MonitorRemoved> for SchedulerActor {
+    type Result = Result<(), Error>;
 
-fn to_milliseconds(time: &str) -> i32 {
-    const BASE_10: u32 = 10;
-    let parts = time.split_whitespace();
-    for part in parts {
-        let mut unit = 0;
-
-        for c in part.chars() {
-            if c.is_digit(BASE_10) {
-                unit = unit * 10;
-                let unit_string = format!("{}", c);
-                unit += unit_string.parse::<i32>().unwrap();
-            } else {
-                return unit
-                    * match c {
-                        's' => SECOND,
-                        'm' => MINUTE,
-                        'h' => HOUR,
-                        'd' => DAY,
-                        _ => 0, // unreachable hopefully
-                    };
+    fn handle(&mut self, msg: MonitorRemoved, _ctx: &mut Context<Self>) -> Self::Result {
+        debug!(
+            "Handling monitor removal (source_id={}, monitor_name={})",
+            msg.source_id, msg.monitor_name
+        );
+
+        match self.monitors.get(&monitor_uid(&msg.monitor_name)) {
+            Some(container) if container.source_id != msg.source_id => {
+                debug!(
+                    "Monitor now belongs to another source. Not removing it (monitor_name={}, source_id={})",
+                    msg.monitor_name, container.source_id
+                );
             }
+            _ => self.remove_monitor(&msg.monitor_name),
         }
+
+        Ok(())
     }
+}
 
-    0
+fn monitor_uid(monitor_name: &str) -> String {
+    format!("https://api.schnooty.com/monitors/{}", monitor_name)
 }
 
 #[derive(Clone)]
@@ -145,9 +168,3 @@ struct ScheduleEvent {
     timestamp: Instant,
     monitor_name: String,
 }
-
-pub const MILLI: i32 = 1;
-pub const SECOND: i32 = 1000 * MILLI;
-pub const MINUTE: i32 = 60 * SECOND;
-pub const HOUR: i32 = MINUTE * 60;
-pub const DAY: i32 = HOUR * 24;
@@ -50,9 +50,11 @@ impl Actor for SessionActor {
 impl Handler<CurrentConfig> for SessionActor {
     type Result = Result<(), Error>;
 
-    fn handle(&mut self, _config_msg: CurrentConfig, ctx: &mut Context<Self>) -> Self::Result {
+    fn handle(&mut self, config_msg: CurrentConfig, ctx: &mut Context<Self>) -> Self::Result {
         debug!("Handling latest config for session");
 
+        self.config = config_msg.config;
+
         let uid = String::from("https://api.schnooty.com/sessions");
 
         if let Err(err) = self.timer.do_send(TimerSpec {
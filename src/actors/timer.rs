@@ -5,12 +5,15 @@ use std::time::Duration;
 
 pub struct TimerActor {
     schedule: HashMap<String, Receiver>,
+    /// Timers that fire once, keyed by UID
+    once: HashMap<String, SpawnHandle>,
 }
 
 impl TimerActor {
     pub fn new() -> Self {
         Self {
             schedule: HashMap::new(),
+            once: HashMap::new(),
         }
     }
 }
@@ -98,3 +101,68 @@ impl Handler<TimerSpec> for TimerActor {
         Ok(())
     }
 }
+
+/// Sends a single timeout after the delay. Setting a timer with the UID of one that has not
+/// fired yet replaces it.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<(), Error>")]
+pub struct TimerOnce {
+    pub uid: String,
+    pub recipient: Recipient<Timeout>,
+    pub delay: Duration,
+}
+
+impl Handler<TimerOnce> for TimerActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: TimerOnce, ctx: &mut Context<Self>) -> Self::Result {
+        debug!(
+            "Setting up one-off timer for {} in {:?}",
+            msg.uid, msg.delay
+        );
+        if let Some(handle) = self.once.remove(&msg.uid) {
+            debug!("Cancelling existing one-off timer");
+            ctx.cancel_future(handle);
+        }
+
+        let rec = msg.recipient;
+        let uid = msg.uid.clone();
+        let handle = ctx.run_later(msg.delay, move |this, _| {
+            this.once.remove(&uid);
+            if let Err(err) = rec.do_send(Timeout { uid: uid.clone() }) {
+                error!("Error sending timeout to {}: {}", uid, err);
+            }
+        });
+        self.once.insert(msg.uid, handle);
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<(), Error>")]
+pub struct TimerCancel {
+    pub uid: String,
+}
+
+impl Handler<TimerCancel> for TimerActor {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: TimerCancel, ctx: &mut Context<Self>) -> Self::Result {
+        match self.schedule.remove(&msg.uid) {
+            Some(s) => {
+                debug!("Cancelling timer for {}", msg.uid);
+                ctx.cancel_future(s.interval);
+            }
+            None => match self.once.remove(&msg.uid) {
+                Some(handle) => {
+                    debug!("Cancelling one-off timer for {}", msg.uid);
+                    ctx.cancel_future(handle);
+                }
+                None => debug!("No timer to cancel for {}", msg.uid),
+            },
+        }
+
+        Ok(())
+    }
+}
@@ -1,16 +1,41 @@
 #![allow(warnings)]
 
+use crate::alerts::exec_alert::{run_command, ExecGuard};
+use crate::alerts::log_alert::write_log_record;
+use crate::alerts::template::{
+    render_json_template, RenderedMessage, DEFAULT_EMAIL_BODY, DEFAULT_EMAIL_SUBJECT,
+};
 use crate::api::ApiFuture;
+use crate::duration;
 use crate::error::Error;
+use crate::http::HttpClient;
 use crate::openapi_client::models;
 use chrono::offset::Utc;
 use chrono::prelude::*;
+use hmac::{Hmac, Mac, NewMac};
 use lettre::smtp::authentication::Credentials;
 use lettre::smtp::extension::ClientId;
 use lettre::{ClientSecurity, ClientTlsParameters, Envelope, SendableEmail, SmtpClient, Transport};
 use lettre_email::EmailBuilder;
 use log::{debug, error, info};
 use native_tls::TlsConnector;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The header that carries the signature of a webhook body, unless the alert names another one.
+const DEFAULT_SIGNATURE_HEADER: &str = "X-Schnooty-Signature";
+
+/// The Slack Web API method used when a Slack alert has a bot token.
+const SLACK_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+/// The PagerDuty Events API v2 endpoint, unless the alert has its own url.
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// The Opsgenie API, unless the alert has its own url, such as https://api.eu.opsgenie.com.
+const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
 
 pub trait AlertApi {
     fn send_msteams_msg(
@@ -24,23 +49,83 @@ pub trait AlertApi {
         alert: &models::WebhookAlertBody,
         payload: &AlertPayload,
     ) -> ApiFuture<()>;
+    fn send_slack_msg(
+        &self,
+        alert: &models::SlackAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()>;
+    fn send_pagerduty_event(
+        &self,
+        alert: &models::PagerDutyAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()>;
+    fn send_opsgenie_alert(
+        &self,
+        alert: &models::OpsgenieAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()>;
+    fn send_log_record(
+        &self,
+        alert: &models::LogAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()>;
+    fn run_exec_command(
+        &self,
+        alert: &models::ExecAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()>;
 }
 
-pub struct AlertApiImpl {}
+pub struct AlertApiImpl {
+    /// Timestamp of the Slack message that started the thread of each DOWN monitor, keyed by
+    /// channel and monitor name
+    slack_threads: Arc<Mutex<HashMap<String, String>>>,
+    /// Held while writing to log files, so records are not interleaved with a rotation
+    log_file_lock: Arc<Mutex<()>>,
+    /// Exec alert commands that are running, keyed by command and monitor name
+    exec_running: Arc<Mutex<HashSet<String>>>,
+}
 
 impl AlertApiImpl {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            slack_threads: Arc::new(Mutex::new(HashMap::new())),
+            log_file_lock: Arc::new(Mutex::new(())),
+            exec_running: Arc::new(Mutex::new(HashSet::new())),
+        }
     }
 }
 
 impl AlertApi for AlertApiImpl {
     fn send_msteams_msg(
         &self,
-        _alert: &models::MsTeamsAlertBody,
-        _payload: &AlertPayload,
+        alert: &models::MsTeamsAlertBody,
+        payload: &AlertPayload,
     ) -> ApiFuture<()> {
-        Box::pin(async { Err(Error::new("MS Teams alert is not yet implemented")) })
+        let url = alert.url.clone();
+        let card = RenderedMessage::render(
+            alert.subject_template.as_deref(),
+            alert.body_template.as_deref(),
+            payload,
+        )
+        .map(|message| msteams_card(payload, &message));
+        Box::pin(async move {
+            let card = card?;
+            let url = match url {
+                Some(url) => url,
+                None => return Err(Error::new("MS Teams alert is misconfigured. It has no url")),
+            };
+
+            debug!("Sending MS Teams message (url={})", url);
+
+            let request = reqwest::Client::new()
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .body(card.to_string())
+                .build()?;
+            send_request(request, "MS Teams message").await?;
+            Ok(())
+        })
     }
 
     #[allow(unused_variables)]
@@ -48,59 +133,18 @@ impl AlertApi for AlertApiImpl {
         let alert = alert.clone();
         let payload: AlertPayload = payload.clone();
         Box::pin(async move {
-            let (subject, _body) = match payload.status.status {
-                models::MonitorStatusIndicator::OK => (
-                    format!("[Schnooty] Monitor {} has recovered", payload.monitor_name),
-                    format!(
-                        "The following monitor has recovered: {}\n",
-                        payload.monitor_name
-                    ),
+            let message = RenderedMessage::render(
+                Some(
+                    alert
+                        .subject_template
+                        .as_deref()
+                        .unwrap_or(DEFAULT_EMAIL_SUBJECT),
                 ),
-                models::MonitorStatusIndicator::DOWN => (
-                    format!("[Schnooty] Monitor {} is DOWN", payload.monitor_name),
-                    format!(
-                        "The following monitor is now DOWN (failing): {}\n",
-                        payload.monitor_name
-                    ),
-                ),
-            };
-            let mut email_body = String::new();
-            let timestamp: DateTime<Utc> = payload.status.timestamp;
-
-            email_body.push_str(&format!(
-                "The following monitor {}: {}\n\n",
-                if let models::MonitorStatusIndicator::OK = payload.status.status {
-                    "is up"
-                } else {
-                    "is down"
-                },
-                payload.monitor_name
-            ));
-            email_body.push_str(&format!("Got result: {}\n", payload.status.actual_result));
-            email_body.push_str(&format!(
-                "Expected result: {}\n\n",
-                payload.status.expected_result
-            ));
-            email_body.push_str(&format!("Description: {}\n", payload.status.description));
-            email_body.push_str(&format!("Timestamp: {}\n", timestamp));
-            email_body.push_str(&format!("Hostname: {}\n", payload.node_info.hostname));
-            email_body.push_str(&format!("Platform: {}\n", payload.node_info.platform));
-            email_body.push_str(&format!("CPU info: {}\n", payload.node_info.cpu));
-            email_body.push_str(&format!("RAM info: {}\n\n", payload.node_info.ram));
-
-            let logs = payload.status.log;
-
-            if logs.len() > 0 {
-                email_body.push_str("Monitor log below\n");
-                for log in logs.iter() {
-                    let timestamp: DateTime<Utc> = log.timestamp;
-                    email_body.push_str(&format!("{}: {}\n", timestamp, log.value));
-                }
-                email_body.push_str("\n\n");
-            }
-
-            email_body
-                .push_str("You can view your monitors by logging in at www.openmonitors.com\n");
+                Some(alert.body_template.as_deref().unwrap_or(DEFAULT_EMAIL_BODY)),
+                &payload,
+            )?;
+            let subject = message.subject.unwrap_or_default();
+            let email_body = message.body.unwrap_or_default();
 
             let (recipients, from, username, password, host, port, tls_mode) = match (
                 alert.recipients,
@@ -222,8 +266,13 @@ impl AlertApi for AlertApiImpl {
                 }
             };
 
-            match transport.send(email.into()) {
-                Ok(_) => info!("Email sent successfully!"),
+            // lettre's SMTP transport is blocking, so keep it off the event loop
+            match actix_rt::task::spawn_blocking(move || transport.send(email.into())).await {
+                Ok(Ok(_)) => info!("Email sent successfully!"),
+                Ok(Err(e)) => {
+                    error!("Failed to send email: {:?}", e);
+                    return Err(Error::from(e));
+                }
                 Err(e) => {
                     error!("Failed to send email: {:?}", e);
                     return Err(Error::from(e));
@@ -236,24 +285,1432 @@ impl AlertApi for AlertApiImpl {
 
     fn send_webhook(
         &self,
-        _alert: &models::WebhookAlertBody,
-        _payload: &AlertPayload,
+        alert: &models::WebhookAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()> {
+        let alert = alert.clone();
+        let payload = WebhookPayload::from(payload);
+        Box::pin(async move {
+            let url = match alert.url {
+                Some(ref url) => url.clone(),
+                None => return Err(Error::new("Webhook alert is misconfigured. It has no url")),
+            };
+
+            let body = match alert.body_template {
+                Some(ref template) => render_json_template(template, &payload)?,
+                None => serde_json::to_string(&payload)?,
+            };
+
+            let headers = alert.headers.clone().unwrap_or_default();
+            let mut builder = reqwest::Client::new().post(&url);
+
+            if !headers
+                .iter()
+                .any(|h| h.name.eq_ignore_ascii_case("Content-Type"))
+            {
+                builder = builder.header("Content-Type", "application/json");
+            }
+
+            for header in headers.iter() {
+                builder = builder.header(header.name.as_str(), header.value.as_str());
+            }
+
+            if let Some(ref secret) = alert.secret {
+                let signature_header = alert
+                    .signature_header
+                    .as_deref()
+                    .unwrap_or(DEFAULT_SIGNATURE_HEADER);
+                builder = builder.header(
+                    signature_header,
+                    format!("sha256={}", sign_hmac_sha256(secret, &body)),
+                );
+            }
+
+            debug!("Sending webhook (url={})", url);
+
+            send_request(builder.body(body).build()?, "Webhook").await?;
+            Ok(())
+        })
+    }
+
+    fn send_slack_msg(
+        &self,
+        alert: &models::SlackAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()> {
+        let alert = alert.clone();
+        let message = RenderedMessage::render(
+            alert.subject_template.as_deref(),
+            alert.body_template.as_deref(),
+            payload,
+        )
+        .map(|message| slack_message(payload, &message));
+        let slack_threads = self.slack_threads.clone();
+        let monitor_name = payload.monitor_name.clone();
+        let status = payload.status.status;
+        let is_digest = !payload.digest.is_empty();
+        Box::pin(async move {
+            let mut message = message?;
+            let token = match alert.token {
+                Some(token) => token,
+                None => {
+                    // an incoming webhook does not say which message it posted, so it cannot thread
+                    let url = match alert.url {
+                        Some(url) => url,
+                        None => {
+                            return Err(Error::new(
+                                "Slack alert is misconfigured. It needs a url or a token",
+                            ))
+                        }
+                    };
+
+                    debug!("Sending Slack message (url={})", url);
+
+                    let request = reqwest::Client::new()
+                        .post(&url)
+                        .header("Content-Type", "application/json")
+                        .body(message.to_string())
+                        .build()?;
+                    send_request(request, "Slack message").await?;
+                    return Ok(());
+                }
+            };
+
+            let channel = match alert.channel {
+                Some(channel) => channel,
+                None => {
+                    return Err(Error::new(
+                        "Slack alert is misconfigured. A token needs a channel",
+                    ))
+                }
+            };
+            let url = alert
+                .url
+                .unwrap_or_else(|| SLACK_POST_MESSAGE_URL.to_owned());
+
+            // a digest covers several monitors, so it is posted to the channel and not threaded
+            let thread_key = format!("{}/{}", channel, monitor_name);
+            let thread_ts = if is_digest {
+                None
+            } else {
+                slack_threads.lock().unwrap().get(&thread_key).cloned()
+            };
+
+            message["channel"] = serde_json::Value::String(channel.clone());
+            if let Some(ref ts) = thread_ts {
+                message["thread_ts"] = serde_json::Value::String(ts.clone());
+                // the recovery also goes to the channel, so everyone sees the outage is over
+                if status == models::MonitorStatusIndicator::OK {
+                    message["reply_broadcast"] = serde_json::Value::Bool(true);
+                }
+            }
+
+            debug!(
+                "Sending Slack message (channel={}, thread_ts={:?})",
+                channel, thread_ts
+            );
+
+            let request = reqwest::Client::new()
+                .post(&url)
+                .bearer_auth(token)
+                .header("Content-Type", "application/json; charset=utf-8")
+                .body(message.to_string())
+                .build()?;
+            let response = send_request(request, "Slack message").await?;
+            let body: serde_json::Value = serde_json::from_slice(&response.bytes().await?)?;
+
+            if body["ok"] != serde_json::Value::Bool(true) {
+                error!("Slack rejected the message (error={})", body["error"]);
+                return Err(Error::new(format!(
+                    "Slack rejected the message: {}",
+                    body["error"]
+                )));
+            }
+
+            if is_digest {
+                return Ok(());
+            }
+
+            let mut slack_threads = slack_threads.lock().unwrap();
+            match status {
+                models::MonitorStatusIndicator::DOWN => {
+                    if let (None, Some(ts)) = (thread_ts, body["ts"].as_str()) {
+                        slack_threads.insert(thread_key, ts.to_owned());
+                    }
+                }
+                models::MonitorStatusIndicator::OK => {
+                    slack_threads.remove(&thread_key);
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    fn send_pagerduty_event(
+        &self,
+        alert: &models::PagerDutyAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()> {
+        let url = alert
+            .url
+            .clone()
+            .unwrap_or_else(|| PAGERDUTY_EVENTS_URL.to_owned());
+        let event = check_pagerduty_alert(alert.routing_key.as_deref(), alert.severity.as_deref())
+            .and_then(|_| {
+                let message = RenderedMessage::render(
+                    alert.subject_template.as_deref(),
+                    alert.body_template.as_deref(),
+                    payload,
+                )?;
+                pagerduty_event(
+                    alert.routing_key.as_deref().unwrap_or_default(),
+                    alert.severity.as_deref().unwrap_or("critical"),
+                    payload,
+                    &message,
+                )
+            });
+        Box::pin(async move {
+            let event = event?;
+
+            debug!(
+                "Sending PagerDuty event (event_action={}, dedup_key={})",
+                event["event_action"], event["dedup_key"]
+            );
+
+            let request = reqwest::Client::new()
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .body(event.to_string())
+                .build()?;
+            send_request(request, "PagerDuty event").await?;
+            Ok(())
+        })
+    }
+
+    fn send_opsgenie_alert(
+        &self,
+        alert: &models::OpsgenieAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()> {
+        let request = opsgenie_request(alert, payload);
+        Box::pin(async move {
+            let request = request?;
+
+            debug!(
+                "Sending Opsgenie request ({} {})",
+                request.method(),
+                request.url()
+            );
+
+            send_request(request, "Opsgenie alert").await?;
+            Ok(())
+        })
+    }
+
+    fn send_log_record(
+        &self,
+        alert: &models::LogAlertBody,
+        payload: &AlertPayload,
+    ) -> ApiFuture<()> {
+        let alert = alert.clone();
+        let payload = payload.clone();
+        let log_file_lock = self.log_file_lock.clone();
+        Box::pin(async move {
+            debug!(
+                "Writing log alert record (target={}, monitor_name={})",
+                alert.target, payload.monitor_name
+            );
+
+            // files, sockets and rotation are all blocking, so keep them off the event loop
+            actix_rt::task::spawn_blocking(move || {
+                let _guard = log_file_lock.lock().unwrap();
+                write_log_record(&alert, &payload)
+            })
+            .await??;
+            Ok(())
+        })
+    }
+
+    fn run_exec_command(
+        &self,
+        alert: &models::ExecAlertBody,
+        payload: &AlertPayload,
     ) -> ApiFuture<()> {
-        Box::pin(async { Ok(()) })
+        let guard = ExecGuard::acquire(
+            &self.exec_running,
+            format!("{} {}", alert.command, payload.monitor_name),
+        );
+        let alert = alert.clone();
+        let payload = payload.clone();
+        Box::pin(async move {
+            let guard = guard?;
+
+            actix_rt::task::spawn_blocking(move || {
+                let _guard = guard;
+                run_command(&alert, &payload)
+            })
+            .await??;
+            Ok(())
+        })
+    }
+}
+
+/// Sends the request of an alert channel. Any response other than 2xx is a failure.
+async fn send_request(
+    request: reqwest::Request,
+    channel: &str,
+) -> Result<reqwest::Response, Error> {
+    let url = request.url().clone();
+    let response = HttpClient::new(request).send().await?;
+
+    if !response.status().is_success() {
+        error!(
+            "{} failed (url={}, status={})",
+            channel,
+            url,
+            response.status()
+        );
+        return Err(Error::new(format!(
+            "{} to {} got response status {}",
+            channel,
+            url,
+            response.status()
+        )));
+    }
+
+    info!("{} sent successfully (url={})", channel, url);
+
+    Ok(response)
+}
+
+/// The number of monitor log lines included in chat messages.
+const CHAT_LOG_LINES: usize = 5;
+
+/// The last few lines of the monitor log, oldest first.
+fn last_log_lines(payload: &AlertPayload) -> &[models::MonitorStatusLogEntry] {
+    let log = &payload.status.log;
+    &log[log.len().saturating_sub(CHAT_LOG_LINES)..]
+}
+
+/// The headline of the alert, such as `Monitor website is DOWN`.
+pub fn alert_title(payload: &AlertPayload) -> String {
+    if payload.delivery_failure.is_some() {
+        return format!(
+            "Alert delivery failing for monitor {}",
+            payload.monitor_name
+        );
+    }
+    if !payload.digest.is_empty() {
+        let (down, ok) = digest_counts(&payload.digest);
+        return format!(
+            "{} monitors changed state ({} DOWN, {} recovered)",
+            payload.digest.len(),
+            down,
+            ok
+        );
+    }
+    if payload.flapping {
+        return format!("Monitor {} is flapping", payload.monitor_name);
+    }
+    match payload.status.status {
+        models::MonitorStatusIndicator::OK => {
+            format!("Monitor {} has recovered", payload.monitor_name)
+        }
+        models::MonitorStatusIndicator::DOWN => format!("Monitor {} is DOWN", payload.monitor_name),
     }
 }
 
-#[derive(Clone, Debug)]
+/// The number of DOWN (including flapping notices) and recovered monitors in a digest.
+fn digest_counts(payloads: &[AlertPayload]) -> (usize, usize) {
+    let down = payloads
+        .iter()
+        .filter(|p| p.status.status == models::MonitorStatusIndicator::DOWN)
+        .count();
+    (down, payloads.len() - down)
+}
+
+/// How long the monitor has been DOWN, or was DOWN in total for a recovery, such as `2h 5m`.
+pub fn downtime_text(payload: &AlertPayload) -> Option<String> {
+    payload.downtime.map(duration::format_duration)
+}
+
+/// A MessageCard for a Teams incoming webhook, coloured green when the monitor is OK and red
+/// when it is DOWN. A body template replaces the sections with its text.
+fn msteams_card(payload: &AlertPayload, message: &RenderedMessage) -> serde_json::Value {
+    let title = message.title(payload);
+    let colour = match payload.status.status {
+        _ if payload.flapping => "FF8C00",
+        models::MonitorStatusIndicator::OK => "2DC72D",
+        models::MonitorStatusIndicator::DOWN => "D70000",
+    };
+
+    if let Some(ref text) = message.body {
+        return serde_json::json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "themeColor": colour,
+            "summary": title,
+            "title": title,
+            "text": text,
+        });
+    }
+
+    if !payload.digest.is_empty() {
+        let facts: Vec<serde_json::Value> = payload
+            .digest
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "name": alert_title(entry),
+                    "value": entry.status.actual_result,
+                })
+            })
+            .collect();
+        return serde_json::json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "themeColor": colour,
+            "summary": title,
+            "title": title,
+            "sections": [{
+                "activitySubtitle": format!("Host {}", payload.node_info.hostname),
+                "facts": facts,
+                "markdown": true,
+            }],
+        });
+    }
+
+    let mut section = serde_json::json!({
+        "activityTitle": title,
+        "activitySubtitle": payload.status.description,
+        "facts": [
+            { "name": "Expected result", "value": payload.status.expected_result },
+            { "name": "Actual result", "value": payload.status.actual_result },
+            { "name": "Host", "value": payload.node_info.hostname },
+            { "name": "Time", "value": payload.status.timestamp.to_rfc3339() },
+        ],
+        "markdown": true,
+    });
+
+    if let Some(downtime) = downtime_text(payload) {
+        section["facts"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({ "name": "Downtime", "value": downtime }));
+    }
+
+    let log_lines = last_log_lines(payload);
+    if !log_lines.is_empty() {
+        section["text"] = serde_json::Value::String(
+            log_lines
+                .iter()
+                .map(|entry| format!("`{}` {}", entry.timestamp.to_rfc3339(), entry.value))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        );
+    }
+
+    serde_json::json!({
+        "@type": "MessageCard",
+        "@context": "https://schema.org/extensions",
+        "themeColor": colour,
+        "summary": title,
+        "title": title,
+        "sections": [section],
+    })
+}
+/// A Block Kit message for Slack, with the title as the notification text. A body template
+/// replaces the blocks under the header with its text.
+fn slack_message(payload: &AlertPayload, message: &RenderedMessage) -> serde_json::Value {
+    let title = message.title(payload);
+    let emoji = match payload.status.status {
+        _ if payload.flapping => ":large_orange_circle:",
+        models::MonitorStatusIndicator::OK => ":large_green_circle:",
+        models::MonitorStatusIndicator::DOWN => ":red_circle:",
+    };
+
+    let field = |name: &str, value: &str| {
+        serde_json::json!({
+            "type": "mrkdwn",
+            "text": format!("*{}*\n{}", name, escape_slack(value)),
+        })
+    };
+
+    let header = serde_json::json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": format!("{} {}", emoji, title), "emoji": true },
+    });
+
+    if let Some(ref text) = message.body {
+        return serde_json::json!({
+            "text": title,
+            "blocks": [
+                header,
+                {
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": text },
+                },
+            ],
+        });
+    }
+
+    if !payload.digest.is_empty() {
+        let lines: Vec<String> = payload
+            .digest
+            .iter()
+            .map(|entry| {
+                format!(
+                    "• *{}*: {}",
+                    escape_slack(&alert_title(entry)),
+                    escape_slack(&entry.status.actual_result)
+                )
+            })
+            .collect();
+        return serde_json::json!({
+            "text": title,
+            "blocks": [
+                header,
+                {
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": lines.join("\n") },
+                },
+            ],
+        });
+    }
+
+    let mut blocks = vec![
+        header,
+        serde_json::json!({
+            "type": "section",
+            "fields": [
+                field("Expected result", &payload.status.expected_result),
+                field("Actual result", &payload.status.actual_result),
+                field("Host", &payload.node_info.hostname),
+                field("Time", &payload.status.timestamp.to_rfc3339()),
+            ],
+        }),
+    ];
+
+    if let Some(downtime) = downtime_text(payload) {
+        blocks[1]["fields"]
+            .as_array_mut()
+            .unwrap()
+            .push(field("Downtime", &downtime));
+    }
+
+    let log_lines = last_log_lines(payload);
+    if !log_lines.is_empty() {
+        let log_text = log_lines
+            .iter()
+            .map(|entry| format!("{} {}", entry.timestamp.to_rfc3339(), entry.value))
+            .collect::<Vec<_>>()
+            .join("\n");
+        blocks.push(serde_json::json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": format!("```{}```", escape_slack(&log_text)) },
+        }));
+    }
+
+    serde_json::json!({
+        "text": title,
+        "blocks": blocks,
+    })
+}
+
+/// Checks the settings of a PagerDuty alert without which its events cannot be sent.
+pub fn check_pagerduty_alert(
+    routing_key: Option<&str>,
+    severity: Option<&str>,
+) -> Result<(), Error> {
+    if routing_key.is_none() {
+        return Err(Error::new(
+            "PagerDuty alert is misconfigured. It has no routingKey",
+        ));
+    }
+    let severity = severity.unwrap_or("critical");
+    if !["critical", "error", "warning", "info"].contains(&severity) {
+        return Err(Error::new(format!(
+            "PagerDuty alert is misconfigured. Severity {} is not one of critical, error, warning or info",
+            severity
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the settings of an Opsgenie alert without which it cannot be created.
+pub fn check_opsgenie_alert(
+    api_key: Option<&str>,
+    priority: Option<&str>,
+    responders: Option<&[String]>,
+) -> Result<(), Error> {
+    if api_key.is_none() {
+        return Err(Error::new(
+            "Opsgenie alert is misconfigured. It has no apiKey",
+        ));
+    }
+    let priority = priority.unwrap_or("P3");
+    if !["P1", "P2", "P3", "P4", "P5"].contains(&priority) {
+        return Err(Error::new(format!(
+            "Opsgenie alert is misconfigured. Priority {} is not one of P1 to P5",
+            priority
+        )));
+    }
+    for responder in responders.into_iter().flatten() {
+        opsgenie_responder(responder)?;
+    }
+    Ok(())
+}
+
+/// Identifies the incident or alert of a monitor in PagerDuty and Opsgenie. A notice that an
+/// alert could not be sent gets its own, so it does not touch the incident it is about.
+fn incident_key(payload: &AlertPayload) -> String {
+    match payload.delivery_failure {
+        Some(_) => format!(
+            "{}/{}/delivery-failure",
+            payload.node_info.hostname, payload.monitor_name
+        ),
+        None => format!("{}/{}", payload.node_info.hostname, payload.monitor_name),
+    }
+}
+
+/// An Events API v2 event that triggers an incident when the monitor is DOWN and resolves it
+/// when the monitor recovers. The dedup key ties both to the same incident. The templates
+/// replace the summary and the description in the details.
+fn pagerduty_event(
+    routing_key: &str,
+    severity: &str,
+    payload: &AlertPayload,
+    message: &RenderedMessage,
+) -> Result<serde_json::Value, Error> {
+    let dedup_key = incident_key(payload);
+
+    if payload.status.status == models::MonitorStatusIndicator::OK {
+        return Ok(serde_json::json!({
+            "routing_key": routing_key,
+            "event_action": "resolve",
+            "dedup_key": dedup_key,
+        }));
+    }
+
+    Ok(serde_json::json!({
+        "routing_key": routing_key,
+        "event_action": "trigger",
+        "dedup_key": dedup_key,
+        "payload": {
+            "summary": message.subject.clone().unwrap_or_else(|| format!(
+                "{} on {}: {}",
+                alert_title(payload), payload.node_info.hostname, payload.status.actual_result
+            )),
+            "source": payload.node_info.hostname,
+            "severity": severity,
+            "timestamp": payload.status.timestamp.to_rfc3339(),
+            "component": payload.monitor_name,
+            "class": payload.status.monitor_type,
+            "custom_details": {
+                "description": message.body.as_ref().unwrap_or(&payload.status.description),
+                "expected_result": payload.status.expected_result,
+                "actual_result": payload.status.actual_result,
+                "platform": payload.node_info.platform,
+                "downtime": downtime_text(payload),
+                "log": last_log_lines(payload)
+                    .iter()
+                    .map(|entry| format!("{} {}", entry.timestamp.to_rfc3339(), entry.value))
+                    .collect::<Vec<_>>(),
+            },
+        },
+    }))
+}
+
+/// Creates an Opsgenie alert when the monitor is DOWN and closes it when the monitor recovers.
+/// Both use the same alias, so Opsgenie keeps one open alert per monitor. The templates replace
+/// the message and description of the alert.
+fn opsgenie_request(
+    alert: &models::OpsgenieAlertBody,
+    payload: &AlertPayload,
+) -> Result<reqwest::Request, Error> {
+    let rendered = RenderedMessage::render(
+        alert.subject_template.as_deref(),
+        alert.body_template.as_deref(),
+        payload,
+    )?;
+    check_opsgenie_alert(
+        alert.api_key.as_deref(),
+        alert.priority.as_deref(),
+        alert.responders.as_deref(),
+    )?;
+    let api_key = alert.api_key.as_deref().unwrap_or_default();
+    let priority = alert.priority.as_deref().unwrap_or("P3");
+
+    let alias = incident_key(payload);
+    let base_url = alert.url.as_deref().unwrap_or(OPSGENIE_API_URL);
+    let mut url = reqwest::Url::parse(base_url)
+        .map_err(|err| Error::new(format!("Opsgenie url {} is invalid: {}", base_url, err)))?;
+
+    let body = match payload.status.status {
+        models::MonitorStatusIndicator::DOWN => {
+            url.path_segments_mut()
+                .map_err(|_| Error::new(format!("Opsgenie url {} is invalid", base_url)))?
+                .pop_if_empty()
+                .extend(&["v2", "alerts"]);
+
+            let responders = alert
+                .responders
+                .iter()
+                .flatten()
+                .map(|responder| opsgenie_responder(responder))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let log_text = last_log_lines(payload)
+                .iter()
+                .map(|entry| format!("{} {}", entry.timestamp.to_rfc3339(), entry.value))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let description = rendered.body.unwrap_or_else(|| {
+                let mut description = payload
+                    .monitor_description
+                    .clone()
+                    .unwrap_or_else(|| payload.status.description.clone());
+                description.push_str(&format!(
+                    "\n\nExpected result: {}\nActual result: {}",
+                    payload.status.expected_result, payload.status.actual_result
+                ));
+                if !log_text.is_empty() {
+                    description.push_str(&format!("\n\nMonitor log:\n{}", log_text));
+                }
+                description
+            });
+
+            let message: String = rendered
+                .subject
+                .unwrap_or_else(|| {
+                    format!("{} on {}", alert_title(payload), payload.node_info.hostname)
+                })
+                .chars()
+                .take(130)
+                .collect();
+
+            serde_json::json!({
+                "message": message,
+                "alias": alias,
+                "description": description.chars().take(15000).collect::<String>(),
+                "responders": responders,
+                "priority": priority,
+                "source": payload.node_info.hostname,
+                "entity": payload.monitor_name,
+                "details": {
+                    "monitorType": payload.status.monitor_type.to_string(),
+                    "expectedResult": payload.status.expected_result,
+                    "actualResult": payload.status.actual_result,
+                    "timestamp": payload.status.timestamp.to_rfc3339(),
+                    "platform": payload.node_info.platform,
+                    "downtime": downtime_text(payload).unwrap_or_default(),
+                    "log": log_text,
+                },
+            })
+        }
+        models::MonitorStatusIndicator::OK => {
+            url.path_segments_mut()
+                .map_err(|_| Error::new(format!("Opsgenie url {} is invalid", base_url)))?
+                .pop_if_empty()
+                .extend(&["v2", "alerts", &alias, "close"]);
+            url.query_pairs_mut().append_pair("identifierType", "alias");
+
+            serde_json::json!({
+                "source": payload.node_info.hostname,
+                "note": match downtime_text(payload) {
+                    Some(downtime) => format!(
+                        "Monitor {} has recovered after {} DOWN: {}",
+                        payload.monitor_name, downtime, payload.status.actual_result
+                    ),
+                    None => format!(
+                        "Monitor {} has recovered: {}",
+                        payload.monitor_name, payload.status.actual_result
+                    ),
+                },
+            })
+        }
+    };
+
+    Ok(reqwest::Client::new()
+        .post(url)
+        .header("Authorization", format!("GenieKey {}", api_key))
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .build()?)
+}
+
+/// Parses a responder like `team:ops` or `user:jo@example.com`. Users are identified by
+/// username and the others by name.
+fn opsgenie_responder(responder: &str) -> Result<serde_json::Value, Error> {
+    let mut parts = responder.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some("user"), Some(username)) => Ok(serde_json::json!({
+            "type": "user",
+            "username": username,
+        })),
+        (Some(type_ @ "team"), Some(name))
+        | (Some(type_ @ "escalation"), Some(name))
+        | (Some(type_ @ "schedule"), Some(name)) => Ok(serde_json::json!({
+            "type": type_,
+            "name": name,
+        })),
+        _ => Err(Error::new(format!(
+            "Opsgenie responder {} should look like team:<name>, user:<username>, escalation:<name> or schedule:<name>",
+            responder
+        ))),
+    }
+}
+
+/// Escapes the characters that Slack treats as control characters in message text.
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Hex-encoded HMAC-SHA256 of the body.
+fn sign_hmac_sha256(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AlertPayload {
     pub monitor_name: String,
+    pub monitor_description: Option<String>,
     pub status: models::MonitorStatus,
     pub node_info: NodeInfo,
+    /// When the monitor went DOWN
+    pub down_since: Option<DateTime<Utc>>,
+    /// How long the monitor has been DOWN so far, or for a recovery how long it was DOWN in total
+    pub downtime: Option<Duration>,
+    /// Whether this is the notice that the monitor started flapping. It is sent as DOWN.
+    pub flapping: bool,
+    /// The state changes collected in a group window, when this is a digest of them
+    pub digest: Vec<AlertPayload>,
+    /// Why alerts could not be delivered, when this reports that on the fallback alert
+    #[serde(default)]
+    pub delivery_failure: Option<String>,
+}
+
+impl AlertPayload {
+    /// A single message for several state changes. It is DOWN if any monitor is DOWN, and its
+    /// status is otherwise that of the latest change.
+    pub fn digest(payloads: Vec<AlertPayload>) -> Self {
+        let latest = payloads
+            .iter()
+            .max_by_key(|p| p.status.timestamp)
+            .cloned()
+            .expect("a digest has at least one state change");
+        let (down, _) = digest_counts(&payloads);
+
+        let mut status = latest.status;
+        status.status = if down > 0 {
+            models::MonitorStatusIndicator::DOWN
+        } else {
+            models::MonitorStatusIndicator::OK
+        };
+        status.description = format!("{} monitors changed state", payloads.len());
+        status.expected_result = String::new();
+        status.actual_result = String::new();
+        status.log = vec![];
+
+        Self {
+            monitor_name: format!("{} monitors", payloads.len()),
+            monitor_description: None,
+            status,
+            node_info: latest.node_info,
+            down_since: None,
+            downtime: None,
+            flapping: false,
+            digest: payloads,
+            delivery_failure: None,
+        }
+    }
+}
+
+/// The JSON body of a webhook, and the values available to a webhook body template.
+///
+/// ```json
+/// {
+///   "monitorName": "website",
+///   "monitorType": "http",
+///   "status": "down",
+///   "flapping": false,
+///   "description": "Checks the website",
+///   "expectedResult": "Status 200",
+///   "actualResult": "Status 503",
+///   "timestamp": "2021-01-01T00:00:00Z",
+///   "log": [{ "timestamp": "2021-01-01T00:00:00Z", "value": "Sending request" }],
+///   "nodeInfo": { "hostname": "web-1", "platform": "linux", "cpu": "...", "ram": "..." },
+///   "downSince": "2020-12-31T23:00:00Z",
+///   "downtimeSeconds": 3600
+/// }
+/// ```
+///
+/// A digest has a summary in these fields, and a `digest` list with one of these for each
+/// state change.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub monitor_name: String,
+    pub monitor_type: models::MonitorType,
+    pub status: models::MonitorStatusIndicator,
+    pub flapping: bool,
+    pub description: String,
+    pub expected_result: String,
+    pub actual_result: String,
+    pub timestamp: DateTime<Utc>,
+    pub log: Vec<models::MonitorStatusLogEntry>,
+    pub node_info: NodeInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub down_since: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downtime_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub digest: Vec<WebhookPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_failure: Option<String>,
+}
+
+impl From<&AlertPayload> for WebhookPayload {
+    fn from(payload: &AlertPayload) -> Self {
+        Self {
+            monitor_name: payload.monitor_name.clone(),
+            monitor_type: payload.status.monitor_type,
+            status: payload.status.status,
+            flapping: payload.flapping,
+            description: payload.status.description.clone(),
+            expected_result: payload.status.expected_result.clone(),
+            actual_result: payload.status.actual_result.clone(),
+            timestamp: payload.status.timestamp,
+            log: payload.status.log.clone(),
+            node_info: payload.node_info.clone(),
+            down_since: payload.down_since,
+            downtime_seconds: payload.downtime.map(|d| d.as_secs()),
+            digest: payload.digest.iter().map(WebhookPayload::from).collect(),
+            delivery_failure: payload.delivery_failure.clone(),
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NodeInfo {
     pub hostname: String,
     pub platform: String,
     pub cpu: String,
     pub ram: String,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::monitoring::MonitorStatusBuilder;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Accepts one request on a local port and responds with the status code. The raw request
+    /// is sent back on the channel.
+    fn serve_once(status: u16) -> (String, mpsc::Receiver<String>) {
+        serve(vec![(status, "")])
+    }
+
+    /// Accepts a request for each response, in order. The raw requests are sent back on the
+    /// channel.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, response_body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+
+                loop {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some(header_end) = text.find("\r\n\r\n") {
+                        let content_length = text[..header_end]
+                            .lines()
+                            .find_map(|line| {
+                                let lower = line.to_ascii_lowercase();
+                                lower
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= header_end + 4 + content_length || read == 0 {
+                            break;
+                        }
+                    }
+                }
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    response_body.len(),
+                    response_body
+                )
+                .unwrap();
+                sender
+                    .send(String::from_utf8_lossy(&request).to_string())
+                    .unwrap();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    fn split_request(request: &str) -> (String, String) {
+        let header_end = request.find("\r\n\r\n").unwrap();
+        (
+            request[..header_end].to_ascii_lowercase(),
+            request[header_end + 4..].to_owned(),
+        )
+    }
+
+    fn payload() -> AlertPayload {
+        let status = MonitorStatusBuilder::new(
+            "website",
+            models::MonitorType::HTTP,
+            Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+        )
+        .description("Checks the website")
+        .down("Status 200", "Status \"503\"");
+
+        AlertPayload {
+            monitor_name: "website".to_owned(),
+            monitor_description: Some("The company website".to_owned()),
+            status,
+            node_info: NodeInfo {
+                hostname: "web-1".to_owned(),
+                platform: "linux".to_owned(),
+                cpu: "4 logical cores".to_owned(),
+                ram: "1 KB used".to_owned(),
+            },
+            down_since: None,
+            downtime: None,
+            flapping: false,
+            digest: vec![],
+            delivery_failure: None,
+        }
+    }
+
+    fn webhook(url: &str) -> models::WebhookAlertBody {
+        let mut alert = models::WebhookAlertBody::new();
+        alert.url = Some(url.to_owned());
+        alert
+    }
+
+    #[actix_rt::test]
+    async fn posts_json_payload() {
+        let (url, request) = serve_once(200);
+
+        AlertApiImpl::new()
+            .send_webhook(&webhook(&url), &payload())
+            .await
+            .unwrap();
+
+        let (headers, body) = split_request(&request.recv().unwrap());
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert!(headers.starts_with("post /hook http/1.1"));
+        assert!(headers.contains("content-type: application/json"));
+        assert_eq!(body["monitorName"], "website");
+        assert_eq!(body["monitorType"], "http");
+        assert_eq!(body["status"], "down");
+        assert_eq!(body["expectedResult"], "Status 200");
+        assert_eq!(body["actualResult"], "Status \"503\"");
+        assert_eq!(body["nodeInfo"]["hostname"], "web-1");
+        assert!(!headers.contains("x-schnooty-signature"));
+    }
+
+    #[actix_rt::test]
+    async fn signs_body_with_secret() {
+        let (url, request) = serve_once(204);
+        let mut alert = webhook(&url);
+        alert.secret = Some("s3cret".to_owned());
+        alert.signature_header = Some("X-Signature".to_owned());
+
+        AlertApiImpl::new()
+            .send_webhook(&alert, &payload())
+            .await
+            .unwrap();
+
+        let (headers, body) = split_request(&request.recv().unwrap());
+
+        assert!(headers.contains(&format!(
+            "x-signature: sha256={}",
+            sign_hmac_sha256("s3cret", &body)
+        )));
+    }
+
+    #[actix_rt::test]
+    async fn renders_body_template_with_json_escaping() {
+        let (url, request) = serve_once(200);
+        let mut alert = webhook(&url);
+        alert.body_template =
+            Some(r#"{"text": "{{monitorName}} is {{status}}: {{actualResult}}"}"#.to_owned());
+
+        AlertApiImpl::new()
+            .send_webhook(&alert, &payload())
+            .await
+            .unwrap();
+
+        let (_, body) = split_request(&request.recv().unwrap());
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(body["text"], "website is down: Status \"503\"");
+    }
+
+    #[actix_rt::test]
+    async fn fails_on_unknown_template_field() {
+        let mut alert = webhook("http://127.0.0.1:9/hook");
+        alert.body_template = Some("{{monitorNmae}}".to_owned());
+
+        assert!(AlertApiImpl::new()
+            .send_webhook(&alert, &payload())
+            .await
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn fails_on_non_success_status() {
+        let (url, _request) = serve_once(500);
+
+        assert!(AlertApiImpl::new()
+            .send_webhook(&webhook(&url), &payload())
+            .await
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn posts_msteams_card() {
+        let (url, request) = serve_once(200);
+        let mut alert = models::MsTeamsAlertBody::new();
+        alert.url = Some(url);
+
+        let mut payload = payload();
+        for i in 0..7 {
+            payload.status.log.push(models::MonitorStatusLogEntry::new(
+                Utc.timestamp_opt(1_600_000_000 + i, 0).unwrap(),
+                format!("line {}", i),
+            ));
+        }
+
+        AlertApiImpl::new()
+            .send_msteams_msg(&alert, &payload)
+            .await
+            .unwrap();
+
+        let (_, body) = split_request(&request.recv().unwrap());
+        let card: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let section = &card["sections"][0];
+        let text = section["text"].as_str().unwrap();
+
+        assert_eq!(card["@type"], "MessageCard");
+        assert_eq!(card["themeColor"], "D70000");
+        assert_eq!(card["title"], "Monitor website is DOWN");
+        assert_eq!(section["facts"][0]["value"], "Status 200");
+        assert_eq!(section["facts"][1]["value"], "Status \"503\"");
+        assert_eq!(section["facts"][2]["value"], "web-1");
+        assert!(!text.contains("line 1"));
+        assert!(text.contains("line 2"));
+        assert!(text.contains("line 6"));
+    }
+
+    #[test]
+    fn colours_recovered_msteams_card_green() {
+        let mut payload = payload();
+        payload.status.status = models::MonitorStatusIndicator::OK;
+
+        let card = msteams_card(&payload, &RenderedMessage::default());
+
+        assert_eq!(card["themeColor"], "2DC72D");
+        assert_eq!(card["title"], "Monitor website has recovered");
+        assert!(card["sections"][0].get("text").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn posts_slack_message_to_incoming_webhook() {
+        let (url, request) = serve_once(200);
+        let mut alert = models::SlackAlertBody::new();
+        alert.url = Some(url);
+
+        AlertApiImpl::new()
+            .send_slack_msg(&alert, &payload())
+            .await
+            .unwrap();
+
+        let (headers, body) = split_request(&request.recv().unwrap());
+        let message: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert!(!headers.contains("authorization"));
+        assert_eq!(message["text"], "Monitor website is DOWN");
+        assert_eq!(message["blocks"][0]["type"], "header");
+        assert_eq!(
+            message["blocks"][1]["fields"][1]["text"],
+            "*Actual result*\nStatus \"503\""
+        );
+        assert!(message.get("channel").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn posts_slack_message_from_templates() {
+        let (url, request) = serve_once(200);
+        let mut alert = models::SlackAlertBody::new();
+        alert.url = Some(url);
+        alert.subject_template = Some("{{monitorName}} needs a look".to_owned());
+        alert.body_template = Some("Got {{actualResult}} on {{nodeInfo.hostname}}".to_owned());
+
+        AlertApiImpl::new()
+            .send_slack_msg(&alert, &payload())
+            .await
+            .unwrap();
+
+        let (_, body) = split_request(&request.recv().unwrap());
+        let message: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(message["text"], "website needs a look");
+        assert_eq!(
+            message["blocks"][0]["text"]["text"],
+            ":red_circle: website needs a look"
+        );
+        assert_eq!(
+            message["blocks"][1]["text"]["text"],
+            "Got Status \"503\" on web-1"
+        );
+        assert_eq!(message["blocks"].as_array().unwrap().len(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn posts_digest_of_state_changes() {
+        let mut recovered = payload();
+        recovered.monitor_name = "api".to_owned();
+        recovered.status.status = models::MonitorStatusIndicator::OK;
+        recovered.status.actual_result = "Status 200".to_owned();
+        let digest = AlertPayload::digest(vec![payload(), recovered]);
+
+        let (url, request) = serve_once(200);
+        AlertApiImpl::new()
+            .send_webhook(&webhook(&url), &digest)
+            .await
+            .unwrap();
+        let (_, body) = split_request(&request.recv().unwrap());
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(body["monitorName"], "2 monitors");
+        assert_eq!(body["status"], "down");
+        assert_eq!(body["digest"][0]["monitorName"], "website");
+        assert_eq!(body["digest"][1]["status"], "ok");
+
+        let message = slack_message(&digest, &RenderedMessage::default());
+        assert_eq!(
+            message["text"],
+            "2 monitors changed state (1 DOWN, 1 recovered)"
+        );
+        assert_eq!(
+            message["blocks"][1]["text"]["text"],
+            "• *Monitor website is DOWN*: Status \"503\"\n• *Monitor api has recovered*: Status 200"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn threads_slack_messages_per_monitor() {
+        let (url, request) = serve(vec![
+            (200, r#"{"ok": true, "ts": "1600000000.000100"}"#),
+            (200, r#"{"ok": true, "ts": "1600000001.000100"}"#),
+            (200, r#"{"ok": true, "ts": "1600000002.000100"}"#),
+            (200, r#"{"ok": true, "ts": "1600000003.000100"}"#),
+        ]);
+        let mut alert = models::SlackAlertBody::new();
+        alert.url = Some(url);
+        alert.token = Some("xoxb-token".to_owned());
+        alert.channel = Some("#alerts".to_owned());
+
+        let api = AlertApiImpl::new();
+        let down = payload();
+        let mut recovered = payload();
+        recovered.status.status = models::MonitorStatusIndicator::OK;
+
+        for payload in vec![&down, &down, &recovered, &down] {
+            api.send_slack_msg(&alert, payload).await.unwrap();
+        }
+
+        let messages: Vec<(String, serde_json::Value)> = (0..4)
+            .map(|_| {
+                let (headers, body) = split_request(&request.recv().unwrap());
+                (headers, serde_json::from_str(&body).unwrap())
+            })
+            .collect();
+
+        assert!(messages[0].0.contains("authorization: bearer xoxb-token"));
+        assert_eq!(messages[0].1["channel"], "#alerts");
+        assert!(messages[0].1.get("thread_ts").is_none());
+        assert_eq!(messages[1].1["thread_ts"], "1600000000.000100");
+        assert_eq!(messages[2].1["thread_ts"], "1600000000.000100");
+        assert_eq!(messages[2].1["reply_broadcast"], true);
+        assert!(messages[3].1.get("thread_ts").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn fails_when_slack_rejects_message() {
+        let (url, _request) = serve(vec![(
+            200,
+            r#"{"ok": false, "error": "channel_not_found"}"#,
+        )]);
+        let mut alert = models::SlackAlertBody::new();
+        alert.url = Some(url);
+        alert.token = Some("xoxb-token".to_owned());
+        alert.channel = Some("#nowhere".to_owned());
+
+        assert!(AlertApiImpl::new()
+            .send_slack_msg(&alert, &payload())
+            .await
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn triggers_and_resolves_pagerduty_incident() {
+        let (url, request) = serve(vec![(202, r#"{"status": "success"}"#); 2]);
+        let mut alert = models::PagerDutyAlertBody::new();
+        alert.url = Some(url);
+        alert.routing_key = Some("R0UT1NG".to_owned());
+        alert.severity = Some("warning".to_owned());
+
+        let api = AlertApiImpl::new();
+        let down = payload();
+        let mut recovered = payload();
+        recovered.status.status = models::MonitorStatusIndicator::OK;
+
+        api.send_pagerduty_event(&alert, &down).await.unwrap();
+        api.send_pagerduty_event(&alert, &recovered).await.unwrap();
+
+        let (_, trigger) = split_request(&request.recv().unwrap());
+        let (_, resolve) = split_request(&request.recv().unwrap());
+        let trigger: serde_json::Value = serde_json::from_str(&trigger).unwrap();
+        let resolve: serde_json::Value = serde_json::from_str(&resolve).unwrap();
+
+        assert_eq!(trigger["routing_key"], "R0UT1NG");
+        assert_eq!(trigger["event_action"], "trigger");
+        assert_eq!(trigger["dedup_key"], "web-1/website");
+        assert_eq!(trigger["payload"]["severity"], "warning");
+        assert_eq!(trigger["payload"]["source"], "web-1");
+        assert_eq!(trigger["payload"]["component"], "website");
+        assert_eq!(
+            trigger["payload"]["custom_details"]["actual_result"],
+            "Status \"503\""
+        );
+        assert_eq!(resolve["event_action"], "resolve");
+        assert_eq!(resolve["dedup_key"], "web-1/website");
+        assert!(resolve.get("payload").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn triggers_own_pagerduty_incident_for_delivery_failure() {
+        let (url, request) = serve(vec![(202, r#"{"status": "success"}"#)]);
+        let mut alert = models::PagerDutyAlertBody::new();
+        alert.url = Some(url);
+        alert.routing_key = Some("R0UT1NG".to_owned());
+
+        let mut notice = payload();
+        notice.delivery_failure = Some("The webhook alert could not be sent".to_owned());
+        AlertApiImpl::new()
+            .send_pagerduty_event(&alert, &notice)
+            .await
+            .unwrap();
+
+        let (_, trigger) = split_request(&request.recv().unwrap());
+        let trigger: serde_json::Value = serde_json::from_str(&trigger).unwrap();
+        assert_eq!(trigger["event_action"], "trigger");
+        assert_eq!(trigger["dedup_key"], "web-1/website/delivery-failure");
+    }
+
+    #[actix_rt::test]
+    async fn rejects_unknown_pagerduty_severity() {
+        let mut alert = models::PagerDutyAlertBody::new();
+        alert.url = Some("http://127.0.0.1:9/enqueue".to_owned());
+        alert.routing_key = Some("R0UT1NG".to_owned());
+        alert.severity = Some("disaster".to_owned());
+
+        assert!(AlertApiImpl::new()
+            .send_pagerduty_event(&alert, &payload())
+            .await
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn creates_and_closes_opsgenie_alert() {
+        let (url, request) = serve(vec![(202, r#"{"result": "Request will be processed"}"#); 2]);
+        let mut alert = models::OpsgenieAlertBody::new();
+        alert.url = Some(format!("{}/", url.trim_end_matches("/hook")));
+        alert.api_key = Some("k3y".to_owned());
+        alert.priority = Some("P2".to_owned());
+        alert.responders = Some(vec![
+            "team:ops".to_owned(),
+            "user:jo@example.com".to_owned(),
+        ]);
+
+        let api = AlertApiImpl::new();
+        let mut down = payload();
+        down.status.log.push(models::MonitorStatusLogEntry::new(
+            Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+            "Connection refused".to_owned(),
+        ));
+        let mut recovered = payload();
+        recovered.status.status = models::MonitorStatusIndicator::OK;
+
+        api.send_opsgenie_alert(&alert, &down).await.unwrap();
+        api.send_opsgenie_alert(&alert, &recovered).await.unwrap();
+
+        let (create_headers, create) = split_request(&request.recv().unwrap());
+        let (close_headers, close) = split_request(&request.recv().unwrap());
+        let create: serde_json::Value = serde_json::from_str(&create).unwrap();
+        let close: serde_json::Value = serde_json::from_str(&close).unwrap();
+        let description = create["description"].as_str().unwrap();
+
+        assert!(create_headers.starts_with("post /v2/alerts http/1.1"));
+        assert!(create_headers.contains("authorization: geniekey k3y"));
+        assert_eq!(create["alias"], "web-1/website");
+        assert_eq!(create["priority"], "P2");
+        assert_eq!(create["responders"][0]["type"], "team");
+        assert_eq!(create["responders"][0]["name"], "ops");
+        assert_eq!(create["responders"][1]["username"], "jo@example.com");
+        assert!(description.starts_with("The company website"));
+        assert!(description.contains("Connection refused"));
+        assert_eq!(create["details"]["actualResult"], "Status \"503\"");
+        assert!(create["details"]["log"]
+            .as_str()
+            .unwrap()
+            .contains("Connection refused"));
+        assert!(close_headers
+            .starts_with("post /v2/alerts/web-1%2fwebsite/close?identifiertype=alias http/1.1"));
+        assert_eq!(close["source"], "web-1");
+    }
+
+    #[test]
+    fn rejects_bad_opsgenie_settings() {
+        let mut alert = models::OpsgenieAlertBody::new();
+        alert.api_key = Some("k3y".to_owned());
+        alert.priority = Some("P9".to_owned());
+        assert!(opsgenie_request(&alert, &payload()).is_err());
+
+        alert.priority = None;
+        alert.responders = Some(vec!["ops".to_owned()]);
+        assert!(opsgenie_request(&alert, &payload()).is_err());
+    }
+}
@@ -0,0 +1,476 @@
+use crate::alerts::{AlertPayload, WebhookPayload};
+use crate::config::AlertDelivery;
+use crate::error::Error;
+use crate::openapi_client::models;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions, Permissions};
+use std::io::Write;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::time::Duration;
+
+/// An alert that failed to send and is waiting to be retried. Only the key of the alert is kept,
+/// so its settings and secrets are not written to the spool, and the retry uses the alert in the
+/// current config.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingDelivery {
+    pub id: u64,
+    pub alert_key: String,
+    pub alert_type: String,
+    pub payload: AlertPayload,
+    /// How many times sending has failed
+    pub attempts: usize,
+    pub next_attempt: DateTime<Utc>,
+    pub last_error: String,
+    /// Whether a retry is being sent now
+    #[serde(skip)]
+    pub in_flight: bool,
+}
+
+/// The alerts waiting to be retried. They are kept in the spool file, if there is one, so they
+/// are retried after a restart.
+pub struct DeliveryQueue {
+    settings: AlertDelivery,
+    pending: Vec<PendingDelivery>,
+    next_id: u64,
+    dead_lettered: usize,
+}
+
+impl DeliveryQueue {
+    /// A queue that keeps failed alerts in memory only, until it is configured.
+    pub fn new() -> Self {
+        Self {
+            settings: AlertDelivery::default(),
+            pending: vec![],
+            next_id: 1,
+            dead_lettered: 0,
+        }
+    }
+
+    /// Applies new settings. When the spool path changes, the alerts spooled there are added to
+    /// the queue.
+    pub fn configure(&mut self, settings: AlertDelivery) {
+        let spool_changed = settings.spool_path != self.settings.spool_path;
+        self.settings = settings;
+
+        if spool_changed {
+            self.load_spool();
+            self.save_spool();
+        }
+    }
+
+    pub fn fallback_alert(&self) -> Option<&models::Alert> {
+        self.settings.fallback_alert.as_ref()
+    }
+
+    pub fn pending(&self) -> &[PendingDelivery] {
+        &self.pending
+    }
+
+    /// How many alerts have been given up on since the agent started.
+    pub fn dead_lettered(&self) -> usize {
+        self.dead_lettered
+    }
+
+    /// Records a failed attempt to send an alert, which is a retry when the ID is set. Returns
+    /// the alert if it has run out of attempts, after writing it to the dead-letter file. A
+    /// failed retry that has been removed from the queue in the meantime is not queued again.
+    pub fn failed(
+        &mut self,
+        id: Option<u64>,
+        alert: &models::Alert,
+        alert_key: &str,
+        payload: &AlertPayload,
+        error: &Error,
+        now: DateTime<Utc>,
+    ) -> Option<PendingDelivery> {
+        let index = match id {
+            Some(id) => self.pending.iter().position(|p| p.id == id)?,
+            None => {
+                self.pending.push(PendingDelivery {
+                    id: self.next_id,
+                    alert_key: alert_key.to_owned(),
+                    alert_type: alert.type_.clone(),
+                    payload: payload.clone(),
+                    attempts: 0,
+                    next_attempt: now,
+                    last_error: String::new(),
+                    in_flight: false,
+                });
+                self.next_id += 1;
+                self.pending.len() - 1
+            }
+        };
+
+        let attempts = self.pending[index].attempts + 1;
+        let backoff = self.backoff(attempts);
+        let entry = &mut self.pending[index];
+        entry.attempts = attempts;
+        entry.last_error = error.to_string();
+        entry.in_flight = false;
+        entry.next_attempt =
+            now + chrono::Duration::from_std(backoff).unwrap_or_else(|_| chrono::Duration::zero());
+
+        let exhausted = if entry.attempts >= self.settings.max_attempts {
+            let entry = self.pending.remove(index);
+            self.write_dead_letter(&entry, now);
+            self.dead_lettered += 1;
+            Some(entry)
+        } else {
+            None
+        };
+
+        self.save_spool();
+        exhausted
+    }
+
+    /// Removes an alert whose retry was sent, or that a newer state change has superseded.
+    pub fn remove(&mut self, id: u64) {
+        self.pending.retain(|p| p.id != id);
+        self.save_spool();
+    }
+
+    /// Gives up on an alert that cannot be retried, such as when it is no longer in the config,
+    /// and writes it to the dead-letter file.
+    pub fn dead_letter(&mut self, id: u64, reason: &str, now: DateTime<Utc>) {
+        let index = match self.pending.iter().position(|p| p.id == id) {
+            Some(i) => i,
+            None => return,
+        };
+        let mut entry = self.pending.remove(index);
+        entry.last_error = reason.to_owned();
+        self.write_dead_letter(&entry, now);
+        self.dead_lettered += 1;
+        self.save_spool();
+    }
+
+    /// Drops the monitor from the alerts waiting to be retried with the alert key, as a newer
+    /// state change of the monitor is being sent. A digest keeps its other monitors.
+    pub fn supersede(&mut self, alert_key: &str, monitor_name: &str) {
+        let mut superseded = vec![];
+        let mut changed = false;
+
+        for pending in self.pending.iter_mut().filter(|p| p.alert_key == alert_key) {
+            let mut changes = if pending.payload.digest.is_empty() {
+                vec![pending.payload.clone()]
+            } else {
+                pending.payload.digest.clone()
+            };
+            let count = changes.len();
+            changes.retain(|p| p.monitor_name != monitor_name);
+            if changes.len() == count {
+                continue;
+            }
+
+            changed = true;
+            match changes.len() {
+                0 => superseded.push(pending.id),
+                1 => pending.payload = changes.remove(0),
+                _ => pending.payload = AlertPayload::digest(changes),
+            }
+        }
+
+        if !changed {
+            return;
+        }
+        self.pending.retain(|p| !superseded.contains(&p.id));
+        debug!(
+            "Dropped retry superseded by a newer state change (alert={}, monitor_name={})",
+            alert_key, monitor_name
+        );
+        self.save_spool();
+    }
+
+    /// The alerts that are due to be retried. They are marked as in flight until their retry
+    /// has succeeded or failed.
+    pub fn take_due(&mut self, now: DateTime<Utc>) -> Vec<PendingDelivery> {
+        self.pending
+            .iter_mut()
+            .filter(|p| !p.in_flight && p.next_attempt <= now)
+            .map(|p| {
+                p.in_flight = true;
+                p.clone()
+            })
+            .collect()
+    }
+
+    /// The wait before the next attempt. It doubles after each failed attempt up to the maximum,
+    /// and a random part of up to half is taken off so retries from many agents spread out.
+    pub fn backoff(&self, attempts: usize) -> Duration {
+        let (initial, max) = self
+            .settings
+            .backoff()
+            .unwrap_or((Duration::from_secs(30), Duration::from_secs(1800)));
+        let exponent = attempts.saturating_sub(1).min(31) as u32;
+        let backoff = initial
+            .checked_mul(2u32.pow(exponent))
+            .unwrap_or(max)
+            .min(max);
+
+        backoff.mul_f64(rand::thread_rng().gen_range(0.5, 1.0))
+    }
+
+    fn load_spool(&mut self) {
+        let path = match self.settings.spool_path {
+            Some(ref p) => p.clone(),
+            None => return,
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(err) => {
+                debug!("No alerts loaded from spool at {}: {}", path, err);
+                return;
+            }
+        };
+
+        match serde_json::from_str::<Vec<PendingDelivery>>(&contents) {
+            Ok(spooled) => {
+                info!(
+                    "Loaded alerts to retry from spool (path={}, count={})",
+                    path,
+                    spooled.len()
+                );
+                for mut delivery in spooled {
+                    delivery.id = self.next_id;
+                    self.next_id += 1;
+                    self.pending.push(delivery);
+                }
+            }
+            Err(err) => warn!("Ignoring unreadable alert spool at {}: {}", path, err),
+        }
+    }
+
+    /// Writes the queue to the spool file, which only the agent's user can read. The queue is
+    /// written to a temporary file that then replaces the spool, so a crash while writing
+    /// leaves the previous spool intact.
+    fn save_spool(&self) {
+        let path = match self.settings.spool_path {
+            Some(ref p) => p,
+            None => return,
+        };
+        let temp_path = format!("{}.tmp", path);
+
+        let result = serde_json::to_string(&self.pending)
+            .map_err(Error::from)
+            .and_then(|contents| {
+                let mut file = OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .mode(0o600)
+                    .open(&temp_path)?;
+                file.set_permissions(Permissions::from_mode(0o600))?;
+                file.write_all(contents.as_bytes())?;
+                file.sync_all()?;
+                fs::rename(&temp_path, path)?;
+                Ok(())
+            });
+
+        if let Err(err) = result {
+            error!("Failed to write alert spool at {}: {}", path, err);
+        }
+    }
+
+    /// Appends the alert as a line of JSON to the dead-letter file.
+    fn write_dead_letter(&self, delivery: &PendingDelivery, now: DateTime<Utc>) {
+        let path = match self.settings.dead_letter_path {
+            Some(ref p) => p,
+            None => return,
+        };
+
+        let record = serde_json::json!({
+            "failedAt": now,
+            "attempts": delivery.attempts,
+            "lastError": delivery.last_error,
+            "alertKey": delivery.alert_key,
+            "alertType": delivery.alert_type,
+            "payload": WebhookPayload::from(&delivery.payload),
+        });
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", record));
+
+        if let Err(err) = result {
+            error!("Failed to write dead letter at {}: {}", path, err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alerts::NodeInfo;
+    use crate::monitoring::MonitorStatusBuilder;
+    use chrono::TimeZone;
+
+    fn payload() -> AlertPayload {
+        AlertPayload {
+            monitor_name: "website".to_owned(),
+            monitor_description: None,
+            status: MonitorStatusBuilder::new(
+                "website",
+                models::MonitorType::HTTP,
+                Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+            )
+            .down("200", "503"),
+            node_info: NodeInfo {
+                hostname: "web-1".to_owned(),
+                platform: "linux".to_owned(),
+                cpu: "4 logical cores".to_owned(),
+                ram: "1 KB used".to_owned(),
+            },
+            down_since: None,
+            downtime: None,
+            flapping: false,
+            digest: vec![],
+            delivery_failure: None,
+        }
+    }
+
+    fn alert() -> models::Alert {
+        let mut body = models::AlertBody::new();
+        body.url = Some("http://localhost/hook".to_owned());
+        models::Alert::new("webhook".to_owned(), vec![], 1, true, body)
+    }
+
+    fn settings(name: &str) -> AlertDelivery {
+        let path = std::env::temp_dir().join(format!("schnooty-{}-{}", name, std::process::id()));
+        AlertDelivery {
+            max_attempts: 3,
+            initial_backoff: "10s".to_owned(),
+            max_backoff: "25s".to_owned(),
+            spool_path: Some(format!("{}.json", path.display())),
+            dead_letter_path: Some(format!("{}.jsonl", path.display())),
+            fallback_alert: None,
+        }
+    }
+
+    #[test]
+    fn backs_off_exponentially_with_jitter() {
+        let mut queue = DeliveryQueue::new();
+        queue.configure(AlertDelivery {
+            spool_path: None,
+            ..settings("backoff")
+        });
+
+        for _ in 0..20 {
+            let first = queue.backoff(1);
+            assert!(first >= Duration::from_secs(5) && first <= Duration::from_secs(10));
+            let second = queue.backoff(2);
+            assert!(second >= Duration::from_secs(10) && second <= Duration::from_secs(20));
+            assert!(queue.backoff(30) <= Duration::from_secs(25));
+        }
+    }
+
+    #[test]
+    fn keeps_spool_across_restarts() {
+        let settings = settings("spool");
+        let now = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+
+        let mut queue = DeliveryQueue::new();
+        queue.configure(settings.clone());
+        queue.failed(
+            None,
+            &alert(),
+            "hook",
+            &payload(),
+            &Error::new("timed out"),
+            now,
+        );
+
+        // a write cut short by a crash leaves the spool as it was
+        let temp_path = format!("{}.tmp", settings.spool_path.as_ref().unwrap());
+        fs::write(&temp_path, "[{\"id\":").unwrap();
+
+        let mut restarted = DeliveryQueue::new();
+        restarted.configure(settings.clone());
+        assert!(fs::metadata(&temp_path).is_err());
+
+        let mode = fs::metadata(settings.spool_path.as_ref().unwrap())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(!fs::read_to_string(settings.spool_path.as_ref().unwrap())
+            .unwrap()
+            .contains("http://localhost/hook"));
+        fs::remove_file(settings.spool_path.unwrap()).unwrap();
+
+        assert_eq!(restarted.pending().len(), 1);
+        assert_eq!(restarted.pending()[0].alert_key, "hook");
+        assert_eq!(restarted.pending()[0].attempts, 1);
+        assert_eq!(restarted.pending()[0].last_error, "timed out");
+        assert!(restarted.take_due(now).is_empty());
+        assert_eq!(
+            restarted
+                .take_due(now + chrono::Duration::seconds(10))
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn dead_letters_after_max_attempts() {
+        let settings = settings("dead-letter");
+        let now = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let mut queue = DeliveryQueue::new();
+        queue.configure(settings.clone());
+
+        let error = Error::new("connection refused");
+        assert!(queue
+            .failed(None, &alert(), "hook", &payload(), &error, now)
+            .is_none());
+        let id = queue.pending()[0].id;
+        assert!(queue
+            .failed(Some(id), &alert(), "hook", &payload(), &error, now)
+            .is_none());
+        let exhausted = queue.failed(Some(id), &alert(), "hook", &payload(), &error, now);
+
+        let dead_letters = fs::read_to_string(settings.dead_letter_path.as_ref().unwrap()).unwrap();
+        fs::remove_file(settings.dead_letter_path.unwrap()).unwrap();
+        fs::remove_file(settings.spool_path.unwrap()).unwrap();
+        let record: serde_json::Value = serde_json::from_str(dead_letters.trim()).unwrap();
+
+        assert_eq!(exhausted.unwrap().attempts, 3);
+        assert!(queue.pending().is_empty());
+        assert_eq!(queue.dead_lettered(), 1);
+        assert_eq!(record["attempts"], 3);
+        assert_eq!(record["lastError"], "connection refused");
+        assert_eq!(record["payload"]["monitorName"], "website");
+    }
+
+    #[test]
+    fn drops_retries_superseded_by_newer_state_changes() {
+        let now = Utc.timestamp_opt(1_600_000_000, 0).unwrap();
+        let mut queue = DeliveryQueue::new();
+        let error = Error::new("timed out");
+
+        let mut other = payload();
+        other.monitor_name = "api".to_owned();
+        let digest = AlertPayload::digest(vec![payload(), other.clone()]);
+        queue.failed(None, &alert(), "hook", &payload(), &error, now);
+        queue.failed(None, &alert(), "other", &payload(), &error, now);
+        queue.failed(None, &alert(), "hook", &digest, &error, now);
+
+        queue.supersede("hook", "website");
+
+        assert_eq!(queue.pending().len(), 2);
+        assert_eq!(queue.pending()[0].alert_key, "other");
+        assert_eq!(queue.pending()[1].payload.monitor_name, "api");
+        assert!(queue.pending()[1].payload.digest.is_empty());
+
+        // a retry that was in flight when it was dropped is not queued again when it fails
+        let id = queue.take_due(now + chrono::Duration::seconds(60))[1].id;
+        queue.supersede("hook", "api");
+        assert!(queue
+            .failed(Some(id), &alert(), "hook", &other, &error, now)
+            .is_none());
+        assert_eq!(queue.pending().len(), 1);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::alerts::{AlertPayload, WebhookPayload};
+use crate::duration;
+use crate::error::Error;
+use crate::openapi_client::models;
+use std::collections::HashSet;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const DEFAULT_TIMEOUT: &str = "30s";
+const POLL_INTERVAL_MS: u64 = 50;
+/// How long the output of a command is read for after it ends, when that is past its timeout
+const OUTPUT_GRACE_MS: u64 = 500;
+/// The most exec alert commands that run at once across all monitors
+pub const MAX_CONCURRENT_EXECS: usize = 4;
+
+/// A slot for running a command. Each command runs at most once at a time for a monitor, and at
+/// most `MAX_CONCURRENT_EXECS` run in total. The slot is freed when the guard is dropped.
+pub struct ExecGuard {
+    running: Arc<Mutex<HashSet<String>>>,
+    key: String,
+}
+
+impl ExecGuard {
+    pub fn acquire(running: &Arc<Mutex<HashSet<String>>>, key: String) -> Result<Self, Error> {
+        let mut running_keys = running.lock().unwrap();
+        if running_keys.contains(&key) {
+            return Err(Error::new(format!(
+                "Exec alert skipped. The command is still running for this monitor ({})",
+                key
+            )));
+        }
+        if running_keys.len() >= MAX_CONCURRENT_EXECS {
+            return Err(Error::new(format!(
+                "Exec alert skipped. {} commands are already running ({})",
+                running_keys.len(),
+                key
+            )));
+        }
+        running_keys.insert(key.clone());
+
+        Ok(Self {
+            running: running.clone(),
+            key,
+        })
+    }
+}
+
+impl Drop for ExecGuard {
+    fn drop(&mut self) {
+        self.running.lock().unwrap().remove(&self.key);
+    }
+}
+
+/// Runs the command of the alert with the payload in `SCHNOOTY_*` environment variables and as
+/// JSON on stdin. Its output goes to the agent log. The command runs in its own process group,
+/// which is killed if it runs past its timeout. This blocks, so it belongs on the blocking pool.
+pub fn run_command(alert: &models::ExecAlertBody, payload: &AlertPayload) -> Result<(), Error> {
+    let timeout = duration::parse_duration(alert.timeout.as_deref().unwrap_or(DEFAULT_TIMEOUT))?;
+    let input = serde_json::to_string(&WebhookPayload::from(payload))?;
+
+    debug!(
+        "Running exec alert command (command={}, monitor_name={})",
+        alert.command, payload.monitor_name
+    );
+
+    let mut child = Command::new(&alert.command)
+        .args(alert.args.iter().flatten())
+        .envs(exec_env(payload))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()?;
+
+    // the pipes are handled on their own threads so a command that ignores stdin or writes a
+    // lot of output cannot block us
+    if let Some(mut stdin) = child.stdin.take() {
+        thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+    let stdout = read_lines(child.stdout.take());
+    let stderr = read_lines(child.stderr.take());
+
+    let started = Instant::now();
+    let exit_status = loop {
+        if let Some(exit_status) = child.try_wait()? {
+            break Some(exit_status);
+        }
+        if started.elapsed() >= timeout {
+            kill_process_group(&child)?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+    };
+
+    // something the command left running may hold its output open, so that is not waited for
+    let deadline = (started + timeout).max(Instant::now() + Duration::from_millis(OUTPUT_GRACE_MS));
+    for line in receive_lines(&stdout, deadline) {
+        info!(
+            "Exec alert output (command={}, monitor_name={}): {}",
+            alert.command, payload.monitor_name, line
+        );
+    }
+    for line in receive_lines(&stderr, deadline) {
+        warn!(
+            "Exec alert error output (command={}, monitor_name={}): {}",
+            alert.command, payload.monitor_name, line
+        );
+    }
+
+    match exit_status {
+        Some(exit_status) if exit_status.success() => Ok(()),
+        Some(exit_status) => Err(Error::new(format!(
+            "Exec alert command {} failed with {}",
+            alert.command, exit_status
+        ))),
+        None => Err(Error::new(format!(
+            "Exec alert command {} was killed after running for {:?}",
+            alert.command, timeout
+        ))),
+    }
+}
+
+/// Kills the command and everything it started in its process group.
+fn kill_process_group(child: &Child) -> Result<(), Error> {
+    // the group ID is the command's process ID, and a negative ID signals the whole group
+    if unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) } != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+/// Reads the lines of the pipe on their own thread until it closes.
+fn read_lines<R: Read + Send + 'static>(pipe: Option<R>) -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(pipe) = pipe {
+        thread::spawn(move || {
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    receiver
+}
+
+/// The lines read until the pipe closes or the deadline passes.
+fn receive_lines(receiver: &Receiver<String>, deadline: Instant) -> Vec<String> {
+    let mut lines = vec![];
+    while let Ok(line) = receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        lines.push(line);
+    }
+    lines
+}
+
+fn exec_env(payload: &AlertPayload) -> Vec<(&'static str, String)> {
+    let mut env = vec![
+        ("SCHNOOTY_MONITOR_NAME", payload.monitor_name.clone()),
+        (
+            "SCHNOOTY_MONITOR_TYPE",
+            payload.status.monitor_type.to_string(),
+        ),
+        ("SCHNOOTY_STATUS", payload.status.status.to_string()),
+        ("SCHNOOTY_FLAPPING", payload.flapping.to_string()),
+        ("SCHNOOTY_DESCRIPTION", payload.status.description.clone()),
+        (
+            "SCHNOOTY_EXPECTED_RESULT",
+            payload.status.expected_result.clone(),
+        ),
+        (
+            "SCHNOOTY_ACTUAL_RESULT",
+            payload.status.actual_result.clone(),
+        ),
+        ("SCHNOOTY_TIMESTAMP", payload.status.timestamp.to_rfc3339()),
+        ("SCHNOOTY_HOSTNAME", payload.node_info.hostname.clone()),
+    ];
+    if let Some(ref description) = payload.monitor_description {
+        env.push(("SCHNOOTY_MONITOR_DESCRIPTION", description.clone()));
+    }
+    if let Some(down_since) = payload.down_since {
+        env.push(("SCHNOOTY_DOWN_SINCE", down_since.to_rfc3339()));
+    }
+    if let Some(downtime) = payload.downtime {
+        env.push(("SCHNOOTY_DOWNTIME_SECONDS", downtime.as_secs().to_string()));
+    }
+    env
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alerts::NodeInfo;
+    use crate::monitoring::MonitorStatusBuilder;
+    use chrono::{TimeZone, Utc};
+    use std::fs;
+
+    fn payload() -> AlertPayload {
+        AlertPayload {
+            monitor_name: "nginx".to_owned(),
+            monitor_description: Some("The web server".to_owned()),
+            status: MonitorStatusBuilder::new(
+                "nginx",
+                models::MonitorType::PROCESS,
+                Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+            )
+            .down("nginx is running", "nginx is not running"),
+            node_info: NodeInfo {
+                hostname: "web-1".to_owned(),
+                platform: "linux".to_owned(),
+                cpu: "4 logical cores".to_owned(),
+                ram: "1 KB used".to_owned(),
+            },
+            down_since: None,
+            downtime: None,
+            flapping: false,
+            digest: vec![],
+            delivery_failure: None,
+        }
+    }
+
+    fn shell(script: &str, timeout: Option<&str>) -> models::ExecAlertBody {
+        let mut alert = models::ExecAlertBody::new("/bin/sh".to_owned());
+        alert.args = Some(vec!["-c".to_owned(), script.to_owned()]);
+        alert.timeout = timeout.map(|t| t.to_owned());
+        alert
+    }
+
+    #[test]
+    fn passes_payload_in_env_and_stdin() {
+        let output = std::env::temp_dir().join(format!("schnooty-exec-{}", std::process::id()));
+        let script = format!(
+            "echo \"$SCHNOOTY_MONITOR_NAME $SCHNOOTY_STATUS $SCHNOOTY_MONITOR_DESCRIPTION\" > {0} && cat >> {0}",
+            output.display()
+        );
+
+        run_command(&shell(&script, None), &payload()).unwrap();
+
+        let written = fs::read_to_string(&output).unwrap();
+        fs::remove_file(&output).unwrap();
+        let stdin: serde_json::Value =
+            serde_json::from_str(written.lines().nth(1).unwrap()).unwrap();
+
+        assert_eq!(written.lines().next(), Some("nginx down The web server"));
+        assert_eq!(stdin["monitorName"], "nginx");
+        assert_eq!(stdin["actualResult"], "nginx is not running");
+    }
+
+    #[test]
+    fn fails_on_non_zero_exit() {
+        assert!(run_command(&shell("echo broken >&2; exit 3", None), &payload()).is_err());
+    }
+
+    #[test]
+    fn kills_command_after_timeout() {
+        let started = Instant::now();
+
+        assert!(run_command(&shell("exec sleep 10", Some("200ms")), &payload()).is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn kills_process_group_after_timeout() {
+        let marker =
+            std::env::temp_dir().join(format!("schnooty-exec-group-{}", std::process::id()));
+        let script = format!("(sleep 1; touch {}) & exec sleep 10", marker.display());
+        let started = Instant::now();
+
+        assert!(run_command(&shell(&script, Some("200ms")), &payload()).is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn does_not_wait_for_output_held_open_after_exit() {
+        let started = Instant::now();
+
+        run_command(&shell("sleep 10 & echo done", Some("1s")), &payload()).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn limits_running_commands() {
+        let running = Arc::new(Mutex::new(HashSet::new()));
+
+        let first = ExecGuard::acquire(&running, "restart nginx".to_owned()).unwrap();
+        assert!(ExecGuard::acquire(&running, "restart nginx".to_owned()).is_err());
+        drop(first);
+        assert!(ExecGuard::acquire(&running, "restart nginx".to_owned()).is_ok());
+
+        let guards: Vec<_> = (0..MAX_CONCURRENT_EXECS)
+            .map(|i| ExecGuard::acquire(&running, format!("restart {}", i)).unwrap())
+            .collect();
+        assert!(ExecGuard::acquire(&running, "restart other".to_owned()).is_err());
+        drop(guards);
+    }
+}
@@ -0,0 +1,348 @@
+use crate::alerts::{alert_title, AlertPayload, WebhookPayload};
+use crate::error::Error;
+use crate::openapi_client::models;
+use chrono::SecondsFormat;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::net::UdpSocket;
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+
+const DEFAULT_MAX_SIZE: u64 = 10 * 1024 * 1024;
+const DEFAULT_MAX_FILES: usize = 5;
+const SYSLOG_SOCKET: &str = "/dev/log";
+const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+const APP_NAME: &str = "schnooty";
+/// Syslog facility for system daemons
+const SYSLOG_FACILITY_DAEMON: u8 = 3;
+/// Structured data ID of syslog records. 32473 is the enterprise number reserved for examples.
+const SYSLOG_SD_ID: &str = "schnooty@32473";
+
+/// Checks that a log alert has a target it can write to.
+pub fn check_log_alert(target: Option<&str>, path: Option<&str>) -> Result<(), Error> {
+    match target {
+        Some("file") if path.is_none() => Err(Error::new(
+            "Log alert is misconfigured. A file target needs a path",
+        )),
+        Some("file") | Some("syslog") | Some("journald") => Ok(()),
+        Some(target) => Err(Error::new(format!(
+            "Log alert is misconfigured. Target {} is not one of file, syslog or journald",
+            target
+        ))),
+        None => Err(Error::new("Log alert is misconfigured. It has no target")),
+    }
+}
+
+/// Writes one record of the state change to the target of the log alert. This blocks, so it
+/// belongs on the blocking pool.
+pub fn write_log_record(alert: &models::LogAlertBody, payload: &AlertPayload) -> Result<(), Error> {
+    check_log_alert(Some(&alert.target), alert.path.as_deref())?;
+
+    match alert.target.as_ref() {
+        "file" => {
+            let path = alert.path.as_deref().unwrap_or_default();
+            let max_size = alert
+                .max_size
+                .map(|s| s.max(1) as u64)
+                .unwrap_or(DEFAULT_MAX_SIZE);
+            let max_files = alert
+                .max_files
+                .map(|f| f.max(0) as usize)
+                .unwrap_or(DEFAULT_MAX_FILES);
+
+            let mut record = serde_json::to_string(&WebhookPayload::from(payload))?;
+            record.push('\n');
+
+            write_file_record(path, max_size, max_files, &record)
+        }
+        "syslog" => {
+            let record = syslog_record(payload);
+            match alert.address {
+                Some(ref address) => {
+                    UdpSocket::bind("0.0.0.0:0")?.send_to(record.as_bytes(), address)?;
+                }
+                None => {
+                    let path = alert.path.as_deref().unwrap_or(SYSLOG_SOCKET);
+                    UnixDatagram::unbound()?.send_to(record.as_bytes(), path)?;
+                }
+            }
+            Ok(())
+        }
+        // journald, the only other target the check lets through
+        _ => {
+            let path = alert.path.as_deref().unwrap_or(JOURNALD_SOCKET);
+            UnixDatagram::unbound()?.send_to(&journald_record(payload), path)?;
+            Ok(())
+        }
+    }
+}
+
+/// Appends the record to the file, first rotating it if the record would take it over
+/// `max_size`. The rotated files are `<path>.1` (newest) to `<path>.<max_files>` (oldest).
+fn write_file_record(
+    path: &str,
+    max_size: u64,
+    max_files: usize,
+    record: &str,
+) -> Result<(), Error> {
+    let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+    if size > 0 && size + record.len() as u64 > max_size {
+        debug!("Rotating log alert file (path={})", path);
+
+        if max_files == 0 {
+            fs::remove_file(path)?;
+        } else {
+            for index in (1..max_files).rev() {
+                let from = format!("{}.{}", path, index);
+                if Path::new(&from).exists() {
+                    fs::rename(&from, format!("{}.{}", path, index + 1))?;
+                }
+            }
+            fs::rename(path, format!("{}.1", path))?;
+        }
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(record.as_bytes())?;
+
+    Ok(())
+}
+
+/// Syslog severity of the record: error when DOWN and notice when recovered.
+fn severity(payload: &AlertPayload) -> u8 {
+    match payload.status.status {
+        models::MonitorStatusIndicator::DOWN => 3,
+        models::MonitorStatusIndicator::OK => 5,
+    }
+}
+
+fn message(payload: &AlertPayload) -> String {
+    format!("{}: {}", alert_title(payload), payload.status.actual_result)
+}
+
+/// An RFC 5424 syslog record with the monitor details as structured data.
+fn syslog_record(payload: &AlertPayload) -> String {
+    let hostname: String = payload
+        .node_info
+        .hostname
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .collect();
+    let msg_id = match payload.status.status {
+        _ if payload.flapping => "monitor-flapping",
+        models::MonitorStatusIndicator::DOWN => "monitor-down",
+        models::MonitorStatusIndicator::OK => "monitor-up",
+    };
+
+    format!(
+        "<{}>1 {} {} {} {} {} [{} monitor=\"{}\" status=\"{}\" type=\"{}\" expected=\"{}\" actual=\"{}\"] {}",
+        SYSLOG_FACILITY_DAEMON * 8 + severity(payload),
+        payload
+            .status
+            .timestamp
+            .to_rfc3339_opts(SecondsFormat::Millis, true),
+        if hostname.is_empty() { "-" } else { &hostname },
+        APP_NAME,
+        std::process::id(),
+        msg_id,
+        SYSLOG_SD_ID,
+        escape_sd_value(&payload.monitor_name),
+        payload.status.status,
+        payload.status.monitor_type,
+        escape_sd_value(&payload.status.expected_result),
+        escape_sd_value(&payload.status.actual_result),
+        message(payload).replace('\n', " ")
+    )
+}
+
+/// Escapes the characters that RFC 5424 does not allow unescaped in a parameter value.
+fn escape_sd_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(']', "\\]")
+}
+
+/// A record in the native journald protocol, with the monitor details as extra fields.
+fn journald_record(payload: &AlertPayload) -> Vec<u8> {
+    let log = payload
+        .status
+        .log
+        .iter()
+        .map(|entry| format!("{} {}", entry.timestamp.to_rfc3339(), entry.value))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut record = vec![];
+    for (name, value) in vec![
+        ("MESSAGE", message(payload)),
+        ("PRIORITY", severity(payload).to_string()),
+        ("SYSLOG_IDENTIFIER", APP_NAME.to_owned()),
+        ("SCHNOOTY_MONITOR", payload.monitor_name.clone()),
+        ("SCHNOOTY_STATUS", payload.status.status.to_string()),
+        (
+            "SCHNOOTY_MONITOR_TYPE",
+            payload.status.monitor_type.to_string(),
+        ),
+        ("SCHNOOTY_DESCRIPTION", payload.status.description.clone()),
+        (
+            "SCHNOOTY_EXPECTED_RESULT",
+            payload.status.expected_result.clone(),
+        ),
+        (
+            "SCHNOOTY_ACTUAL_RESULT",
+            payload.status.actual_result.clone(),
+        ),
+        ("SCHNOOTY_LOG", log),
+    ] {
+        if value.contains('\n') {
+            // values over several lines are sent with their length instead of after an =
+            record.extend_from_slice(name.as_bytes());
+            record.push(b'\n');
+            record.extend_from_slice(&(value.len() as u64).to_le_bytes());
+            record.extend_from_slice(value.as_bytes());
+            record.push(b'\n');
+        } else {
+            record.extend_from_slice(format!("{}={}\n", name, value).as_bytes());
+        }
+    }
+    record
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::alerts::NodeInfo;
+    use crate::monitoring::MonitorStatusBuilder;
+    use chrono::{TimeZone, Utc};
+    use std::path::PathBuf;
+
+    fn payload() -> AlertPayload {
+        let mut status = MonitorStatusBuilder::new(
+            "website",
+            models::MonitorType::HTTP,
+            Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+        )
+        .down("Status 200", "Status \"503\" [gateway]");
+        status.log.push(models::MonitorStatusLogEntry::new(
+            Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+            "Sending request".to_owned(),
+        ));
+        status.log.push(models::MonitorStatusLogEntry::new(
+            Utc.timestamp_opt(1_600_000_001, 0).unwrap(),
+            "Got 503".to_owned(),
+        ));
+
+        AlertPayload {
+            monitor_name: "website".to_owned(),
+            monitor_description: None,
+            status,
+            node_info: NodeInfo {
+                hostname: "web-1".to_owned(),
+                platform: "linux".to_owned(),
+                cpu: "4 logical cores".to_owned(),
+                ram: "1 KB used".to_owned(),
+            },
+            down_since: None,
+            downtime: None,
+            flapping: false,
+            digest: vec![],
+            delivery_failure: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("schnooty-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn appends_json_records_and_rotates_file() {
+        let dir = temp_dir("log-alert-file");
+        let path = dir.join("alerts.log").to_str().unwrap().to_owned();
+        let mut alert = models::LogAlertBody::new("file".to_owned());
+        alert.path = Some(path.clone());
+        alert.max_size = Some(1000);
+        alert.max_files = Some(2);
+
+        for _ in 0..5 {
+            write_log_record(&alert, &payload()).unwrap();
+        }
+
+        let current = fs::read_to_string(&path).unwrap();
+        let record: serde_json::Value =
+            serde_json::from_str(current.lines().next().unwrap()).unwrap();
+
+        assert_eq!(record["monitorName"], "website");
+        assert_eq!(record["status"], "down");
+        assert!(Path::new(&format!("{}.1", path)).exists());
+        assert!(Path::new(&format!("{}.2", path)).exists());
+        assert!(!Path::new(&format!("{}.3", path)).exists());
+        assert!(fs::metadata(&path).unwrap().len() <= 1000);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn sends_rfc_5424_record_over_udp() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let mut alert = models::LogAlertBody::new("syslog".to_owned());
+        alert.address = Some(server.local_addr().unwrap().to_string());
+
+        write_log_record(&alert, &payload()).unwrap();
+
+        let mut buffer = [0u8; 2048];
+        let (length, _) = server.recv_from(&mut buffer).unwrap();
+        let record = String::from_utf8_lossy(&buffer[..length]).to_string();
+
+        assert!(record.starts_with(&format!(
+            "<27>1 2020-09-13T12:26:40.000Z web-1 schnooty {} monitor-down [schnooty@32473 monitor=\"website\"",
+            std::process::id()
+        )));
+        assert!(record.contains(r#"actual="Status \"503\" [gateway\]""#));
+        assert!(record.ends_with("] Monitor website is DOWN: Status \"503\" [gateway]"));
+    }
+
+    #[test]
+    fn sends_native_journald_record() {
+        let dir = temp_dir("log-alert-journald");
+        let socket_path = dir.join("journal.socket");
+        let server = UnixDatagram::bind(&socket_path).unwrap();
+        let mut alert = models::LogAlertBody::new("journald".to_owned());
+        alert.path = Some(socket_path.to_str().unwrap().to_owned());
+
+        write_log_record(&alert, &payload()).unwrap();
+
+        let mut buffer = [0u8; 4096];
+        let length = server.recv(&mut buffer).unwrap();
+        let record = &buffer[..length];
+        let text = String::from_utf8_lossy(record);
+        let log = "2020-09-13T12:26:40+00:00 Sending request\n2020-09-13T12:26:41+00:00 Got 503";
+        let mut log_field = b"SCHNOOTY_LOG\n".to_vec();
+        log_field.extend_from_slice(&(log.len() as u64).to_le_bytes());
+        log_field.extend_from_slice(log.as_bytes());
+        log_field.push(b'\n');
+
+        assert!(text.starts_with(
+            "MESSAGE=Monitor website is DOWN: Status \"503\" [gateway]\nPRIORITY=3\n"
+        ));
+        assert!(text.contains("SYSLOG_IDENTIFIER=schnooty\n"));
+        assert!(text.contains("SCHNOOTY_MONITOR=website\n"));
+        assert!(record.ends_with(&log_field));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_target() {
+        let alert = models::LogAlertBody::new("printer".to_owned());
+
+        assert!(write_log_record(&alert, &payload()).is_err());
+    }
+}
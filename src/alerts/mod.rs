@@ -1,3 +1,10 @@
 mod alert_api;
+mod delivery;
+mod exec_alert;
+mod log_alert;
+mod template;
 
 pub use alert_api::*;
+pub use delivery::*;
+pub use log_alert::check_log_alert;
+pub use template::*;
@@ -0,0 +1,324 @@
+use crate::alerts::{alert_title, downtime_text, AlertPayload, NodeInfo, WebhookPayload};
+use crate::error::Error;
+use crate::monitoring::MonitorStatusBuilder;
+use crate::openapi_client::models;
+use chrono::{DateTime, Utc};
+use handlebars::Handlebars;
+use serde::Serialize;
+use std::time::Duration;
+
+/// The alert types whose messages can be rendered from templates.
+pub const TEMPLATE_ALERT_TYPES: &[&str] = &[
+    "email",
+    "msTeamsMessage",
+    "slack",
+    "pagerduty",
+    "opsgenie",
+    "webhook",
+];
+
+/// The subject of an email, unless the alert or its channel has a template for it.
+pub const DEFAULT_EMAIL_SUBJECT: &str = "[Schnooty] {{title}}";
+
+/// The text of an email, unless the alert or its channel has a template for it.
+pub const DEFAULT_EMAIL_BODY: &str = "\
+{{#if deliveryFailure}}
+{{deliveryFailure}}
+
+{{/if}}
+{{#if digest}}
+The following monitors changed state:
+
+{{#each digest}}
+{{title}}
+  Got result: {{actualResult}}
+  Expected result: {{expectedResult}}
+{{#if downtime}}
+  Downtime: {{downtime}}
+{{/if}}
+  Timestamp: {{timestamp}}
+
+{{/each}}
+{{else}}
+{{title}}
+
+Got result: {{actualResult}}
+Expected result: {{expectedResult}}
+{{#if downtime}}
+{{#if (eq status \"ok\")}}Total downtime{{else}}Down for{{/if}}: {{downtime}}
+{{/if}}
+Description: {{description}}
+Timestamp: {{timestamp}}
+{{/if}}
+Hostname: {{nodeInfo.hostname}}
+Platform: {{nodeInfo.platform}}
+CPU info: {{nodeInfo.cpu}}
+RAM info: {{nodeInfo.ram}}
+{{#if log}}
+
+Monitor log:
+{{#each log}}
+{{timestamp}}: {{value}}
+{{/each}}
+{{/if}}
+";
+
+/// The fields a message template can use. Every field is present, so a template that names
+/// one is valid even when it has no value.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateData {
+    /// The headline of the alert, such as `Monitor website is DOWN`
+    pub title: String,
+    pub monitor_name: String,
+    pub monitor_description: Option<String>,
+    pub monitor_type: models::MonitorType,
+    pub status: models::MonitorStatusIndicator,
+    pub flapping: bool,
+    pub description: String,
+    pub expected_result: String,
+    pub actual_result: String,
+    pub timestamp: DateTime<Utc>,
+    pub log: Vec<models::MonitorStatusLogEntry>,
+    pub node_info: NodeInfo,
+    pub down_since: Option<DateTime<Utc>>,
+    /// How long the monitor has been DOWN, such as `2h 5m`
+    pub downtime: Option<String>,
+    pub digest: Vec<TemplateData>,
+    pub delivery_failure: Option<String>,
+}
+
+impl From<&AlertPayload> for TemplateData {
+    fn from(payload: &AlertPayload) -> Self {
+        Self {
+            title: alert_title(payload),
+            monitor_name: payload.monitor_name.clone(),
+            monitor_description: payload.monitor_description.clone(),
+            monitor_type: payload.status.monitor_type,
+            status: payload.status.status,
+            flapping: payload.flapping,
+            description: payload.status.description.clone(),
+            expected_result: payload.status.expected_result.clone(),
+            actual_result: payload.status.actual_result.clone(),
+            timestamp: payload.status.timestamp,
+            log: payload.status.log.clone(),
+            node_info: payload.node_info.clone(),
+            down_since: payload.down_since,
+            downtime: downtime_text(payload),
+            digest: payload.digest.iter().map(TemplateData::from).collect(),
+            delivery_failure: payload.delivery_failure.clone(),
+        }
+    }
+}
+
+/// The subject and text of a message rendered from the templates of an alert. Each is `None`
+/// when there is no template for it, and the channel uses its own format.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderedMessage {
+    pub subject: Option<String>,
+    pub body: Option<String>,
+}
+
+impl RenderedMessage {
+    pub fn render(
+        subject_template: Option<&str>,
+        body_template: Option<&str>,
+        payload: &AlertPayload,
+    ) -> Result<Self, Error> {
+        let data = TemplateData::from(payload);
+        Ok(Self {
+            subject: subject_template
+                .map(|t| render_text_template("subject template", t, &data))
+                .transpose()?,
+            body: body_template
+                .map(|t| render_text_template("body template", t, &data))
+                .transpose()?,
+        })
+    }
+
+    /// The subject, or the alert title if there is no subject template.
+    pub fn title(&self, payload: &AlertPayload) -> String {
+        self.subject.clone().unwrap_or_else(|| alert_title(payload))
+    }
+}
+
+/// Renders a template of plain text. Values are not escaped.
+fn render_text_template(name: &str, template: &str, data: &TemplateData) -> Result<String, Error> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars.register_escape_fn(handlebars::no_escape);
+
+    handlebars
+        .render_template(template, data)
+        .map_err(|err| Error::new(format!("Failed to render {}: {}", name, err)))
+}
+
+/// Renders a template whose output is JSON. Values are escaped so they are safe to put
+/// between quotes in a JSON string.
+pub fn render_json_template<T: Serialize>(template: &str, data: &T) -> Result<String, Error> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars.register_escape_fn(escape_json);
+
+    handlebars
+        .render_template(template, data)
+        .map_err(|err| Error::new(format!("Failed to render body template: {}", err)))
+}
+
+fn escape_json(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_owned()).to_string();
+    quoted[1..quoted.len() - 1].to_owned()
+}
+
+/// Checks the templates of an alert type by rendering them with an example payload, so a
+/// template that does not parse or names an unknown field is found when the config loads.
+pub fn check_templates(
+    alert_type: &str,
+    subject_template: Option<&str>,
+    body_template: Option<&str>,
+) -> Result<(), Error> {
+    if subject_template.is_none() && body_template.is_none() {
+        return Ok(());
+    }
+    if !TEMPLATE_ALERT_TYPES.contains(&alert_type) {
+        return Err(Error::new(format!(
+            "only {} alerts have templates",
+            TEMPLATE_ALERT_TYPES.join(", ")
+        )));
+    }
+
+    let payload = example_payload();
+    if alert_type == "webhook" {
+        if subject_template.is_some() {
+            return Err(Error::new("webhook alerts have no subjectTemplate"));
+        }
+        if let Some(template) = body_template {
+            render_json_template(template, &WebhookPayload::from(&payload))?;
+        }
+        return Ok(());
+    }
+
+    RenderedMessage::render(subject_template, body_template, &payload)?;
+    Ok(())
+}
+
+/// A payload with every optional field set, so rendering it uses every field.
+fn example_payload() -> AlertPayload {
+    let timestamp = Utc::now();
+    let mut status = MonitorStatusBuilder::new("example", models::MonitorType::HTTP, timestamp)
+        .down("200", "503");
+    status.log = vec![models::MonitorStatusLogEntry::new(
+        timestamp,
+        "Got status 503".to_owned(),
+    )];
+
+    let entry = AlertPayload {
+        monitor_name: "example".to_owned(),
+        monitor_description: Some("Example monitor".to_owned()),
+        status,
+        node_info: NodeInfo {
+            hostname: "example-host".to_owned(),
+            platform: "linux".to_owned(),
+            cpu: "1 logical core".to_owned(),
+            ram: "1 KB used".to_owned(),
+        },
+        down_since: Some(timestamp),
+        downtime: Some(Duration::from_secs(60)),
+        flapping: false,
+        digest: vec![],
+        delivery_failure: Some("Example failure".to_owned()),
+    };
+
+    AlertPayload {
+        digest: vec![entry.clone()],
+        ..entry
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn payload(status: models::MonitorStatusIndicator) -> AlertPayload {
+        let builder = MonitorStatusBuilder::new(
+            "website",
+            models::MonitorType::HTTP,
+            Utc.timestamp_opt(1_600_000_000, 0).unwrap(),
+        );
+        AlertPayload {
+            monitor_name: "website".to_owned(),
+            monitor_description: None,
+            status: match status {
+                models::MonitorStatusIndicator::OK => builder.ok("200", "200"),
+                models::MonitorStatusIndicator::DOWN => builder.down("200", "503"),
+            },
+            node_info: NodeInfo {
+                hostname: "web-1".to_owned(),
+                platform: "linux".to_owned(),
+                cpu: "4 logical cores".to_owned(),
+                ram: "1 KB used".to_owned(),
+            },
+            down_since: None,
+            downtime: Some(Duration::from_secs(125)),
+            flapping: false,
+            digest: vec![],
+            delivery_failure: None,
+        }
+    }
+
+    #[test]
+    fn renders_default_email() {
+        let message = RenderedMessage::render(
+            Some(DEFAULT_EMAIL_SUBJECT),
+            Some(DEFAULT_EMAIL_BODY),
+            &payload(models::MonitorStatusIndicator::OK),
+        )
+        .unwrap();
+
+        assert_eq!(
+            message.subject.unwrap(),
+            "[Schnooty] Monitor website has recovered"
+        );
+        let body = message.body.unwrap();
+        assert!(body.starts_with("Monitor website has recovered\n\nGot result: 200\n"));
+        assert!(body.contains("\nTotal downtime: 2m 5s\n"));
+        assert!(body.contains("\nHostname: web-1\n"));
+        assert!(!body.contains("openmonitors"));
+    }
+
+    #[test]
+    fn renders_templates_without_escaping() {
+        let mut payload = payload(models::MonitorStatusIndicator::DOWN);
+        payload.status.actual_result = "<503 & down>".to_owned();
+
+        let message = RenderedMessage::render(
+            Some("{{monitorName}} on {{nodeInfo.hostname}}"),
+            Some("{{actualResult}}{{#if downSince}} since {{downSince}}{{/if}}"),
+            &payload,
+        )
+        .unwrap();
+
+        assert_eq!(message.subject.unwrap(), "website on web-1");
+        assert_eq!(message.body.unwrap(), "<503 & down>");
+        assert_eq!(
+            RenderedMessage::render(None, None, &payload).unwrap(),
+            RenderedMessage::default()
+        );
+    }
+
+    #[test]
+    fn checks_templates() {
+        assert!(check_templates("email", Some("{{title}}"), Some(DEFAULT_EMAIL_BODY)).is_ok());
+        assert!(check_templates("slack", None, Some("{{#each digest}}{{title}}{{/each}}")).is_ok());
+        assert!(
+            check_templates("webhook", None, Some("{\"text\": \"{{downtimeSeconds}}\"}")).is_ok()
+        );
+        assert!(check_templates("log", None, None).is_ok());
+
+        assert!(check_templates("email", Some("{{monitorNmae}}"), None).is_err());
+        assert!(check_templates("email", None, Some("{{#if title}}")).is_err());
+        assert!(check_templates("webhook", Some("{{title}}"), None).is_err());
+        assert!(check_templates("exec", Some("{{title}}"), None).is_err());
+    }
+}
@@ -1,20 +1,192 @@
 use crate::api::{ApiFuture, ReadApi};
+use crate::error::Error;
 use crate::openapi_client::models;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
 
-pub struct FileApi;
+/// Reads monitors and alerts from YAML files. The path may be a pattern such as
+/// `monitors.d/*.yaml`, in which case every matching file is read in alphabetical order.
+pub struct FileApi {
+    path: String,
+}
+
+/// The contents of a single monitor or alert file.
+#[derive(Deserialize, Debug, Default)]
+struct SourceFile {
+    #[serde(default)]
+    monitors: Vec<models::Monitor>,
+    #[serde(default)]
+    alerts: Vec<models::Alert>,
+}
 
 impl FileApi {
-    //pub fn new(_path: &str) -> Self {
-    //    FileApi
-    //}
+    pub fn new<S: ToString>(path: S) -> Self {
+        FileApi {
+            path: path.to_string(),
+        }
+    }
+
+    fn load(&self) -> ApiFuture<Vec<SourceFile>> {
+        let path = self.path.clone();
+
+        Box::pin(async move { actix_rt::task::spawn_blocking(move || load_files(&path)).await? })
+    }
 }
 
 impl ReadApi for FileApi {
     fn get_monitors(&self) -> ApiFuture<Vec<models::Monitor>> {
-        unimplemented!()
+        let files = self.load();
+        let path = self.path.clone();
+
+        Box::pin(async move {
+            let mut monitors = vec![];
+            let mut names = HashSet::new();
+
+            for file in files.await? {
+                for monitor in file.monitors {
+                    if !names.insert(monitor.name.clone()) {
+                        return Err(Error::new(format!(
+                            "Monitor {} is defined more than once in {}",
+                            monitor.name, path
+                        )));
+                    }
+                    monitors.push(monitor);
+                }
+            }
+
+            Ok(monitors)
+        })
     }
 
     fn get_alerts(&self) -> ApiFuture<Vec<models::Alert>> {
-        unimplemented!()
+        let files = self.load();
+
+        Box::pin(async move {
+            Ok(files
+                .await?
+                .into_iter()
+                .flat_map(|file| file.alerts)
+                .collect())
+        })
+    }
+}
+
+fn load_files(pattern: &str) -> Result<Vec<SourceFile>, Error> {
+    let mut paths: Vec<PathBuf> = glob::glob(pattern)
+        .map_err(|err| Error::new(format!("Invalid path {}: {}", pattern, err)))?
+        .collect::<Result<_, _>>()
+        .map_err(|err| Error::new(format!("Failed to read {}: {}", pattern, err)))?;
+    paths.sort();
+
+    // an empty directory is fine, but a plain path that does not exist is a mistake
+    if paths.is_empty() && !pattern.contains(&['*', '?', '['][..]) {
+        return Err(Error::new(format!("No file found at {}", pattern)));
+    }
+
+    let mut files = vec![];
+    for path in paths {
+        debug!("Loading monitors and alerts from {}", path.display());
+
+        let contents = fs::read_to_string(&path).map_err(|err| {
+            Error::new(format!(
+                "Failed to load file at {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+
+        // an empty file has nothing in it yet
+        if contents.trim().is_empty() {
+            continue;
+        }
+
+        let file: SourceFile = serde_yaml::from_str(&contents).map_err(|err| {
+            Error::new(format!(
+                "Failed to parse file at {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+        files.push(file);
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("schnooty-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn monitors_file(names: &[&str]) -> String {
+        let mut contents = "monitors:\n".to_owned();
+        for name in names {
+            contents.push_str(&format!(
+                "  - name: {}\n    type: http\n    enabled: true\n    period: 1m\n    \
+                 timeout: 5s\n    body:\n      url: http://localhost/\n",
+                name
+            ));
+        }
+        contents
+    }
+
+    fn names(monitors: Vec<models::Monitor>) -> Vec<String> {
+        monitors.into_iter().map(|monitor| monitor.name).collect()
+    }
+
+    #[actix_rt::test]
+    async fn loads_matching_files_in_order() {
+        let dir = temp_dir("file-api-glob");
+        fs::write(dir.join("b.yaml"), monitors_file(&["database"])).unwrap();
+        fs::write(dir.join("a.yaml"), monitors_file(&["website", "api"])).unwrap();
+        fs::write(dir.join("c.yaml"), "").unwrap();
+        fs::write(dir.join("ignored.txt"), "not yaml: [").unwrap();
+
+        let api = FileApi::new(dir.join("*.yaml").display());
+        assert_eq!(
+            names(api.get_monitors().await.unwrap()),
+            vec!["website", "api", "database"]
+        );
+        assert!(api.get_alerts().await.unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[actix_rt::test]
+    async fn rejects_monitor_defined_in_two_files() {
+        let dir = temp_dir("file-api-duplicate");
+        fs::write(dir.join("a.yaml"), monitors_file(&["website"])).unwrap();
+        fs::write(dir.join("b.yaml"), monitors_file(&["website"])).unwrap();
+
+        let err = FileApi::new(dir.join("*.yaml").display())
+            .get_monitors()
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Monitor website is defined more than once"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[actix_rt::test]
+    async fn allows_empty_pattern_but_not_missing_file() {
+        let dir = temp_dir("file-api-missing");
+
+        let api = FileApi::new(dir.join("*.yaml").display());
+        assert!(api.get_monitors().await.unwrap().is_empty());
+
+        let api = FileApi::new(dir.join("monitors.yaml").display());
+        assert!(api.get_monitors().await.is_err());
+
+        let _ = fs::remove_dir_all(&dir);
     }
 }
@@ -1,5 +1,31 @@
+use crate::actors::alert_key;
+use crate::alerts::{
+    check_log_alert, check_opsgenie_alert, check_pagerduty_alert, check_templates,
+};
+use crate::duration::{
+    alert_escalate_after, alert_group_window, alert_repeat_interval, monitor_period,
+    monitor_timeout, parse_duration,
+};
+use crate::error::Error;
 use crate::openapi_client::models;
+use crate::silence::Silence;
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+/// Identifies the monitors defined at the top level of the config file
+pub const CONFIG_MONITORS_SOURCE_ID: &str = "config://monitors"; // yes it is a URI
+/// Identifies the alerts defined at the top level of the config file
+pub const CONFIG_ALERTS_SOURCE_ID: &str = "config://alerts"; // yes it is a URI
+
+/// The types of alert that can collect state changes in a group window and send a digest. The
+/// others track or act on each monitor separately.
+pub const DIGEST_ALERT_TYPES: &[&str] = &["email", "msTeamsMessage", "slack", "webhook"];
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "type")]
@@ -18,10 +44,455 @@ pub struct Config {
     pub create_session: bool,
     #[serde(default)]
     pub upload_statuses: bool,
+    #[serde(default = "default_max_concurrent_monitors")]
+    pub max_concurrent_monitors: usize,
+    #[serde(default)]
+    pub reload_on_change: bool,
+    #[serde(default = "default_api_sync_period")]
+    pub api_sync_period: String,
+    /// Where the monitors and alerts from the API are cached. There is no cache unless it is set
+    #[serde(default)]
+    pub api_cache_path: Option<String>,
+    #[serde(default)]
+    pub monitor_sources: Vec<MonitorSource>,
+    #[serde(default)]
+    pub alert_sources: Vec<AlertSource>,
+    #[serde(default)]
+    pub flap_detection: FlapDetection,
+    #[serde(default)]
+    pub silences: Vec<Silence>,
+    /// Path of the Unix socket for controlling the agent while it runs, such as adding silences
+    #[serde(default)]
+    pub control_socket: Option<String>,
+    #[serde(default)]
+    pub alert_delivery: AlertDelivery,
+    /// Templates for the messages of every alert of a type, keyed by alert type. They replace
+    /// the built-in format, and an alert's own templates replace them.
+    #[serde(default)]
+    pub templates: HashMap<String, MessageTemplates>,
+}
+
+impl Config {
+    /// Reads, parses and validates the YAML config file at the given path.
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let mut contents = String::new();
+
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut contents))
+            .map_err(|err| Error::new(format!("Failed to load file at {}: {}", path, err)))?;
+
+        let config: Config = serde_yaml::from_str(&contents).map_err(|err| {
+            Error::new(format!("Failed to parse config file at {}: {}", path, err))
+        })?;
+
+        config
+            .validate()
+            .map_err(|err| Error::new(format!("Invalid config file at {}: {}", path, err)))?;
+
+        Ok(config)
+    }
+
+    /// Checks the config for values that cannot be used, such as a malformed monitor period.
+    pub fn validate(&self) -> Result<(), Error> {
+        check_monitors(CONFIG_MONITORS_SOURCE_ID, &self.monitors)?;
+        check_alerts(&self.alerts)?;
+
+        parse_duration(&self.api_sync_period)
+            .map_err(|err| Error::new(format!("Invalid api_sync_period: {}", err)))?;
+
+        self.flap_detection.validate()?;
+        self.alert_delivery.validate()?;
+
+        for (alert_type, templates) in self.templates.iter() {
+            check_templates(
+                alert_type,
+                templates.subject.as_deref(),
+                templates.body.as_deref(),
+            )
+            .map_err(|err| {
+                Error::new(format!(
+                    "Invalid templates for {} alerts: {}",
+                    alert_type, err
+                ))
+            })?;
+        }
+
+        let mut silence_ids = HashSet::new();
+        for silence in self.silences.iter() {
+            silence.validate()?;
+            if let Some(ref id) = silence.id {
+                if !silence_ids.insert(id) {
+                    return Err(Error::new(format!(
+                        "Silence {} is defined more than once",
+                        id
+                    )));
+                }
+            }
+        }
+
+        for source_ids in [
+            self.resolve_monitor_sources()?
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            self.resolve_alert_sources()?
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect(),
+        ] {
+            let mut seen = HashSet::new();
+            for source_id in source_ids {
+                if !seen.insert(source_id.clone()) {
+                    return Err(Error::new(format!(
+                        "Source {} is listed more than once",
+                        source_id
+                    )));
+                }
+            }
+        }
+
+        for (source_id, source) in self.resolve_monitor_sources()? {
+            if let SourceDefinition::Inline { ref monitors, .. } = source {
+                check_monitors(&source_id, monitors)?;
+            }
+        }
+
+        let mut alerts: Vec<&models::Alert> = self.alerts.iter().collect();
+        let alert_sources = self.resolve_alert_sources()?;
+        for (_, source) in alert_sources.iter() {
+            if let SourceDefinition::Inline { alerts: ref a, .. } = source {
+                check_alerts(a)?;
+                alerts.extend(a.iter());
+            }
+        }
+        check_alert_keys(&alerts)?;
+
+        let mut monitors: Vec<&models::Monitor> = self.monitors.iter().collect();
+        let inline_sources = self.resolve_monitor_sources()?;
+        for (_, source) in inline_sources.iter() {
+            if let SourceDefinition::Inline {
+                monitors: ref m, ..
+            } = source
+            {
+                monitors.extend(m.iter());
+            }
+        }
+        if let Some(cycle) = find_dependency_cycle(&monitors) {
+            return Err(Error::new(format!(
+                "Monitor dependencies form a cycle: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// The sources of monitors in order of precedence. The monitors at the top level of the
+    /// config take precedence over all of these, and the Schnooty API at `base_url` comes last.
+    pub fn resolve_monitor_sources(&self) -> Result<Vec<(String, SourceDefinition)>, Error> {
+        let mut sources = vec![];
+        for (index, source) in self.monitor_sources.iter().enumerate() {
+            let definition = match source {
+                MonitorSource::File { path } => SourceDefinition::File { path: path.clone() },
+                MonitorSource::Api { base_url, api_key } => self.resolve_api(base_url, api_key)?,
+                MonitorSource::Inline { monitors } => SourceDefinition::Inline {
+                    monitors: monitors.clone(),
+                    alerts: vec![],
+                },
+            };
+            sources.push((definition.source_id("monitors", index), definition));
+        }
+        sources.extend(self.base_url_source());
+        Ok(sources)
+    }
+
+    /// The sources of alerts in order. The alerts at the top level of the config come first,
+    /// and the Schnooty API at `base_url` comes last.
+    pub fn resolve_alert_sources(&self) -> Result<Vec<(String, SourceDefinition)>, Error> {
+        let mut sources = vec![];
+        for (index, source) in self.alert_sources.iter().enumerate() {
+            let definition = match source {
+                AlertSource::File { path } => SourceDefinition::File { path: path.clone() },
+                AlertSource::Api { base_url, api_key } => self.resolve_api(base_url, api_key)?,
+                AlertSource::Inline { alerts } => SourceDefinition::Inline {
+                    monitors: vec![],
+                    alerts: alerts.clone(),
+                },
+            };
+            sources.push((definition.source_id("alerts", index), definition));
+        }
+        sources.extend(self.base_url_source());
+        Ok(sources)
+    }
+
+    fn resolve_api(
+        &self,
+        base_url: &Option<String>,
+        api_key: &Option<String>,
+    ) -> Result<SourceDefinition, Error> {
+        let base_url = match base_url.as_ref().or(self.base_url.as_ref()) {
+            Some(url) => url.clone(),
+            None => {
+                return Err(Error::new(
+                    "API source has no base_url, and there is no base_url to fall back on",
+                ))
+            }
+        };
+
+        Ok(SourceDefinition::Api {
+            base_url,
+            api_key: api_key.clone().or(self.api_key.clone()),
+            cache_path: None,
+        })
+    }
+
+    fn base_url_source(&self) -> Option<(String, SourceDefinition)> {
+        self.base_url.as_ref().map(|url| {
+            let definition = SourceDefinition::Api {
+                base_url: url.clone(),
+                api_key: self.api_key.clone(),
+                cache_path: self.api_cache_path.clone(),
+            };
+            (definition.source_id("", 0), definition)
+        })
+    }
+}
+
+/// Watches the config file, and sends on the receiver each time it changes. The directory is
+/// watched rather than the file, so an editor that saves by renaming a new file over the config
+/// is seen too. Changes stop being seen when the watcher is dropped.
+pub fn watch_config_file(path: &str) -> Result<(RecommendedWatcher, UnboundedReceiver<()>), Error> {
+    let path = Path::new(path);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_owned())
+        .ok_or_else(|| Error::new(format!("Invalid config path {}", path.display())))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let (sender, receiver) = mpsc::unbounded();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        match result {
+            Ok(event) => {
+                let is_config = event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name() == Some(file_name.as_os_str()));
+                // reading the config is not a change
+                if is_config && !event.kind.is_access() {
+                    let _ = sender.unbounded_send(());
+                }
+            }
+            Err(err) => error!("Error watching config file: {}", err),
+        }
+    })
+    .map_err(|err| Error::new(format!("Failed to watch {}: {}", path.display(), err)))?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(|err| Error::new(format!("Failed to watch {}: {}", path.display(), err)))?;
+
+    Ok((watcher, receiver))
+}
+
+/// Checks a list of monitors from one source for bad durations and duplicate names.
+pub fn check_monitors(source_id: &str, monitors: &[models::Monitor]) -> Result<(), Error> {
+    let mut names = HashSet::new();
+    for monitor in monitors.iter() {
+        monitor_period(monitor)?;
+        monitor_timeout(monitor)?;
+
+        if !names.insert(monitor.name.as_str()) {
+            return Err(Error::new(format!(
+                "Monitor {} is defined more than once in {}",
+                monitor.name, source_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks a list of alerts for bad durations and templates.
+fn check_alerts(alerts: &[models::Alert]) -> Result<(), Error> {
+    for alert in alerts.iter() {
+        check_templates(
+            &alert.type_,
+            alert.body.subject_template.as_deref(),
+            alert.body.body_template.as_deref(),
+        )
+        .map_err(|err| {
+            Error::new(format!(
+                "Alert {} has an invalid template: {}",
+                alert.id.as_deref().unwrap_or(&alert.type_),
+                err
+            ))
+        })?;
+        match alert.type_.as_str() {
+            "pagerduty" => check_pagerduty_alert(
+                alert.body.routing_key.as_deref(),
+                alert.body.severity.as_deref(),
+            ),
+            "opsgenie" => check_opsgenie_alert(
+                alert.body.api_key.as_deref(),
+                alert.body.priority.as_deref(),
+                alert.body.responders.as_deref(),
+            ),
+            "log" => check_log_alert(alert.body.target.as_deref(), alert.body.path.as_deref()),
+            // exec alerts are not retried, so a bad timeout must not wait for the command to run
+            "exec" => match alert.body.timeout {
+                Some(ref timeout) => parse_duration(timeout)
+                    .map(|_| ())
+                    .map_err(|err| Error::new(format!("Invalid timeout: {}", err))),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+        .map_err(|err| {
+            Error::new(format!(
+                "Alert {} is invalid: {}",
+                alert.id.as_deref().unwrap_or(&alert.type_),
+                err
+            ))
+        })?;
+        alert_repeat_interval(alert)?;
+        alert_escalate_after(alert)?;
+        if alert_group_window(alert)?.is_some()
+            && !DIGEST_ALERT_TYPES.contains(&alert.type_.as_str())
+        {
+            return Err(Error::new(format!(
+                "Alert {} has a groupWindow, but only {} alerts can send a digest",
+                alert.id.as_deref().unwrap_or(&alert.type_),
+                DIGEST_ALERT_TYPES.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that no two alerts share a key, as they would share the alert state of each monitor.
+/// Alerts without an ID share a key when they are of the same type and send to the same place.
+fn check_alert_keys(alerts: &[&models::Alert]) -> Result<(), Error> {
+    let mut keys = HashSet::new();
+    for alert in alerts.iter() {
+        if !keys.insert(alert_key(alert)) {
+            return Err(Error::new(match alert.id {
+                Some(ref id) => format!("Alert {} is defined more than once", id),
+                None => format!(
+                    "More than one {} alert sends to the same place. Give them an id to tell them apart",
+                    alert.type_
+                ),
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// Gives the alert the templates of its type for any it does not have itself.
+pub fn apply_templates(templates: &HashMap<String, MessageTemplates>, alert: &mut models::Alert) {
+    let defaults = match templates.get(&alert.type_) {
+        Some(t) => t,
+        None => return,
+    };
+    if alert.body.subject_template.is_none() {
+        alert.body.subject_template = defaults.subject.clone();
+    }
+    if alert.body.body_template.is_none() {
+        alert.body.body_template = defaults.body.clone();
+    }
+}
+
+/// Finds a cycle in the `dependsOn` lists of the monitors, and returns the names of the
+/// monitors in it with the first repeated at the end. Dependencies on monitors that are not in
+/// the list are ignored.
+pub fn find_dependency_cycle(monitors: &[&models::Monitor]) -> Option<Vec<String>> {
+    let parents = |monitor: &models::Monitor| -> Vec<usize> {
+        monitor
+            .depends_on
+            .iter()
+            .flatten()
+            .filter_map(|parent| {
+                monitors
+                    .iter()
+                    .position(|m| m.name == *parent || m.id.as_deref() == Some(parent.as_str()))
+            })
+            .collect()
+    };
+
+    // 0 is unvisited, 1 is on the current path and 2 is done
+    let mut visited = vec![0u8; monitors.len()];
+    let mut path: Vec<usize> = vec![];
+    let mut stack: Vec<(usize, Vec<usize>)> = vec![];
+
+    for start in 0..monitors.len() {
+        if visited[start] != 0 {
+            continue;
+        }
+        visited[start] = 1;
+        path.push(start);
+        stack.push((start, parents(monitors[start])));
+
+        while let Some((_, remaining)) = stack.last_mut() {
+            match remaining.pop() {
+                Some(next) if visited[next] == 1 => {
+                    let begin = path.iter().position(|i| *i == next).unwrap_or(0);
+                    let mut cycle: Vec<String> = path[begin..]
+                        .iter()
+                        .map(|i| monitors[*i].name.clone())
+                        .collect();
+                    cycle.push(monitors[next].name.clone());
+                    return Some(cycle);
+                }
+                Some(next) if visited[next] == 0 => {
+                    visited[next] = 1;
+                    path.push(next);
+                    stack.push((next, parents(monitors[next])));
+                }
+                Some(_) => {}
+                None => {
+                    if let Some((done, _)) = stack.pop() {
+                        visited[done] = 2;
+                        path.pop();
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// A monitor or alert source with the defaults from the rest of the config filled in.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SourceDefinition {
+    File {
+        path: String,
+    },
+    Api {
+        base_url: String,
+        api_key: Option<String>,
+        cache_path: Option<String>,
+    },
+    Inline {
+        monitors: Vec<models::Monitor>,
+        alerts: Vec<models::Alert>,
+    },
+}
+
+impl SourceDefinition {
+    /// Uniquely identifies the source. Inline sources are told apart by their position.
+    fn source_id(&self, kind: &str, index: usize) -> String {
+        match self {
+            SourceDefinition::File { path } => format!("file://{}", path),
+            SourceDefinition::Api { base_url, .. } => format!("api://{}", base_url),
+            SourceDefinition::Inline { .. } => format!("inline://{}/{}", kind, index),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum MonitorSource {
+    /// YAML file(s) with a `monitors` list. The path may be a pattern like `monitors.d/*.yaml`.
     #[serde(rename = "file")]
     File { path: String },
     #[serde(rename = "api")]
@@ -29,10 +500,13 @@ pub enum MonitorSource {
         base_url: Option<String>,
         api_key: Option<String>,
     },
+    #[serde(rename = "inline")]
+    Inline { monitors: Vec<models::Monitor> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum AlertSource {
+    /// YAML file(s) with an `alerts` list. The path may be a pattern like `alerts.d/*.yaml`.
     #[serde(rename = "file")]
     File { path: String },
     #[serde(rename = "api")]
@@ -40,6 +514,135 @@ pub enum AlertSource {
         base_url: Option<String>,
         api_key: Option<String>,
     },
+    #[serde(rename = "inline")]
+    Inline { alerts: Vec<models::Alert> },
+}
+
+/// Settings for detecting monitors that keep switching between OK and DOWN. A monitor starts
+/// flapping when the share of its recent results that changed state reaches `high_threshold`
+/// percent, and stops flapping when it falls below `low_threshold` percent.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlapDetection {
+    #[serde(default = "default_flap_detection_enabled")]
+    pub enabled: bool,
+    /// How many of the latest results are looked at
+    #[serde(default = "default_flap_window")]
+    pub window: usize,
+    #[serde(default = "default_flap_high_threshold")]
+    pub high_threshold: f64,
+    #[serde(default = "default_flap_low_threshold")]
+    pub low_threshold: f64,
+}
+
+impl FlapDetection {
+    fn validate(&self) -> Result<(), Error> {
+        if self.window < 3 {
+            return Err(Error::new(
+                "Invalid flap_detection: window must be at least 3 results",
+            ));
+        }
+        if !(0.0..=100.0).contains(&self.low_threshold)
+            || !(0.0..=100.0).contains(&self.high_threshold)
+            || self.low_threshold > self.high_threshold
+        {
+            return Err(Error::new(
+                "Invalid flap_detection: thresholds must be percentages with low_threshold no greater than high_threshold",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for FlapDetection {
+    fn default() -> Self {
+        Self {
+            enabled: default_flap_detection_enabled(),
+            window: default_flap_window(),
+            high_threshold: default_flap_high_threshold(),
+            low_threshold: default_flap_low_threshold(),
+        }
+    }
+}
+
+/// Handlebars templates for the subject and text of a message.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct MessageTemplates {
+    #[serde(default)]
+    pub subject: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// Settings for retrying alerts that fail to send. Failed alerts are retried with a backoff that
+/// doubles after each attempt, and kept in the spool at `spool_path`, if it is set, until they
+/// are sent. After `max_attempts` an alert is written to `dead_letter_path`, if it is set, and
+/// `fallback_alert` is sent a notice that alert delivery is failing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AlertDelivery {
+    #[serde(default = "default_delivery_max_attempts")]
+    pub max_attempts: usize,
+    #[serde(default = "default_delivery_initial_backoff")]
+    pub initial_backoff: String,
+    #[serde(default = "default_delivery_max_backoff")]
+    pub max_backoff: String,
+    #[serde(default)]
+    pub spool_path: Option<String>,
+    #[serde(default)]
+    pub dead_letter_path: Option<String>,
+    #[serde(default)]
+    pub fallback_alert: Option<models::Alert>,
+}
+
+impl AlertDelivery {
+    fn validate(&self) -> Result<(), Error> {
+        if self.max_attempts < 1 {
+            return Err(Error::new(
+                "Invalid alert_delivery: max_attempts must be at least 1",
+            ));
+        }
+        let (initial, max) = self.backoff()?;
+        if initial > max {
+            return Err(Error::new(
+                "Invalid alert_delivery: initial_backoff must be no greater than max_backoff",
+            ));
+        }
+        if let Some(ref alert) = self.fallback_alert {
+            check_alerts(std::slice::from_ref(alert))?;
+        }
+        Ok(())
+    }
+
+    /// The initial and maximum backoff between attempts.
+    pub fn backoff(&self) -> Result<(Duration, Duration), Error> {
+        let parse = |name: &str, value: &str| {
+            parse_duration(value)
+                .ok()
+                .filter(|d| *d > Duration::from_secs(0))
+                .ok_or_else(|| {
+                    Error::new(format!(
+                        "Invalid alert_delivery: {} must be a duration greater than zero",
+                        name
+                    ))
+                })
+        };
+        Ok((
+            parse("initial_backoff", &self.initial_backoff)?,
+            parse("max_backoff", &self.max_backoff)?,
+        ))
+    }
+}
+
+impl Default for AlertDelivery {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_delivery_max_attempts(),
+            initial_backoff: default_delivery_initial_backoff(),
+            max_backoff: default_delivery_max_backoff(),
+            spool_path: None,
+            dead_letter_path: None,
+            fallback_alert: None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -69,3 +672,221 @@ fn default_api_key() -> Option<String> {
     //String::new()
     None
 }
+
+fn default_max_concurrent_monitors() -> usize {
+    16
+}
+
+fn default_api_sync_period() -> String {
+    "1m".to_owned()
+}
+
+fn default_flap_detection_enabled() -> bool {
+    false
+}
+
+fn default_flap_window() -> usize {
+    21
+}
+
+fn default_flap_high_threshold() -> f64 {
+    50.0
+}
+
+fn default_flap_low_threshold() -> f64 {
+    25.0
+}
+
+fn default_delivery_max_attempts() -> usize {
+    8
+}
+
+fn default_delivery_initial_backoff() -> String {
+    "30s".to_owned()
+}
+
+fn default_delivery_max_backoff() -> String {
+    "30m".to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::monitoring::test_monitor;
+    use futures::{FutureExt, StreamExt};
+
+    fn monitor(name: &str, depends_on: &[&str]) -> models::Monitor {
+        let mut monitor = test_monitor(name);
+        monitor.depends_on = Some(depends_on.iter().map(|d| d.to_string()).collect());
+        monitor
+    }
+
+    /// Waits for the config file to change, and takes the other events of the same save.
+    async fn next_change(changes: &mut UnboundedReceiver<()>) {
+        actix_rt::time::timeout(Duration::from_secs(5), changes.next())
+            .await
+            .unwrap()
+            .unwrap();
+        actix_rt::time::sleep(Duration::from_millis(100)).await;
+        while let Some(Some(_)) = changes.next().now_or_never() {}
+    }
+
+    #[actix_rt::test]
+    async fn sees_config_saved_in_place_or_by_rename() {
+        let dir = std::env::temp_dir().join(format!("schnooty-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.yaml");
+        std::fs::write(&path, "monitors: []").unwrap();
+        let (_watcher, mut changes) = watch_config_file(path.to_str().unwrap()).unwrap();
+
+        // within the same second, so the modified time may not change
+        std::fs::write(&path, "monitors: [] ").unwrap();
+        next_change(&mut changes).await;
+
+        let saved = dir.join("config.yaml.new");
+        std::fs::write(&saved, "alerts: []").unwrap();
+        std::fs::rename(&saved, &path).unwrap();
+        next_change(&mut changes).await;
+
+        // reading the config or writing another file is not a change
+        Config::load(path.to_str().unwrap()).ok();
+        std::fs::write(dir.join("other.yaml"), "").unwrap();
+        actix_rt::time::sleep(Duration::from_millis(200)).await;
+        assert!(changes.next().now_or_never().is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn finds_dependency_cycles() {
+        let switch = monitor("switch", &[]);
+        let database = monitor("database", &["switch"]);
+        let api = monitor("api", &["database", "switch", "unknown"]);
+        assert_eq!(find_dependency_cycle(&[&api, &database, &switch]), None);
+
+        let switch = monitor("switch", &["api"]);
+        assert_eq!(
+            find_dependency_cycle(&[&api, &database, &switch]),
+            Some(vec![
+                "api".to_owned(),
+                "switch".to_owned(),
+                "api".to_owned()
+            ])
+        );
+        assert!(find_dependency_cycle(&[&monitor("loop", &["loop"])]).is_some());
+    }
+
+    #[test]
+    fn leaves_flap_detection_off_by_default() {
+        let config: Config = serde_yaml::from_str("flap_detection:\n  window: 5").unwrap();
+        assert!(!config.flap_detection.enabled);
+        assert!(!FlapDetection::default().enabled);
+    }
+
+    #[test]
+    fn rejects_alerts_that_share_a_key() {
+        let alert = |id: Option<&str>, url: &str, threshold: isize| {
+            let mut body = models::AlertBody::new();
+            body.url = Some(url.to_owned());
+            let mut alert = models::Alert::new("webhook".to_owned(), vec![], threshold, true, body);
+            alert.id = id.map(str::to_owned);
+            alert
+        };
+        let check = |alerts: &[models::Alert]| check_alert_keys(&alerts.iter().collect::<Vec<_>>());
+
+        assert!(check(&[alert(None, "http://a", 1), alert(None, "http://b", 1)]).is_ok());
+        assert!(check(&[alert(None, "http://a", 1), alert(None, "http://a", 3)]).is_err());
+        assert!(check(&[
+            alert(Some("team"), "http://a", 1),
+            alert(Some("oncall"), "http://a", 3)
+        ])
+        .is_ok());
+        assert!(check(&[
+            alert(Some("team"), "http://a", 1),
+            alert(Some("team"), "http://b", 1)
+        ])
+        .is_err());
+    }
+
+    fn alert(type_: &str, body: &str) -> Result<(), Error> {
+        let yaml = format!(
+            "alerts:\n  - type: {}\n    monitors: []\n    threshold: 1\n    enabled: true\n    body: {}",
+            type_, body
+        );
+        serde_yaml::from_str::<Config>(&yaml).unwrap().validate()
+    }
+
+    #[test]
+    fn checks_pagerduty_alerts() {
+        assert!(alert("pagerduty", "{routingKey: R0UT1NG}").is_ok());
+        assert!(alert("pagerduty", "{routingKey: R0UT1NG, severity: warning}").is_ok());
+        assert!(alert("pagerduty", "{routingKey: R0UT1NG, severity: disaster}").is_err());
+        assert!(alert("pagerduty", "{severity: warning}").is_err());
+    }
+
+    #[test]
+    fn checks_opsgenie_alerts() {
+        assert!(alert("opsgenie", "{apiKey: k3y}").is_ok());
+        assert!(alert(
+            "opsgenie",
+            "{apiKey: k3y, priority: P1, responders: ['team:ops', 'user:jo@example.com']}"
+        )
+        .is_ok());
+        assert!(alert("opsgenie", "{apiKey: k3y, priority: P0}").is_err());
+        assert!(alert("opsgenie", "{apiKey: k3y, responders: [ops]}").is_err());
+        assert!(alert("opsgenie", "{priority: P1}").is_err());
+    }
+
+    #[test]
+    fn checks_log_alerts() {
+        assert!(alert("log", "{target: file, path: /var/log/schnooty/alerts.log}").is_ok());
+        assert!(alert("log", "{target: syslog}").is_ok());
+        assert!(alert("log", "{target: journald}").is_ok());
+        assert!(alert("log", "{target: file}").is_err());
+        assert!(alert("log", "{target: stdout}").is_err());
+        assert!(alert("log", "{path: /var/log/schnooty/alerts.log}").is_err());
+    }
+
+    #[test]
+    fn checks_exec_alert_timeout() {
+        assert!(alert("exec", "{command: /usr/local/bin/restart-web}").is_ok());
+        assert!(alert("exec", "{command: /usr/local/bin/restart-web, timeout: 1m}").is_ok());
+        assert!(alert(
+            "exec",
+            "{command: /usr/local/bin/restart-web, timeout: soon}"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn checks_and_applies_templates() {
+        let config = |yaml: &str| serde_yaml::from_str::<Config>(yaml).unwrap().validate();
+
+        assert!(
+            config("templates:\n  email:\n    subject: '{{title}} on {{nodeInfo.hostname}}'")
+                .is_ok()
+        );
+        assert!(config("templates:\n  email:\n    subject: '{{titel}}'").is_err());
+        assert!(config("templates:\n  exec:\n    body: '{{title}}'").is_err());
+        assert!(config(
+            "alerts:\n  - type: slack\n    monitors: []\n    threshold: 1\n    enabled: true\n    body:\n      bodyTemplate: '{{#if flapping}}'"
+        )
+        .is_err());
+
+        let mut templates = HashMap::new();
+        templates.insert(
+            "email".to_owned(),
+            MessageTemplates {
+                subject: Some("{{title}}".to_owned()),
+                body: Some("{{actualResult}}".to_owned()),
+            },
+        );
+        let mut body = models::AlertBody::new();
+        body.body_template = Some("{{description}}".to_owned());
+        let mut alert = models::Alert::new("email".to_owned(), vec![], 1, true, body);
+        apply_templates(&templates, &mut alert);
+
+        assert_eq!(alert.body.subject_template.as_deref(), Some("{{title}}"));
+        assert_eq!(alert.body.body_template.as_deref(), Some("{{description}}"));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::actors::{
+    AddSilence, AlerterActor, GetAlerterStatus, GetDeliveryStatus, GetSilences, RemoveSilence,
+};
+use crate::error::Error;
+use crate::silence::Silence;
+use actix::Addr;
+use async_std::io::prelude::*;
+use async_std::io::BufReader;
+use async_std::os::unix::net::{UnixListener, UnixStream};
+use async_std::stream::StreamExt;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::os::unix::fs::FileTypeExt;
+
+/// A request sent to the control socket as a line of JSON, such as
+/// `{"command": "remove_silence", "id": "silence-1"}`.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlRequest {
+    AddSilence { silence: Box<Silence> },
+    RemoveSilence { id: String },
+    ListSilences,
+    Status,
+    DeliveryStatus,
+}
+
+/// Binds the control socket at the path, readable and writable only by the agent's user. A
+/// socket left behind by an earlier run is removed first, but anything else at the path is an
+/// error.
+pub async fn bind_control_socket(path: &str) -> Result<UnixListener, Error> {
+    match async_std::fs::symlink_metadata(path).await {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            async_std::fs::remove_file(path).await?;
+        }
+        Ok(_) => {
+            return Err(Error::new(format!(
+                "{} already exists and is not a socket",
+                path
+            )))
+        }
+        Err(_) => {}
+    }
+
+    // the socket is created with the restrictive umask rather than changed after binding, so
+    // there is no moment when others can connect to it
+    let old_umask = unsafe { libc::umask(0o177) };
+    let listener = std::os::unix::net::UnixListener::bind(path);
+    unsafe { libc::umask(old_umask) };
+    Ok(UnixListener::from(listener?))
+}
+
+/// Answers requests on the control socket until the listener fails.
+pub async fn serve_control_socket(listener: UnixListener, alerter_addr: Addr<AlerterActor>) {
+    let mut incoming = listener.incoming();
+
+    while let Some(stream) = incoming.next().await {
+        match stream {
+            Ok(stream) => {
+                actix_rt::spawn(handle_connection(stream, alerter_addr.clone()));
+            }
+            Err(err) => error!("Error accepting control socket connection: {}", err),
+        }
+    }
+}
+
+/// Reads requests from the connection one line at a time and writes a line of JSON for each:
+/// `{"ok": true, "result": ...}` or `{"ok": false, "error": "..."}`.
+async fn handle_connection(stream: UnixStream, alerter_addr: Addr<AlerterActor>) {
+    let mut lines = BufReader::new(stream.clone()).lines();
+    let mut writer = stream;
+
+    while let Some(Ok(line)) = lines.next().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match handle_request(&line, &alerter_addr).await {
+            Ok(result) => json!({ "ok": true, "result": result }),
+            Err(err) => json!({ "ok": false, "error": err.to_string() }),
+        };
+
+        let mut response = response.to_string();
+        response.push('\n');
+        if let Err(err) = writer.write_all(response.as_bytes()).await {
+            debug!("Control socket connection closed (error={})", err);
+            return;
+        }
+    }
+}
+
+async fn handle_request(line: &str, alerter_addr: &Addr<AlerterActor>) -> Result<Value, Error> {
+    let request: ControlRequest = serde_json::from_str(line)
+        .map_err(|err| Error::new(format!("Invalid control request: {}", err)))?;
+
+    debug!("Handling control request (request={:?})", request);
+
+    Ok(match request {
+        ControlRequest::AddSilence { silence } => {
+            let id = alerter_addr
+                .send(AddSilence { silence: *silence })
+                .await??;
+            json!({ "id": id })
+        }
+        ControlRequest::RemoveSilence { id } => {
+            alerter_addr.send(RemoveSilence { id }).await??;
+            Value::Null
+        }
+        ControlRequest::ListSilences => {
+            serde_json::to_value(alerter_addr.send(GetSilences).await?)?
+        }
+        ControlRequest::Status => serde_json::to_value(alerter_addr.send(GetAlerterStatus).await?)?,
+        ControlRequest::DeliveryStatus => {
+            serde_json::to_value(alerter_addr.send(GetDeliveryStatus).await?)?
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::actors::TimerActor;
+    use crate::alerts::AlertApiImpl;
+    use actix::Actor;
+    use std::os::unix::fs::PermissionsExt;
+
+    async fn request(stream: &mut UnixStream, request: Value) -> Value {
+        let mut line = request.to_string();
+        line.push('\n');
+        stream.write_all(line.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        BufReader::new(stream.clone())
+            .read_line(&mut response)
+            .await
+            .unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn adds_lists_and_removes_silences() {
+        let path = std::env::temp_dir().join(format!("schnooty-control-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+        let alerter_addr =
+            AlerterActor::new(AlertApiImpl::new(), TimerActor::new().start()).start();
+        let listener = bind_control_socket(path).await.unwrap();
+        actix_rt::spawn(serve_control_socket(listener, alerter_addr));
+
+        let mut stream = UnixStream::connect(path).await.unwrap();
+
+        let added = request(
+            &mut stream,
+            json!({
+                "command": "add_silence",
+                "silence": { "tags": ["web"], "duration": "1h", "reason": "Deploying" }
+            }),
+        )
+        .await;
+        assert_eq!(added["ok"], true);
+        let id = added["result"]["id"].as_str().unwrap().to_owned();
+
+        let listed = request(&mut stream, json!({ "command": "list_silences" })).await;
+        assert_eq!(listed["result"][0]["id"], id.as_str());
+        assert_eq!(listed["result"][0]["active"], true);
+        assert_eq!(listed["result"][0]["reason"], "Deploying");
+
+        let removed = request(
+            &mut stream,
+            json!({ "command": "remove_silence", "id": id }),
+        )
+        .await;
+        assert_eq!(removed["ok"], true);
+
+        let removed_again = request(
+            &mut stream,
+            json!({ "command": "remove_silence", "id": id }),
+        )
+        .await;
+        assert_eq!(removed_again["ok"], false);
+
+        let invalid = request(&mut stream, json!({ "command": "restart" })).await;
+        assert_eq!(invalid["ok"], false);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn binds_only_over_an_old_socket() {
+        let path = std::env::temp_dir().join(format!("schnooty-bind-{}", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        std::fs::write(path, "not a socket").unwrap();
+        assert!(bind_control_socket(path).await.is_err());
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "not a socket");
+        std::fs::remove_file(path).unwrap();
+
+        drop(bind_control_socket(path).await.unwrap());
+        let listener = bind_control_socket(path).await.unwrap();
+        let mode = std::fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        drop(listener);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,320 @@
+use crate::error::Error;
+use crate::openapi_client::models;
+use std::time::Duration;
+
+const MILLI: u64 = 1;
+const SECOND: u64 = 1000 * MILLI;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Parses a duration like `30s`, `1m30s`, `1h 30m`, `500ms` or an ISO-8601 duration
+/// like `PT1M30S`.
+pub fn parse_duration(value: &str) -> Result<Duration, Error> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return Err(Error::new("Duration is empty"));
+    }
+
+    let millis = if value.starts_with('P') || value.starts_with('p') {
+        parse_iso_8601(&value[1..])
+    } else {
+        parse_compound(value)
+    }
+    .map_err(|err| Error::new(format!("Invalid duration '{}': {}", value, err)))?;
+
+    Ok(Duration::from_millis(millis))
+}
+
+/// Parses the period of the monitor. The period must be greater than zero.
+pub fn monitor_period(monitor: &models::Monitor) -> Result<Duration, Error> {
+    parse_positive(&monitor.period).map_err(|err| {
+        Error::new(format!(
+            "Monitor {} has an invalid period: {}",
+            monitor.name, err
+        ))
+    })
+}
+
+/// Parses the timeout of the monitor. The timeout must be greater than zero.
+pub fn monitor_timeout(monitor: &models::Monitor) -> Result<Duration, Error> {
+    parse_positive(&monitor.timeout).map_err(|err| {
+        Error::new(format!(
+            "Monitor {} has an invalid timeout: {}",
+            monitor.name, err
+        ))
+    })
+}
+
+/// Parses the repeat interval of the alert, if it has one. The interval must be greater than
+/// zero.
+pub fn alert_repeat_interval(alert: &models::Alert) -> Result<Option<Duration>, Error> {
+    alert_duration(alert, "repeatInterval", alert.repeat_interval.as_ref())
+}
+
+/// Parses how long the monitor stays DOWN before the alert escalates to being sent, if it has a
+/// delay. The delay must be greater than zero.
+pub fn alert_escalate_after(alert: &models::Alert) -> Result<Option<Duration>, Error> {
+    alert_duration(alert, "escalateAfter", alert.escalate_after.as_ref())
+}
+
+/// Parses how long the alert collects state changes for before sending them as a digest, if
+/// it groups them. The window must be greater than zero.
+pub fn alert_group_window(alert: &models::Alert) -> Result<Option<Duration>, Error> {
+    alert_duration(alert, "groupWindow", alert.group_window.as_ref())
+}
+
+fn alert_duration(
+    alert: &models::Alert,
+    field: &str,
+    value: Option<&String>,
+) -> Result<Option<Duration>, Error> {
+    value
+        .map(|value| {
+            parse_positive(value).map_err(|err| {
+                Error::new(format!(
+                    "Alert {} has an invalid {}: {}",
+                    alert.id.as_deref().unwrap_or(&alert.type_),
+                    field,
+                    err
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// Formats a duration to the second, such as `2d 3h 5m 10s`.
+pub fn format_duration(duration: Duration) -> String {
+    let mut seconds = duration.as_secs();
+    let mut parts = vec![];
+    for (unit, unit_seconds) in &[
+        ("d", DAY / SECOND),
+        ("h", HOUR / SECOND),
+        ("m", MINUTE / SECOND),
+    ] {
+        if seconds >= *unit_seconds {
+            parts.push(format!("{}{}", seconds / unit_seconds, unit));
+            seconds %= unit_seconds;
+        }
+    }
+    if seconds > 0 || parts.is_empty() {
+        parts.push(format!("{}s", seconds));
+    }
+    parts.join(" ")
+}
+
+impl models::TimeDelta {
+    pub fn to_duration(&self) -> Result<Duration, Error> {
+        parse_duration(self)
+    }
+}
+
+fn parse_positive(value: &str) -> Result<Duration, Error> {
+    let duration = parse_duration(value)?;
+    if duration == Duration::from_millis(0) {
+        return Err(Error::new(format!(
+            "Duration '{}' must be greater than zero",
+            value.trim()
+        )));
+    }
+    Ok(duration)
+}
+
+/// Parses sequences of `<number><unit>` such as `1h30m` or `1h 30m 10s`.
+fn parse_compound(value: &str) -> Result<u64, String> {
+    let mut chars = value.chars().peekable();
+    let mut total = 0u64;
+
+    while chars.peek().is_some() {
+        while let Some(c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        let mut number = String::new();
+        while let Some(c) = chars.peek() {
+            if c.is_ascii_digit() {
+                number.push(*c);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        let mut unit = String::new();
+        while let Some(c) = chars.peek() {
+            if c.is_alphabetic() {
+                unit.push(c.to_ascii_lowercase());
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if number.is_empty() && unit.is_empty() {
+            match chars.peek() {
+                Some(c) => return Err(format!("unexpected character '{}'", c)),
+                None => break,
+            }
+        }
+
+        if number.is_empty() {
+            return Err(format!("unit '{}' has no number before it", unit));
+        }
+
+        if unit.is_empty() {
+            return Err(format!("number {} has no unit after it", number));
+        }
+
+        let unit_millis = match unit.as_ref() {
+            "ms" | "msec" | "millis" | "millisecond" | "milliseconds" => MILLI,
+            "s" | "sec" | "secs" | "second" | "seconds" => SECOND,
+            "m" | "min" | "mins" | "minute" | "minutes" => MINUTE,
+            "h" | "hr" | "hrs" | "hour" | "hours" => HOUR,
+            "d" | "day" | "days" => DAY,
+            "w" | "week" | "weeks" => WEEK,
+            _ => return Err(format!("unknown unit '{}'", unit)),
+        };
+
+        total = number
+            .parse::<u64>()
+            .ok()
+            .and_then(|n| n.checked_mul(unit_millis))
+            .and_then(|millis| total.checked_add(millis))
+            .ok_or_else(|| "duration is too large".to_string())?;
+    }
+
+    Ok(total)
+}
+
+/// Parses the part of an ISO-8601 duration after the leading `P`, e.g. `1DT2H30M` or `2W`.
+/// Years and months are rejected because their length varies.
+fn parse_iso_8601(value: &str) -> Result<u64, String> {
+    if value.is_empty() {
+        return Err("ISO-8601 duration has no components".to_string());
+    }
+
+    let mut total = 0u64;
+    let mut in_time = false;
+    let mut number = String::new();
+    let mut has_component = false;
+
+    for c in value.chars() {
+        let c = c.to_ascii_uppercase();
+        match c {
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            _ => {
+                if number.is_empty() {
+                    return Err(format!("designator '{}' has no number before it", c));
+                }
+
+                let unit_millis = match (in_time, c) {
+                    (false, 'W') => WEEK,
+                    (false, 'D') => DAY,
+                    (true, 'H') => HOUR,
+                    (true, 'M') => MINUTE,
+                    (true, 'S') => SECOND,
+                    (false, 'Y') | (false, 'M') => {
+                        return Err("years and months are not supported".to_string())
+                    }
+                    _ => return Err(format!("unexpected designator '{}'", c)),
+                };
+
+                let amount = number
+                    .parse::<f64>()
+                    .map_err(|_| format!("'{}' is not a number", number))?;
+
+                if number.contains('.') && c != 'S' {
+                    return Err("only seconds may have a fraction".to_string());
+                }
+
+                let millis = amount * unit_millis as f64;
+                if !millis.is_finite() || millis >= u64::MAX as f64 {
+                    return Err("duration is too large".to_string());
+                }
+
+                total = total
+                    .checked_add(millis.round() as u64)
+                    .ok_or_else(|| "duration is too large".to_string())?;
+
+                number.clear();
+                has_component = true;
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return Err(format!("number {} has no designator after it", number));
+    }
+
+    if !has_component {
+        return Err("ISO-8601 duration has no components".to_string());
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(value: &str) -> u64 {
+        parse_duration(value).unwrap().as_millis() as u64
+    }
+
+    #[test]
+    fn parses_compound_durations() {
+        assert_eq!(millis("30s"), 30 * SECOND);
+        assert_eq!(millis("1h30m"), HOUR + 30 * MINUTE);
+        assert_eq!(millis(" 1h 30m 10s "), HOUR + 30 * MINUTE + 10 * SECOND);
+        assert_eq!(millis("1m500ms"), MINUTE + 500);
+        assert_eq!(millis("2w1d"), 2 * WEEK + DAY);
+        assert_eq!(millis("3Hours"), 3 * HOUR);
+    }
+
+    #[test]
+    fn parses_iso_8601_durations() {
+        assert_eq!(millis("PT1M30S"), MINUTE + 30 * SECOND);
+        assert_eq!(millis("P1DT2H30M"), DAY + 2 * HOUR + 30 * MINUTE);
+        assert_eq!(millis("P2W"), 2 * WEEK);
+        assert_eq!(millis("pt0.5s"), 500);
+        assert_eq!(millis("PT1,25S"), 1250);
+
+        assert!(parse_duration("P1Y").is_err());
+        assert!(parse_duration("P1M").is_err());
+        assert!(parse_duration("PT1.5M").is_err());
+        assert!(parse_duration("P").is_err());
+        assert!(parse_duration("PT").is_err());
+        assert!(parse_duration("PT5").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("   ").is_err());
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("5s!").is_err());
+        assert!(parse_duration("99999999999999999999s").is_err());
+        assert!(parse_duration("18446744073709551615w").is_err());
+        assert!(parse_duration("18446744073709551615ms 1ms").is_err());
+        assert!(parse_positive("0s").is_err());
+    }
+
+    #[test]
+    fn formats_durations_to_the_second() {
+        assert_eq!(format_duration(Duration::from_millis(0)), "0s");
+        assert_eq!(format_duration(Duration::from_millis(125_500)), "2m 5s");
+        assert_eq!(
+            format_duration(Duration::from_secs(2 * 86400 + 3 * 3600 + 10)),
+            "2d 3h 10s"
+        );
+    }
+}
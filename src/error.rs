@@ -71,6 +71,22 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<actix_rt::task::JoinError> for Error {
+    fn from(err: actix_rt::task::JoinError) -> Self {
+        Self {
+            description: format!("Blocking task failed: {}", err),
+        }
+    }
+}
+
+impl From<actix::MailboxError> for Error {
+    fn from(err: actix::MailboxError) -> Self {
+        Self {
+            description: format!("Actor is unavailable: {}", err),
+        }
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Self {
         Self {
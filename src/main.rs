@@ -12,14 +12,20 @@ extern crate log;
 extern crate actix;
 extern crate actix_rt;
 extern crate async_std;
+extern crate glob;
+extern crate handlebars;
+extern crate hmac;
 extern crate hostname;
 extern crate lazy_static;
 extern crate lettre_email;
+extern crate libc;
 extern crate native_tls;
+extern crate notify;
 extern crate num_cpus;
 extern crate rand;
 extern crate redis;
 extern crate serde_yaml;
+extern crate sha2;
 #[cfg(test)]
 extern crate test_logger;
 //extern crate http as http_types;
@@ -33,20 +39,29 @@ mod actors;
 mod alerts;
 mod api;
 mod config;
+mod control;
+mod duration;
 mod error;
 mod http;
 mod monitoring;
 mod openapi_client;
+mod silence;
 
 use crate::actix::Actor;
 use crate::actors::*;
-use crate::api::HttpApi;
+use crate::api::{FileApi, HttpApi, MemoryApi};
 use crate::config::*;
+use actix::Addr;
+use actix_rt::signal::unix::{signal, SignalKind};
 use clap::{AppSettings, Clap};
-use std::fs::File;
-use std::io::Read;
+use futures::future::Either;
+use futures::{FutureExt, StreamExt};
 use std::time::Duration;
 
+/// How long to wait after the config file changes before reloading it, as an editor can write
+/// it in several steps
+const CONFIG_SETTLE_MS: u64 = 200;
+
 #[derive(Clap, Debug)]
 #[clap(version = "0.1.1", author = "Mate Antunovic <mate AT schnooty.com>")]
 #[clap(setting = AppSettings::ColoredHelp)]
@@ -69,34 +84,13 @@ async fn main() {
     let config_file_path = &opts.config;
     info!("Loading config from: {}", config_file_path);
 
-    let mut file = match File::open(config_file_path) {
-        Ok(f) => f,
-        Err(err) => {
-            error!("Error loading config from {}: {}", config_file_path, err);
-            std::process::exit(1);
-        }
-    };
-
-    let mut contents = String::new();
-
     info!("Parsing config");
 
-    match file.read_to_string(&mut contents) {
-        Ok(_) => {}
-        Err(err) => {
-            error!("Failed to load file at {}: {}", config_file_path, err);
-            return;
-        }
-    };
-
-    let config: Config = match serde_yaml::from_str(&contents) {
+    let config = match Config::load(config_file_path) {
         Ok(c) => c,
         Err(err) => {
-            error!(
-                "Failed to parse config file at {}: {}",
-                config_file_path, err
-            );
-            return;
+            error!("{}", err);
+            std::process::exit(1);
         }
     };
 
@@ -121,36 +115,48 @@ async fn main() {
     debug!("Starting the Actix system");
 
     let timer_addr = actors::TimerActor::new().start();
-    let alerter = actors::AlerterActor::new(alerts::AlertApiImpl::new());
+    let alerter = actors::AlerterActor::new(alerts::AlertApiImpl::new(), timer_addr.clone());
     let alerter_addr = alerter.start();
 
-    let status_recipients = if let Some(ref api_addr) = &api_addr {
+    let mut status_recipients = vec![alerter_addr.clone().recipient()];
+    if let Some(ref api_addr) = &api_addr {
         let uploader = actors::UploaderActor::new(api_addr.clone());
         let uploader_addr = uploader.start();
 
-        vec![uploader_addr.recipient(), alerter_addr.clone().recipient()]
+        status_recipients.push(uploader_addr.recipient());
     } else {
         info!("No API URL. Statuses will not be uploaded");
-        vec![]
-    };
+    }
 
     let monitoring = monitoring::MonitorFutureMaker::new();
 
-    let executor_actor = actors::ExecutorActor::new(monitoring, status_recipients);
+    let executor_actor = actors::ExecutorActor::new(
+        monitoring,
+        status_recipients,
+        config.max_concurrent_monitors,
+    );
     let executor_addr = executor_actor.start();
 
     let scheduler_actor =
-        actors::SchedulerActor::new(vec![executor_addr.recipient()], timer_addr.clone());
+        actors::SchedulerActor::new(vec![executor_addr.clone().recipient()], timer_addr.clone());
     let scheduler_addr = scheduler_actor.start();
 
     // all the configurators
 
     let configurator = ConfiguratorActor::new(
-        vec![scheduler_addr.recipient()],
-        vec![alerter_addr.recipient()],
+        vec![
+            scheduler_addr.clone().recipient(),
+            alerter_addr.clone().recipient(),
+        ],
+        vec![alerter_addr.clone().recipient()],
+        vec![
+            scheduler_addr.recipient(),
+            executor_addr.recipient(),
+            alerter_addr.clone().recipient(),
+        ],
     );
 
-    let session_actor = actors::SessionActor::new(&config, timer_addr.recipient(), vec![]);
+    let session_actor = actors::SessionActor::new(&config, timer_addr.clone().recipient(), vec![]);
     let session_actor_addr = session_actor.start();
     let configurator_addr = configurator.start();
 
@@ -194,8 +200,261 @@ async fn main() {
         }
     };
 
+    if let Err(err) = alerter_addr
+        .send(CurrentConfig {
+            config: config.clone(),
+        })
+        .await
+    {
+        error!("Error configuring alerter: {}", err);
+        std::process::exit(1);
+    }
+
+    if let Some(ref path) = config.control_socket {
+        match control::bind_control_socket(path).await {
+            Ok(listener) => {
+                info!("Listening on control socket (path={})", path);
+                actix_rt::spawn(control::serve_control_socket(
+                    listener,
+                    alerter_addr.clone(),
+                ));
+            }
+            Err(err) => {
+                error!("Error binding control socket (path={}): {}", path, err);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let sources = start_sources(&config, &timer_addr, &configurator_addr);
+
     debug!("Done in the main thread");
+
+    watch_config(
+        config_file_path,
+        config,
+        configurator_addr,
+        session_actor_addr,
+        alerter_addr,
+        timer_addr,
+        sources,
+    )
+    .await;
+}
+
+/// Starts an actor for every monitor and alert source in the config. A source that provides
+/// both monitors and alerts is loaded by a single actor.
+fn start_sources(
+    config: &Config,
+    timer_addr: &Addr<TimerActor>,
+    configurator_addr: &Addr<ConfiguratorActor>,
+) -> Vec<Addr<MonitorSourceActor>> {
+    let (monitor_sources, alert_sources, sync_period) = match (
+        config.resolve_monitor_sources(),
+        config.resolve_alert_sources(),
+        duration::parse_duration(&config.api_sync_period),
+    ) {
+        (Ok(m), Ok(a), Ok(p)) => (m, a, p),
+        _ => {
+            // the config is validated before it gets here
+            error!("Invalid sources in config. No sources started");
+            return vec![];
+        }
+    };
+
+    let mut sources: Vec<(String, SourceDefinition)> = vec![];
+    for (source_id, definition) in monitor_sources.iter().chain(alert_sources.iter()) {
+        if !sources.iter().any(|(id, _)| id == source_id) {
+            sources.push((source_id.clone(), definition.clone()));
+        }
+    }
+
+    sources
+        .into_iter()
+        .map(|(source_id, definition)| {
+            let monitor_recipients = if monitor_sources.iter().any(|(id, _)| *id == source_id) {
+                vec![configurator_addr.clone().recipient()]
+            } else {
+                vec![]
+            };
+            let alert_recipients = if alert_sources.iter().any(|(id, _)| *id == source_id) {
+                vec![configurator_addr.clone().recipient()]
+            } else {
+                vec![]
+            };
+
+            debug!(
+                "Loading from source every {:?} (source_id={})",
+                sync_period, source_id
+            );
+
+            let timer = timer_addr.clone();
+            let source_actor = match definition {
+                SourceDefinition::File { path } => MonitorSourceActor::new(
+                    source_id,
+                    FileApi::new(path),
+                    sync_period,
+                    timer,
+                    monitor_recipients,
+                    alert_recipients,
+                ),
+                SourceDefinition::Api {
+                    base_url,
+                    api_key,
+                    cache_path,
+                } => MonitorSourceActor::new(
+                    source_id,
+                    HttpApi::new(&api::HttpConfig { base_url, api_key }),
+                    sync_period,
+                    timer,
+                    monitor_recipients,
+                    alert_recipients,
+                )
+                .cache_path(cache_path),
+                SourceDefinition::Inline { monitors, alerts } => MonitorSourceActor::new(
+                    source_id,
+                    MemoryApi::new(monitors, alerts),
+                    sync_period,
+                    timer,
+                    monitor_recipients,
+                    alert_recipients,
+                ),
+            };
+            source_actor.start()
+        })
+        .collect()
+}
+
+/// Reloads the config on SIGHUP, or when the file changes if `reload_on_change` is set.
+/// A config that fails to load is rejected and the previous config stays active.
+async fn watch_config(
+    path: &str,
+    mut config: Config,
+    configurator_addr: Addr<ConfiguratorActor>,
+    session_actor_addr: Addr<SessionActor>,
+    alerter_addr: Addr<AlerterActor>,
+    timer_addr: Addr<TimerActor>,
+    mut sources: Vec<Addr<MonitorSourceActor>>,
+) {
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(s) => Some(s),
+        Err(err) => {
+            error!("Failed to listen for SIGHUP: {}", err);
+            None
+        }
+    };
+
+    // the file is watched even if reload_on_change is not set, so a reload can turn it on
+    let (_watcher, mut changes) = match watch_config_file(path) {
+        Ok((watcher, changes)) => (Some(watcher), Some(changes)),
+        Err(err) => {
+            error!("{}. Only SIGHUP reloads the config", err);
+            (None, None)
+        }
+    };
+
     loop {
-        async_std::task::sleep(Duration::new(60 * 60 * 24, 0)).await; // sleep for a day idk
+        let changed = {
+            let hangup_signal = async {
+                match hangup {
+                    Some(ref mut h) => h.recv().await,
+                    None => futures::future::pending().await,
+                }
+            };
+            let change = async {
+                match changes {
+                    Some(ref mut c) => c.next().await,
+                    None => futures::future::pending().await,
+                }
+            };
+            futures::pin_mut!(hangup_signal, change);
+
+            match futures::future::select(hangup_signal, change).await {
+                Either::Left(_) => None,
+                Either::Right((change, _)) => Some(change.is_some()),
+            }
+        };
+
+        match changed {
+            None => info!("Received SIGHUP. Reloading config from {}", path),
+            Some(false) => {
+                error!("Stopped watching the config file. Only SIGHUP reloads the config");
+                changes = None;
+                continue;
+            }
+            Some(true) => {
+                actix::clock::sleep(Duration::from_millis(CONFIG_SETTLE_MS)).await;
+                if let Some(ref mut c) = changes {
+                    while let Some(Some(_)) = c.next().now_or_never() {}
+                }
+                if !config.reload_on_change {
+                    continue;
+                }
+                info!("Config file changed. Reloading config from {}", path);
+            }
+        }
+
+        let new_config = match Config::load(path) {
+            Ok(c) => c,
+            Err(err) => {
+                error!(
+                    "Rejected reloaded config. Keeping the previous config: {}",
+                    err
+                );
+                continue;
+            }
+        };
+
+        if new_config.base_url != config.base_url
+            || new_config.api_key != config.api_key
+            || new_config.max_concurrent_monitors != config.max_concurrent_monitors
+            || new_config.control_socket != config.control_socket
+        {
+            warn!("Changes to base_url, api_key, max_concurrent_monitors or control_socket need a restart to take effect");
+        }
+
+        let sources_changed = new_config.resolve_monitor_sources().ok()
+            != config.resolve_monitor_sources().ok()
+            || new_config.resolve_alert_sources().ok() != config.resolve_alert_sources().ok()
+            || new_config.api_sync_period != config.api_sync_period
+            || new_config.api_cache_path != config.api_cache_path;
+
+        for result in [
+            session_actor_addr
+                .send(CurrentConfig {
+                    config: new_config.clone(),
+                })
+                .await,
+            configurator_addr
+                .send(CurrentConfig {
+                    config: new_config.clone(),
+                })
+                .await,
+            alerter_addr
+                .send(CurrentConfig {
+                    config: new_config.clone(),
+                })
+                .await,
+        ] {
+            match result {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => error!("Error applying reloaded config: {}", err),
+                Err(err) => error!("Error applying reloaded config: {}", err),
+            }
+        }
+
+        if sources_changed {
+            info!("Sources changed. Restarting sources");
+            // wait for the old timers to be cancelled, since the new sources may reuse their IDs
+            for source in sources.drain(..) {
+                if let Err(err) = source.send(StopSource).await {
+                    error!("Error stopping source: {}", err);
+                }
+            }
+            sources = start_sources(&new_config, &timer_addr, &configurator_addr);
+        }
+
+        info!("Reloaded config from {}", path);
+        config = new_config;
     }
 }
@@ -129,3 +129,15 @@ impl fmt::Write for MonitorStatusBuilder {
         Ok(())
     }
 }
+
+/// An HTTP monitor that runs every minute with a timeout of 10s, for tests.
+#[cfg(test)]
+pub fn test_monitor(name: &str) -> models::Monitor {
+    models::Monitor::new(
+        models::MonitorType::HTTP,
+        name.to_owned(),
+        "1m".to_owned(),
+        "10s".to_owned(),
+        models::MonitorBody::new(),
+    )
+}
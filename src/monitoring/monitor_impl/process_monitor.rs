@@ -62,21 +62,26 @@ impl MonitorSource for ProcessMonitor {
 
             writeln!(builder, "Inspecting process information");
 
-            let mut sys_info = System::new();
+            // refreshing every process is slow, so keep it off the event loop
+            let processes = actix_rt::task::spawn_blocking(|| {
+                let mut sys_info = System::new();
 
-            sys_info.refresh_processes();
+                sys_info.refresh_processes();
 
-            let processes = sys_info.get_processes();
+                sys_info
+                    .get_processes()
+                    .values()
+                    .map(|p| (p.cmd().to_vec(), p.memory()))
+                    .collect::<Vec<_>>()
+            })
+            .await?;
 
             let mut total_ram = 0usize;
             let mut total_count = 0u32;
 
-            let processes = processes.values();
-
             let mut instance_violation = false;
 
-            for p in processes {
-                let cmd = p.cmd();
+            for (cmd, memory) in processes.iter() {
                 if cmd.is_empty() {
                     continue;
                 }
@@ -104,7 +109,7 @@ impl MonitorSource for ProcessMonitor {
                 };
 
                 if is_match {
-                    let instance_ram = (p.memory() * 1024) as usize;
+                    let instance_ram = (memory * 1024) as usize;
 
                     if instance_ram > max_ram_instance {
                         writeln!(builder, "Maximum RAM for any one process must be {} bytes or less. I got {} bytes", max_ram_instance, instance_ram);
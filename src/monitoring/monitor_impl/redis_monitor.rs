@@ -1,3 +1,4 @@
+use crate::duration::monitor_timeout;
 use crate::error::Error;
 use crate::monitoring::MonitorFuture;
 use crate::monitoring::MonitorSource;
@@ -19,6 +20,7 @@ impl MonitorSource for RedisMonitor {
     fn monitor(&mut self, monitor: &models::Monitor) -> MonitorFuture {
         let monitor = monitor.clone();
         Box::pin(async {
+            let timeout = monitor_timeout(&monitor)?;
             let monitor_id = match monitor.id {
                 Some(m) => m.to_string(),
                 None => {
@@ -65,7 +67,6 @@ impl MonitorSource for RedisMonitor {
                 "Opening connection to redis on {}:{}",
                 host, port
             );
-            let mut conn = Client::open(connection_info)?;
             // authenticate if necessary
             /*const AUTH: &'static str = "AUTH";
             match (username, password) {
@@ -88,8 +89,17 @@ impl MonitorSource for RedisMonitor {
             const INFO: &'static str = "INFO";
 
             writeln!(&mut result_builder, "Loading data using INFO command");
-            // load data and parse
-            let info_dict: redis::InfoDict = redis::cmd(INFO).query(&mut conn)?;
+            // the redis client is synchronous, so keep it off the event loop. The monitor timeout
+            // also bounds the blocking calls, so a server that hangs does not hold the thread
+            let info_dict: redis::InfoDict =
+                actix_rt::task::spawn_blocking(move || -> redis::RedisResult<redis::InfoDict> {
+                    let client = Client::open(connection_info)?;
+                    let mut conn = client.get_connection_with_timeout(timeout)?;
+                    conn.set_read_timeout(Some(timeout))?;
+                    conn.set_write_timeout(Some(timeout))?;
+                    redis::cmd(INFO).query(&mut conn)
+                })
+                .await??;
 
             writeln!(
                 &mut result_builder,
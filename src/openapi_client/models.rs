@@ -484,11 +484,31 @@ pub struct Alert {
     #[serde(rename = "threshold")]
     pub threshold: isize,
 
+    /// How many OK results in a row are needed before a recovery is sent. Defaults to 1.
+    #[serde(rename = "recoveryThreshold")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_threshold: Option<isize>,
+
     #[serde(rename = "enabled")]
     pub enabled: bool,
 
     #[serde(rename = "body")]
     pub body: models::AlertBody,
+
+    /// How often the DOWN alert is sent again while the monitor stays DOWN, such as 1h.
+    #[serde(rename = "repeatInterval")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat_interval: Option<String>,
+
+    /// How long the monitor stays DOWN before this alert is sent, such as 30m.
+    #[serde(rename = "escalateAfter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub escalate_after: Option<String>,
+
+    /// How long to collect state changes for before sending them as one digest, such as 1m.
+    #[serde(rename = "groupWindow")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_window: Option<String>,
 }
 
 impl Alert {
@@ -505,8 +525,12 @@ impl Alert {
             description: None,
             monitors: monitors,
             threshold: threshold,
+            recovery_threshold: None,
             enabled: enabled,
             body: body,
+            repeat_interval: None,
+            escalate_after: None,
+            group_window: None,
         }
     }
 }
@@ -544,11 +568,31 @@ impl std::string::ToString for Alert {
         params.push("threshold".to_string());
         params.push(self.threshold.to_string());
 
+        if let Some(ref recovery_threshold) = self.recovery_threshold {
+            params.push("recoveryThreshold".to_string());
+            params.push(recovery_threshold.to_string());
+        }
+
         params.push("enabled".to_string());
         params.push(self.enabled.to_string());
 
         // Skipping body in query parameter serialization
 
+        if let Some(ref repeat_interval) = self.repeat_interval {
+            params.push("repeatInterval".to_string());
+            params.push(repeat_interval.to_string());
+        }
+
+        if let Some(ref escalate_after) = self.escalate_after {
+            params.push("escalateAfter".to_string());
+            params.push(escalate_after.to_string());
+        }
+
+        if let Some(ref group_window) = self.group_window {
+            params.push("groupWindow".to_string());
+            params.push(group_window.to_string());
+        }
+
         params.join(",").to_string()
     }
 }
@@ -568,8 +612,12 @@ impl std::str::FromStr for Alert {
             pub description: Vec<String>,
             pub monitors: Vec<Vec<models::IdString>>,
             pub threshold: Vec<isize>,
+            pub recovery_threshold: Vec<isize>,
             pub enabled: Vec<bool>,
             pub body: Vec<models::AlertBody>,
+            pub repeat_interval: Vec<String>,
+            pub escalate_after: Vec<String>,
+            pub group_window: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -612,6 +660,10 @@ impl std::str::FromStr for Alert {
                         <isize as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
+                    "recoveryThreshold" => intermediate_rep.recovery_threshold.push(
+                        <isize as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
                     "enabled" => intermediate_rep.enabled.push(
                         <bool as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?,
                     ),
@@ -619,6 +671,18 @@ impl std::str::FromStr for Alert {
                         <models::AlertBody as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
+                    "repeatInterval" => intermediate_rep.repeat_interval.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "escalateAfter" => intermediate_rep.escalate_after.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "groupWindow" => intermediate_rep.group_window.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
                     _ => {
                         return std::result::Result::Err(
                             "Unexpected key while parsing Alert".to_string(),
@@ -650,6 +714,7 @@ impl std::str::FromStr for Alert {
                 .into_iter()
                 .next()
                 .ok_or("threshold missing in Alert".to_string())?,
+            recovery_threshold: intermediate_rep.recovery_threshold.into_iter().next(),
             enabled: intermediate_rep
                 .enabled
                 .into_iter()
@@ -660,6 +725,9 @@ impl std::str::FromStr for Alert {
                 .into_iter()
                 .next()
                 .ok_or("body missing in Alert".to_string())?,
+            repeat_interval: intermediate_rep.repeat_interval.into_iter().next(),
+            escalate_after: intermediate_rep.escalate_after.into_iter().next(),
+            group_window: intermediate_rep.group_window.into_iter().next(),
         })
     }
 }
@@ -878,6 +946,101 @@ pub struct AlertBody {
     #[serde(rename = "password")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
+
+    /// Handlebars template for the text of the message, or for a webhook the request body.
+    #[serde(rename = "bodyTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
+
+    /// Key for signing webhook requests with HMAC-SHA256.
+    #[serde(rename = "secret")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+
+    /// Header that carries the webhook signature.
+    #[serde(rename = "signatureHeader")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_header: Option<String>,
+
+    /// Slack bot token for chat.postMessage.
+    #[serde(rename = "token")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// Slack channel for chat.postMessage.
+    #[serde(rename = "channel")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// PagerDuty integration key.
+    #[serde(rename = "routingKey")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing_key: Option<String>,
+
+    /// PagerDuty severity of the event.
+    #[serde(rename = "severity")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+
+    /// Opsgenie API integration key.
+    #[serde(rename = "apiKey")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// Opsgenie priority of the alert.
+    #[serde(rename = "priority")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// Opsgenie responders of the alert.
+    #[serde(rename = "responders")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responders: Option<Vec<String>>,
+
+    /// Where log alert records are written: file, syslog or journald.
+    #[serde(rename = "target")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// File or socket path of a log alert.
+    #[serde(rename = "path")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// UDP syslog server of a log alert.
+    #[serde(rename = "address")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// Rotation size of a log alert file.
+    #[serde(rename = "maxSize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<isize>,
+
+    /// Rotated files kept by a log alert.
+    #[serde(rename = "maxFiles")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<isize>,
+
+    /// The program run by an exec alert.
+    #[serde(rename = "command")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Arguments of the program run by an exec alert.
+    #[serde(rename = "args")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<String>>,
+
+    /// How long the program of an exec alert may run, such as 30s.
+    #[serde(rename = "timeout")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    /// Handlebars template for the subject or title of the message.
+    #[serde(rename = "subjectTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_template: Option<String>,
 }
 
 impl AlertBody {
@@ -892,6 +1055,25 @@ impl AlertBody {
             tls_mode: None,
             username: None,
             password: None,
+            body_template: None,
+            secret: None,
+            signature_header: None,
+            token: None,
+            channel: None,
+            routing_key: None,
+            severity: None,
+            api_key: None,
+            priority: None,
+            responders: None,
+            target: None,
+            path: None,
+            address: None,
+            max_size: None,
+            max_files: None,
+            command: None,
+            args: None,
+            timeout: None,
+            subject_template: None,
         }
     }
 }
@@ -949,6 +1131,95 @@ impl std::string::ToString for AlertBody {
             params.push(password.to_string());
         }
 
+        if let Some(ref body_template) = self.body_template {
+            params.push("bodyTemplate".to_string());
+            params.push(body_template.to_string());
+        }
+
+        if let Some(ref secret) = self.secret {
+            params.push("secret".to_string());
+            params.push(secret.to_string());
+        }
+
+        if let Some(ref signature_header) = self.signature_header {
+            params.push("signatureHeader".to_string());
+            params.push(signature_header.to_string());
+        }
+
+        if let Some(ref token) = self.token {
+            params.push("token".to_string());
+            params.push(token.to_string());
+        }
+
+        if let Some(ref channel) = self.channel {
+            params.push("channel".to_string());
+            params.push(channel.to_string());
+        }
+
+        if let Some(ref routing_key) = self.routing_key {
+            params.push("routingKey".to_string());
+            params.push(routing_key.to_string());
+        }
+
+        if let Some(ref severity) = self.severity {
+            params.push("severity".to_string());
+            params.push(severity.to_string());
+        }
+
+        if let Some(ref api_key) = self.api_key {
+            params.push("apiKey".to_string());
+            params.push(api_key.to_string());
+        }
+
+        if let Some(ref priority) = self.priority {
+            params.push("priority".to_string());
+            params.push(priority.to_string());
+        }
+
+        // Skipping responders in query parameter serialization
+
+        if let Some(ref target) = self.target {
+            params.push("target".to_string());
+            params.push(target.to_string());
+        }
+
+        if let Some(ref path) = self.path {
+            params.push("path".to_string());
+            params.push(path.to_string());
+        }
+
+        if let Some(ref address) = self.address {
+            params.push("address".to_string());
+            params.push(address.to_string());
+        }
+
+        if let Some(ref max_size) = self.max_size {
+            params.push("maxSize".to_string());
+            params.push(max_size.to_string());
+        }
+
+        if let Some(ref max_files) = self.max_files {
+            params.push("maxFiles".to_string());
+            params.push(max_files.to_string());
+        }
+
+        if let Some(ref command) = self.command {
+            params.push("command".to_string());
+            params.push(command.to_string());
+        }
+
+        // Skipping args in query parameter serialization
+
+        if let Some(ref timeout) = self.timeout {
+            params.push("timeout".to_string());
+            params.push(timeout.to_string());
+        }
+
+        if let Some(ref subject_template) = self.subject_template {
+            params.push("subjectTemplate".to_string());
+            params.push(subject_template.to_string());
+        }
+
         params.join(",").to_string()
     }
 }
@@ -972,6 +1243,25 @@ impl std::str::FromStr for AlertBody {
             pub tls_mode: Vec<models::TlsMode>,
             pub username: Vec<String>,
             pub password: Vec<String>,
+            pub body_template: Vec<String>,
+            pub secret: Vec<String>,
+            pub signature_header: Vec<String>,
+            pub token: Vec<String>,
+            pub channel: Vec<String>,
+            pub routing_key: Vec<String>,
+            pub severity: Vec<String>,
+            pub api_key: Vec<String>,
+            pub priority: Vec<String>,
+            pub responders: Vec<Vec<String>>,
+            pub target: Vec<String>,
+            pub path: Vec<String>,
+            pub address: Vec<String>,
+            pub max_size: Vec<isize>,
+            pub max_files: Vec<isize>,
+            pub command: Vec<String>,
+            pub args: Vec<Vec<String>>,
+            pub timeout: Vec<String>,
+            pub subject_template: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -1031,6 +1321,86 @@ impl std::str::FromStr for AlertBody {
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
+                    "bodyTemplate" => intermediate_rep.body_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "secret" => intermediate_rep.secret.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "signatureHeader" => intermediate_rep.signature_header.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "token" => intermediate_rep.token.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "channel" => intermediate_rep.channel.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "routingKey" => intermediate_rep.routing_key.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "severity" => intermediate_rep.severity.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "apiKey" => intermediate_rep.api_key.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "priority" => intermediate_rep.priority.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "responders" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in AlertBody"
+                                .to_string(),
+                        )
+                    }
+                    "target" => intermediate_rep.target.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "path" => intermediate_rep.path.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "address" => intermediate_rep.address.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "maxSize" => intermediate_rep.max_size.push(
+                        <isize as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "maxFiles" => intermediate_rep.max_files.push(
+                        <isize as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "command" => intermediate_rep.command.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "args" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in AlertBody"
+                                .to_string(),
+                        )
+                    }
+                    "timeout" => intermediate_rep.timeout.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "subjectTemplate" => intermediate_rep.subject_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
                     _ => {
                         return std::result::Result::Err(
                             "Unexpected key while parsing AlertBody".to_string(),
@@ -1054,6 +1424,25 @@ impl std::str::FromStr for AlertBody {
             tls_mode: intermediate_rep.tls_mode.into_iter().next(),
             username: intermediate_rep.username.into_iter().next(),
             password: intermediate_rep.password.into_iter().next(),
+            body_template: intermediate_rep.body_template.into_iter().next(),
+            secret: intermediate_rep.secret.into_iter().next(),
+            signature_header: intermediate_rep.signature_header.into_iter().next(),
+            token: intermediate_rep.token.into_iter().next(),
+            channel: intermediate_rep.channel.into_iter().next(),
+            routing_key: intermediate_rep.routing_key.into_iter().next(),
+            severity: intermediate_rep.severity.into_iter().next(),
+            api_key: intermediate_rep.api_key.into_iter().next(),
+            priority: intermediate_rep.priority.into_iter().next(),
+            responders: intermediate_rep.responders.into_iter().next(),
+            target: intermediate_rep.target.into_iter().next(),
+            path: intermediate_rep.path.into_iter().next(),
+            address: intermediate_rep.address.into_iter().next(),
+            max_size: intermediate_rep.max_size.into_iter().next(),
+            max_files: intermediate_rep.max_files.into_iter().next(),
+            command: intermediate_rep.command.into_iter().next(),
+            args: intermediate_rep.args.into_iter().next(),
+            timeout: intermediate_rep.timeout.into_iter().next(),
+            subject_template: intermediate_rep.subject_template.into_iter().next(),
         })
     }
 }
@@ -1613,6 +2002,16 @@ pub struct EmailAlertBody {
     #[serde(rename = "password")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
+
+    /// Handlebars template for the subject or title of the message.
+    #[serde(rename = "subjectTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_template: Option<String>,
+
+    /// Handlebars template for the text of the message.
+    #[serde(rename = "bodyTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
 }
 
 impl EmailAlertBody {
@@ -1625,6 +2024,8 @@ impl EmailAlertBody {
             tls_mode: None,
             username: None,
             password: None,
+            subject_template: None,
+            body_template: None,
         }
     }
 }
@@ -1675,6 +2076,16 @@ impl std::string::ToString for EmailAlertBody {
             params.push(password.to_string());
         }
 
+        if let Some(ref subject_template) = self.subject_template {
+            params.push("subjectTemplate".to_string());
+            params.push(subject_template.to_string());
+        }
+
+        if let Some(ref body_template) = self.body_template {
+            params.push("bodyTemplate".to_string());
+            params.push(body_template.to_string());
+        }
+
         params.join(",").to_string()
     }
 }
@@ -1696,6 +2107,8 @@ impl std::str::FromStr for EmailAlertBody {
             pub tls_mode: Vec<models::TlsMode>,
             pub username: Vec<String>,
             pub password: Vec<String>,
+            pub subject_template: Vec<String>,
+            pub body_template: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -1745,6 +2158,14 @@ impl std::str::FromStr for EmailAlertBody {
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
+                    "subjectTemplate" => intermediate_rep.subject_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "bodyTemplate" => intermediate_rep.body_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
                     _ => {
                         return std::result::Result::Err(
                             "Unexpected key while parsing EmailAlertBody".to_string(),
@@ -1766,6 +2187,8 @@ impl std::str::FromStr for EmailAlertBody {
             tls_mode: intermediate_rep.tls_mode.into_iter().next(),
             username: intermediate_rep.username.into_iter().next(),
             password: intermediate_rep.password.into_iter().next(),
+            subject_template: intermediate_rep.subject_template.into_iter().next(),
+            body_template: intermediate_rep.body_template.into_iter().next(),
         })
     }
 }
@@ -3363,6 +3786,16 @@ pub struct Monitor {
 
     #[serde(rename = "body")]
     pub body: models::MonitorBody,
+
+    /// Labels that silences can match on.
+    #[serde(rename = "tags")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// Names or IDs of the monitors this one depends on. Its alerts are held back while any of them is DOWN.
+    #[serde(rename = "dependsOn")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>,
 }
 
 impl Monitor {
@@ -3382,6 +3815,8 @@ impl Monitor {
             period: period,
             timeout: timeout,
             body: body,
+            tags: None,
+            depends_on: None,
         }
     }
 }
@@ -3419,6 +3854,10 @@ impl std::string::ToString for Monitor {
 
         // Skipping body in query parameter serialization
 
+        // Skipping tags in query parameter serialization
+
+        // Skipping dependsOn in query parameter serialization
+
         params.join(",").to_string()
     }
 }
@@ -3441,6 +3880,8 @@ impl std::str::FromStr for Monitor {
             pub period: Vec<String>,
             pub timeout: Vec<String>,
             pub body: Vec<models::MonitorBody>,
+            pub tags: Vec<Vec<String>>,
+            pub depends_on: Vec<Vec<String>>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -3492,6 +3933,18 @@ impl std::str::FromStr for Monitor {
                         <models::MonitorBody as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
+                    "tags" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in Monitor"
+                                .to_string(),
+                        )
+                    }
+                    "dependsOn" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in Monitor"
+                                .to_string(),
+                        )
+                    }
                     _ => {
                         return std::result::Result::Err(
                             "Unexpected key while parsing Monitor".to_string(),
@@ -3538,6 +3991,8 @@ impl std::str::FromStr for Monitor {
                 .into_iter()
                 .next()
                 .ok_or("body missing in Monitor".to_string())?,
+            tags: intermediate_rep.tags.into_iter().next(),
+            depends_on: intermediate_rep.depends_on.into_iter().next(),
         })
     }
 }
@@ -3719,210 +4174,259 @@ impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderVal
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct MonitorBody {
-    #[serde(rename = "url")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub url: Option<String>,
-
-    #[serde(rename = "method")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub method: Option<String>,
+pub struct ExecAlertBody {
+    /// The program to run. It is run directly, not through a shell.
+    #[serde(rename = "command")]
+    pub command: String,
 
-    #[serde(rename = "headers")]
+    /// Arguments passed to the command.
+    #[serde(rename = "args")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub headers: Option<Vec<models::HttpHeader>>,
+    pub args: Option<Vec<String>>,
 
-    #[serde(rename = "body")]
+    /// How long the command may run before it is killed, such as 30s. Defaults to 30s.
+    #[serde(rename = "timeout")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub body: Option<String>,
+    pub timeout: Option<String>,
+}
 
-    /// The name of the executable process to be monitored.
-    #[serde(rename = "executable")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub executable: Option<String>,
+impl ExecAlertBody {
+    pub fn new(command: String) -> ExecAlertBody {
+        ExecAlertBody {
+            command: command,
+            args: None,
+            timeout: None,
+        }
+    }
+}
 
-    /// If true, the process(s) will be located by the full path of the executable e.g. /usr/bin/node
-    #[serde(rename = "isPathAbsolute")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub is_path_absolute: Option<bool>,
+/// Converts the ExecAlertBody value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for ExecAlertBody {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
 
-    /// The minimum number of processes that match the executable.
-    #[serde(rename = "minimumCount")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub minimum_count: Option<isize>,
+        params.push("command".to_string());
+        params.push(self.command.to_string());
 
-    /// The maximum number of processes that match the executable.
-    #[serde(rename = "maximumCount")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum_count: Option<isize>,
+        // Skipping args in query parameter serialization
 
-    #[serde(rename = "maximumRamIndividual")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum_ram_individual: Option<String>,
+        if let Some(ref timeout) = self.timeout {
+            params.push("timeout".to_string());
+            params.push(timeout.to_string());
+        }
 
-    #[serde(rename = "maximumRamTotal")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub maximum_ram_total: Option<String>,
+        params.join(",").to_string()
+    }
+}
 
-    #[serde(rename = "hostname")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub hostname: Option<String>,
+/// Converts Query Parameters representation (style=form, explode=false) to a ExecAlertBody value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for ExecAlertBody {
+    type Err = String;
 
-    #[serde(rename = "port")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub port: Option<u16>,
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub command: Vec<String>,
+            pub args: Vec<Vec<String>>,
+            pub timeout: Vec<String>,
+        }
 
-    #[serde(rename = "db")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub db: Option<isize>,
+        let mut intermediate_rep = IntermediateRep::default();
 
-    #[serde(rename = "username")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub username: Option<String>,
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
 
-    #[serde(rename = "password")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub password: Option<String>,
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing ExecAlertBody".to_string(),
+                    )
+                }
+            };
 
-    #[serde(rename = "constraints")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub constraints: Option<Vec<models::FieldConstraint>>,
-}
+            if let Some(key) = key_result {
+                match key {
+                    "command" => intermediate_rep.command.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "args" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in ExecAlertBody"
+                                .to_string(),
+                        )
+                    }
+                    "timeout" => intermediate_rep.timeout.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing ExecAlertBody".to_string(),
+                        )
+                    }
+                }
+            }
 
-impl MonitorBody {
-    pub fn new() -> MonitorBody {
-        MonitorBody {
-            url: None,
-            method: None,
-            headers: None,
-            body: None,
-            executable: None,
-            is_path_absolute: Some(false),
-            minimum_count: None,
-            maximum_count: None,
-            maximum_ram_individual: None,
-            maximum_ram_total: None,
-            hostname: None,
-            port: None,
-            db: None,
-            username: None,
-            password: None,
-            constraints: None,
+            // Get the next key
+            key_result = string_iter.next();
         }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(ExecAlertBody {
+            command: intermediate_rep
+                .command
+                .into_iter()
+                .next()
+                .ok_or("command missing in ExecAlertBody".to_string())?,
+            args: intermediate_rep.args.into_iter().next(),
+            timeout: intermediate_rep.timeout.into_iter().next(),
+        })
     }
 }
 
-/// Converts the MonitorBody value to the Query Parameters representation (style=form, explode=false)
-/// specified in https://swagger.io/docs/specification/serialization/
-/// Should be implemented in a serde serializer
-impl std::string::ToString for MonitorBody {
-    fn to_string(&self) -> String {
-        let mut params: Vec<String> = vec![];
+// Methods for converting between header::IntoHeaderValue<ExecAlertBody> and hyper::header::HeaderValue
 
-        if let Some(ref url) = self.url {
-            params.push("url".to_string());
-            params.push(url.to_string());
-        }
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<ExecAlertBody>> for hyper::header::HeaderValue {
+    type Error = String;
 
-        if let Some(ref method) = self.method {
-            params.push("method".to_string());
-            params.push(method.to_string());
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<ExecAlertBody>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for ExecAlertBody - value: {} is invalid {}",
+                hdr_value, e
+            )),
         }
+    }
+}
 
-        // Skipping headers in query parameter serialization
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<ExecAlertBody> {
+    type Error = String;
 
-        if let Some(ref body) = self.body {
-            params.push("body".to_string());
-            params.push(body.to_string());
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <ExecAlertBody as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into ExecAlertBody - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
         }
+    }
+}
 
-        if let Some(ref executable) = self.executable {
-            params.push("executable".to_string());
-            params.push(executable.to_string());
-        }
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct LogAlertBody {
+    /// Where records are written: file, syslog or journald.
+    #[serde(rename = "target")]
+    pub target: String,
 
-        if let Some(ref is_path_absolute) = self.is_path_absolute {
-            params.push("isPathAbsolute".to_string());
-            params.push(is_path_absolute.to_string());
-        }
+    /// The file to write to, or the socket of syslog or journald. Defaults to /dev/log for syslog.
+    #[serde(rename = "path")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 
-        if let Some(ref minimum_count) = self.minimum_count {
-            params.push("minimumCount".to_string());
-            params.push(minimum_count.to_string());
-        }
+    /// host:port of a syslog server that listens on UDP, instead of the local socket.
+    #[serde(rename = "address")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
 
-        if let Some(ref maximum_count) = self.maximum_count {
-            params.push("maximumCount".to_string());
-            params.push(maximum_count.to_string());
-        }
+    /// Size in bytes at which the file is rotated. Defaults to 10 MB.
+    #[serde(rename = "maxSize")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<isize>,
 
-        if let Some(ref maximum_ram_individual) = self.maximum_ram_individual {
-            params.push("maximumRamIndividual".to_string());
-            params.push(maximum_ram_individual.to_string());
-        }
+    /// How many rotated files are kept. Defaults to 5.
+    #[serde(rename = "maxFiles")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<isize>,
+}
 
-        if let Some(ref maximum_ram_total) = self.maximum_ram_total {
-            params.push("maximumRamTotal".to_string());
-            params.push(maximum_ram_total.to_string());
+impl LogAlertBody {
+    pub fn new(target: String) -> LogAlertBody {
+        LogAlertBody {
+            target: target,
+            path: None,
+            address: None,
+            max_size: None,
+            max_files: None,
         }
+    }
+}
 
-        if let Some(ref hostname) = self.hostname {
-            params.push("hostname".to_string());
-            params.push(hostname.to_string());
-        }
+/// Converts the LogAlertBody value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for LogAlertBody {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
 
-        if let Some(ref port) = self.port {
-            params.push("port".to_string());
-            params.push(port.to_string());
-        }
+        params.push("target".to_string());
+        params.push(self.target.to_string());
 
-        if let Some(ref db) = self.db {
-            params.push("db".to_string());
-            params.push(db.to_string());
+        if let Some(ref path) = self.path {
+            params.push("path".to_string());
+            params.push(path.to_string());
         }
 
-        if let Some(ref username) = self.username {
-            params.push("username".to_string());
-            params.push(username.to_string());
+        if let Some(ref address) = self.address {
+            params.push("address".to_string());
+            params.push(address.to_string());
         }
 
-        if let Some(ref password) = self.password {
-            params.push("password".to_string());
-            params.push(password.to_string());
+        if let Some(ref max_size) = self.max_size {
+            params.push("maxSize".to_string());
+            params.push(max_size.to_string());
         }
 
-        // Skipping constraints in query parameter serialization
+        if let Some(ref max_files) = self.max_files {
+            params.push("maxFiles".to_string());
+            params.push(max_files.to_string());
+        }
 
         params.join(",").to_string()
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a MonitorBody value
+/// Converts Query Parameters representation (style=form, explode=false) to a LogAlertBody value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for MonitorBody {
+impl std::str::FromStr for LogAlertBody {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         #[derive(Default)]
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
-            pub url: Vec<String>,
-            pub method: Vec<String>,
-            pub headers: Vec<Vec<models::HttpHeader>>,
-            pub body: Vec<String>,
-            pub executable: Vec<String>,
-            pub is_path_absolute: Vec<bool>,
-            pub minimum_count: Vec<isize>,
-            pub maximum_count: Vec<isize>,
-            pub maximum_ram_individual: Vec<String>,
-            pub maximum_ram_total: Vec<String>,
-            pub hostname: Vec<String>,
-            pub port: Vec<u16>,
-            pub db: Vec<isize>,
-            pub username: Vec<String>,
-            pub password: Vec<String>,
-            pub constraints: Vec<Vec<models::FieldConstraint>>,
+            pub target: Vec<String>,
+            pub path: Vec<String>,
+            pub address: Vec<String>,
+            pub max_size: Vec<isize>,
+            pub max_files: Vec<isize>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -3936,82 +4440,36 @@ impl std::str::FromStr for MonitorBody {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing MonitorBody".to_string(),
+                        "Missing value while parsing LogAlertBody".to_string(),
                     )
                 }
             };
 
             if let Some(key) = key_result {
                 match key {
-                    "url" => intermediate_rep.url.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "method" => intermediate_rep.method.push(
+                    "target" => intermediate_rep.target.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "headers" => {
-                        return std::result::Result::Err(
-                            "Parsing a container in this style is not supported in MonitorBody"
-                                .to_string(),
-                        )
-                    }
-                    "body" => intermediate_rep.body.push(
+                    "path" => intermediate_rep.path.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "executable" => intermediate_rep.executable.push(
+                    "address" => intermediate_rep.address.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "isPathAbsolute" => intermediate_rep.is_path_absolute.push(
-                        <bool as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?,
-                    ),
-                    "minimumCount" => intermediate_rep.minimum_count.push(
-                        <isize as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "maximumCount" => intermediate_rep.maximum_count.push(
+                    "maxSize" => intermediate_rep.max_size.push(
                         <isize as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "maximumRamIndividual" => intermediate_rep.maximum_ram_individual.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "maximumRamTotal" => intermediate_rep.maximum_ram_total.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "hostname" => intermediate_rep.hostname.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "port" => intermediate_rep.port.push(
-                        <u16 as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?,
-                    ),
-                    "db" => intermediate_rep.db.push(
+                    "maxFiles" => intermediate_rep.max_files.push(
                         <isize as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "username" => intermediate_rep.username.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "password" => intermediate_rep.password.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "constraints" => {
-                        return std::result::Result::Err(
-                            "Parsing a container in this style is not supported in MonitorBody"
-                                .to_string(),
-                        )
-                    }
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing MonitorBody".to_string(),
+                            "Unexpected key while parsing LogAlertBody".to_string(),
                         )
                     }
                 }
@@ -4022,9 +4480,373 @@ impl std::str::FromStr for MonitorBody {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(MonitorBody {
-            url: intermediate_rep.url.into_iter().next(),
-            method: intermediate_rep.method.into_iter().next(),
+        std::result::Result::Ok(LogAlertBody {
+            target: intermediate_rep
+                .target
+                .into_iter()
+                .next()
+                .ok_or("target missing in LogAlertBody".to_string())?,
+            path: intermediate_rep.path.into_iter().next(),
+            address: intermediate_rep.address.into_iter().next(),
+            max_size: intermediate_rep.max_size.into_iter().next(),
+            max_files: intermediate_rep.max_files.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<LogAlertBody> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<LogAlertBody>> for hyper::header::HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<LogAlertBody>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for LogAlertBody - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<LogAlertBody> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <LogAlertBody as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into LogAlertBody - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct MonitorBody {
+    #[serde(rename = "url")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    #[serde(rename = "method")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+
+    #[serde(rename = "headers")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<models::HttpHeader>>,
+
+    #[serde(rename = "body")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+
+    /// The name of the executable process to be monitored.
+    #[serde(rename = "executable")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable: Option<String>,
+
+    /// If true, the process(s) will be located by the full path of the executable e.g. /usr/bin/node
+    #[serde(rename = "isPathAbsolute")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_path_absolute: Option<bool>,
+
+    /// The minimum number of processes that match the executable.
+    #[serde(rename = "minimumCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_count: Option<isize>,
+
+    /// The maximum number of processes that match the executable.
+    #[serde(rename = "maximumCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_count: Option<isize>,
+
+    #[serde(rename = "maximumRamIndividual")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_ram_individual: Option<String>,
+
+    #[serde(rename = "maximumRamTotal")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_ram_total: Option<String>,
+
+    #[serde(rename = "hostname")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+
+    #[serde(rename = "port")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    #[serde(rename = "db")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db: Option<isize>,
+
+    #[serde(rename = "username")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    #[serde(rename = "password")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    #[serde(rename = "constraints")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Vec<models::FieldConstraint>>,
+}
+
+impl MonitorBody {
+    pub fn new() -> MonitorBody {
+        MonitorBody {
+            url: None,
+            method: None,
+            headers: None,
+            body: None,
+            executable: None,
+            is_path_absolute: Some(false),
+            minimum_count: None,
+            maximum_count: None,
+            maximum_ram_individual: None,
+            maximum_ram_total: None,
+            hostname: None,
+            port: None,
+            db: None,
+            username: None,
+            password: None,
+            constraints: None,
+        }
+    }
+}
+
+/// Converts the MonitorBody value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for MonitorBody {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+
+        if let Some(ref url) = self.url {
+            params.push("url".to_string());
+            params.push(url.to_string());
+        }
+
+        if let Some(ref method) = self.method {
+            params.push("method".to_string());
+            params.push(method.to_string());
+        }
+
+        // Skipping headers in query parameter serialization
+
+        if let Some(ref body) = self.body {
+            params.push("body".to_string());
+            params.push(body.to_string());
+        }
+
+        if let Some(ref executable) = self.executable {
+            params.push("executable".to_string());
+            params.push(executable.to_string());
+        }
+
+        if let Some(ref is_path_absolute) = self.is_path_absolute {
+            params.push("isPathAbsolute".to_string());
+            params.push(is_path_absolute.to_string());
+        }
+
+        if let Some(ref minimum_count) = self.minimum_count {
+            params.push("minimumCount".to_string());
+            params.push(minimum_count.to_string());
+        }
+
+        if let Some(ref maximum_count) = self.maximum_count {
+            params.push("maximumCount".to_string());
+            params.push(maximum_count.to_string());
+        }
+
+        if let Some(ref maximum_ram_individual) = self.maximum_ram_individual {
+            params.push("maximumRamIndividual".to_string());
+            params.push(maximum_ram_individual.to_string());
+        }
+
+        if let Some(ref maximum_ram_total) = self.maximum_ram_total {
+            params.push("maximumRamTotal".to_string());
+            params.push(maximum_ram_total.to_string());
+        }
+
+        if let Some(ref hostname) = self.hostname {
+            params.push("hostname".to_string());
+            params.push(hostname.to_string());
+        }
+
+        if let Some(ref port) = self.port {
+            params.push("port".to_string());
+            params.push(port.to_string());
+        }
+
+        if let Some(ref db) = self.db {
+            params.push("db".to_string());
+            params.push(db.to_string());
+        }
+
+        if let Some(ref username) = self.username {
+            params.push("username".to_string());
+            params.push(username.to_string());
+        }
+
+        if let Some(ref password) = self.password {
+            params.push("password".to_string());
+            params.push(password.to_string());
+        }
+
+        // Skipping constraints in query parameter serialization
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a MonitorBody value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for MonitorBody {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub url: Vec<String>,
+            pub method: Vec<String>,
+            pub headers: Vec<Vec<models::HttpHeader>>,
+            pub body: Vec<String>,
+            pub executable: Vec<String>,
+            pub is_path_absolute: Vec<bool>,
+            pub minimum_count: Vec<isize>,
+            pub maximum_count: Vec<isize>,
+            pub maximum_ram_individual: Vec<String>,
+            pub maximum_ram_total: Vec<String>,
+            pub hostname: Vec<String>,
+            pub port: Vec<u16>,
+            pub db: Vec<isize>,
+            pub username: Vec<String>,
+            pub password: Vec<String>,
+            pub constraints: Vec<Vec<models::FieldConstraint>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing MonitorBody".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "url" => intermediate_rep.url.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "method" => intermediate_rep.method.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "headers" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in MonitorBody"
+                                .to_string(),
+                        )
+                    }
+                    "body" => intermediate_rep.body.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "executable" => intermediate_rep.executable.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "isPathAbsolute" => intermediate_rep.is_path_absolute.push(
+                        <bool as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?,
+                    ),
+                    "minimumCount" => intermediate_rep.minimum_count.push(
+                        <isize as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "maximumCount" => intermediate_rep.maximum_count.push(
+                        <isize as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "maximumRamIndividual" => intermediate_rep.maximum_ram_individual.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "maximumRamTotal" => intermediate_rep.maximum_ram_total.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "hostname" => intermediate_rep.hostname.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "port" => intermediate_rep.port.push(
+                        <u16 as std::str::FromStr>::from_str(val).map_err(|x| format!("{}", x))?,
+                    ),
+                    "db" => intermediate_rep.db.push(
+                        <isize as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "username" => intermediate_rep.username.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "password" => intermediate_rep.password.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "constraints" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in MonitorBody"
+                                .to_string(),
+                        )
+                    }
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing MonitorBody".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(MonitorBody {
+            url: intermediate_rep.url.into_iter().next(),
+            method: intermediate_rep.method.into_iter().next(),
             headers: intermediate_rep.headers.into_iter().next(),
             body: intermediate_rep.body.into_iter().next(),
             executable: intermediate_rep.executable.into_iter().next(),
@@ -4043,20 +4865,328 @@ impl std::str::FromStr for MonitorBody {
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<MonitorBody> and hyper::header::HeaderValue
+// Methods for converting between header::IntoHeaderValue<MonitorBody> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<MonitorBody>> for hyper::header::HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<MonitorBody>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for MonitorBody - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<MonitorBody> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <MonitorBody as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into MonitorBody - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct MonitorStatus {
+    #[serde(rename = "statusId")]
+    pub status_id: String,
+
+    #[serde(rename = "monitorType")]
+    pub monitor_type: models::MonitorType,
+
+    #[serde(rename = "monitorName")]
+    pub monitor_name: String,
+
+    #[serde(rename = "status")]
+    pub status: models::MonitorStatusIndicator,
+
+    /// UTC UNIX timestamp in with fractional offset.
+    #[serde(rename = "timestamp")]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+
+    /// UTC UNIX timestamp in with fractional offset.
+    #[serde(rename = "expiresAt")]
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+
+    #[serde(rename = "expectedResult")]
+    pub expected_result: String,
+
+    #[serde(rename = "actualResult")]
+    pub actual_result: String,
+
+    #[serde(rename = "description")]
+    pub description: String,
+
+    #[serde(rename = "session")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<models::Session>,
+
+    #[serde(rename = "log")]
+    pub log: Vec<models::MonitorStatusLogEntry>,
+}
+
+impl MonitorStatus {
+    pub fn new(
+        status_id: String,
+        monitor_type: models::MonitorType,
+        monitor_name: String,
+        status: models::MonitorStatusIndicator,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        expected_result: String,
+        actual_result: String,
+        description: String,
+        log: Vec<models::MonitorStatusLogEntry>,
+    ) -> MonitorStatus {
+        MonitorStatus {
+            status_id: status_id,
+            monitor_type: monitor_type,
+            monitor_name: monitor_name,
+            status: status,
+            timestamp: timestamp,
+            expires_at: expires_at,
+            expected_result: expected_result,
+            actual_result: actual_result,
+            description: description,
+            session: None,
+            log: log,
+        }
+    }
+}
+
+/// Converts the MonitorStatus value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for MonitorStatus {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+
+        params.push("statusId".to_string());
+        params.push(self.status_id.to_string());
+
+        // Skipping monitorType in query parameter serialization
+
+        params.push("monitorName".to_string());
+        params.push(self.monitor_name.to_string());
+
+        // Skipping status in query parameter serialization
+
+        // Skipping timestamp in query parameter serialization
+
+        // Skipping expiresAt in query parameter serialization
+
+        params.push("expectedResult".to_string());
+        params.push(self.expected_result.to_string());
+
+        params.push("actualResult".to_string());
+        params.push(self.actual_result.to_string());
+
+        params.push("description".to_string());
+        params.push(self.description.to_string());
+
+        // Skipping session in query parameter serialization
+
+        // Skipping log in query parameter serialization
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatus value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for MonitorStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub status_id: Vec<String>,
+            pub monitor_type: Vec<models::MonitorType>,
+            pub monitor_name: Vec<String>,
+            pub status: Vec<models::MonitorStatusIndicator>,
+            pub timestamp: Vec<chrono::DateTime<chrono::Utc>>,
+            pub expires_at: Vec<chrono::DateTime<chrono::Utc>>,
+            pub expected_result: Vec<String>,
+            pub actual_result: Vec<String>,
+            pub description: Vec<String>,
+            pub session: Vec<models::Session>,
+            pub log: Vec<Vec<models::MonitorStatusLogEntry>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing MonitorStatus".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "statusId" => intermediate_rep.status_id.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "monitorType" => intermediate_rep.monitor_type.push(
+                        <models::MonitorType as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "monitorName" => intermediate_rep.monitor_name.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "status" => intermediate_rep.status.push(
+                        <models::MonitorStatusIndicator as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "timestamp" => intermediate_rep.timestamp.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "expiresAt" => intermediate_rep.expires_at.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "expectedResult" => intermediate_rep.expected_result.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "actualResult" => intermediate_rep.actual_result.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "description" => intermediate_rep.description.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "session" => intermediate_rep.session.push(
+                        <models::Session as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "log" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in MonitorStatus"
+                                .to_string(),
+                        )
+                    }
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing MonitorStatus".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(MonitorStatus {
+            status_id: intermediate_rep
+                .status_id
+                .into_iter()
+                .next()
+                .ok_or("statusId missing in MonitorStatus".to_string())?,
+            monitor_type: intermediate_rep
+                .monitor_type
+                .into_iter()
+                .next()
+                .ok_or("monitorType missing in MonitorStatus".to_string())?,
+            monitor_name: intermediate_rep
+                .monitor_name
+                .into_iter()
+                .next()
+                .ok_or("monitorName missing in MonitorStatus".to_string())?,
+            status: intermediate_rep
+                .status
+                .into_iter()
+                .next()
+                .ok_or("status missing in MonitorStatus".to_string())?,
+            timestamp: intermediate_rep
+                .timestamp
+                .into_iter()
+                .next()
+                .ok_or("timestamp missing in MonitorStatus".to_string())?,
+            expires_at: intermediate_rep
+                .expires_at
+                .into_iter()
+                .next()
+                .ok_or("expiresAt missing in MonitorStatus".to_string())?,
+            expected_result: intermediate_rep
+                .expected_result
+                .into_iter()
+                .next()
+                .ok_or("expectedResult missing in MonitorStatus".to_string())?,
+            actual_result: intermediate_rep
+                .actual_result
+                .into_iter()
+                .next()
+                .ok_or("actualResult missing in MonitorStatus".to_string())?,
+            description: intermediate_rep
+                .description
+                .into_iter()
+                .next()
+                .ok_or("description missing in MonitorStatus".to_string())?,
+            session: intermediate_rep.session.into_iter().next(),
+            log: intermediate_rep
+                .log
+                .into_iter()
+                .next()
+                .ok_or("log missing in MonitorStatus".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<MonitorStatus> and hyper::header::HeaderValue
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<header::IntoHeaderValue<MonitorBody>> for hyper::header::HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatus>> for hyper::header::HeaderValue {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<MonitorBody>,
+        hdr_value: header::IntoHeaderValue<MonitorStatus>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match hyper::header::HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for MonitorBody - value: {} is invalid {}",
+                "Invalid header value for MonitorStatus - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -4064,18 +5194,150 @@ impl std::convert::TryFrom<header::IntoHeaderValue<MonitorBody>> for hyper::head
 }
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<MonitorBody> {
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<MonitorStatus> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <MonitorStatus as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into MonitorStatus - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct MonitorStatusArray {
+    #[serde(rename = "statuses")]
+    pub statuses: Vec<models::MonitorStatus>,
+}
+
+impl MonitorStatusArray {
+    pub fn new(statuses: Vec<models::MonitorStatus>) -> MonitorStatusArray {
+        MonitorStatusArray { statuses: statuses }
+    }
+}
+
+/// Converts the MonitorStatusArray value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for MonitorStatusArray {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+        // Skipping statuses in query parameter serialization
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatusArray value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for MonitorStatusArray {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub statuses: Vec<Vec<models::MonitorStatus>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing MonitorStatusArray".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "statuses" => return std::result::Result::Err(
+                        "Parsing a container in this style is not supported in MonitorStatusArray"
+                            .to_string(),
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing MonitorStatusArray".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(MonitorStatusArray {
+            statuses: intermediate_rep
+                .statuses
+                .into_iter()
+                .next()
+                .ok_or("statuses missing in MonitorStatusArray".to_string())?,
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<MonitorStatusArray> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusArray>>
+    for hyper::header::HeaderValue
+{
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<MonitorStatusArray>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for MonitorStatusArray - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue>
+    for header::IntoHeaderValue<MonitorStatusArray>
+{
     type Error = String;
 
     fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <MonitorBody as std::str::FromStr>::from_str(value) {
+                match <MonitorStatusArray as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        "Unable to convert header value '{}' into MonitorBody - {}",
+                        "Unable to convert header value '{}' into MonitorStatusArray - {}",
                         value, err
                     )),
                 }
@@ -4090,132 +5352,40 @@ impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderVal
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct MonitorStatus {
-    #[serde(rename = "statusId")]
-    pub status_id: String,
-
-    #[serde(rename = "monitorType")]
-    pub monitor_type: models::MonitorType,
-
-    #[serde(rename = "monitorName")]
-    pub monitor_name: String,
-
+pub struct MonitorStatusContainer {
     #[serde(rename = "status")]
-    pub status: models::MonitorStatusIndicator,
-
-    /// UTC UNIX timestamp in with fractional offset.
-    #[serde(rename = "timestamp")]
-    pub timestamp: chrono::DateTime<chrono::Utc>,
-
-    /// UTC UNIX timestamp in with fractional offset.
-    #[serde(rename = "expiresAt")]
-    pub expires_at: chrono::DateTime<chrono::Utc>,
-
-    #[serde(rename = "expectedResult")]
-    pub expected_result: String,
-
-    #[serde(rename = "actualResult")]
-    pub actual_result: String,
-
-    #[serde(rename = "description")]
-    pub description: String,
-
-    #[serde(rename = "session")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub session: Option<models::Session>,
-
-    #[serde(rename = "log")]
-    pub log: Vec<models::MonitorStatusLogEntry>,
+    pub status: models::MonitorStatus,
 }
 
-impl MonitorStatus {
-    pub fn new(
-        status_id: String,
-        monitor_type: models::MonitorType,
-        monitor_name: String,
-        status: models::MonitorStatusIndicator,
-        timestamp: chrono::DateTime<chrono::Utc>,
-        expires_at: chrono::DateTime<chrono::Utc>,
-        expected_result: String,
-        actual_result: String,
-        description: String,
-        log: Vec<models::MonitorStatusLogEntry>,
-    ) -> MonitorStatus {
-        MonitorStatus {
-            status_id: status_id,
-            monitor_type: monitor_type,
-            monitor_name: monitor_name,
-            status: status,
-            timestamp: timestamp,
-            expires_at: expires_at,
-            expected_result: expected_result,
-            actual_result: actual_result,
-            description: description,
-            session: None,
-            log: log,
-        }
+impl MonitorStatusContainer {
+    pub fn new(status: models::MonitorStatus) -> MonitorStatusContainer {
+        MonitorStatusContainer { status: status }
     }
 }
 
-/// Converts the MonitorStatus value to the Query Parameters representation (style=form, explode=false)
+/// Converts the MonitorStatusContainer value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::string::ToString for MonitorStatus {
+impl std::string::ToString for MonitorStatusContainer {
     fn to_string(&self) -> String {
         let mut params: Vec<String> = vec![];
-
-        params.push("statusId".to_string());
-        params.push(self.status_id.to_string());
-
-        // Skipping monitorType in query parameter serialization
-
-        params.push("monitorName".to_string());
-        params.push(self.monitor_name.to_string());
-
         // Skipping status in query parameter serialization
 
-        // Skipping timestamp in query parameter serialization
-
-        // Skipping expiresAt in query parameter serialization
-
-        params.push("expectedResult".to_string());
-        params.push(self.expected_result.to_string());
-
-        params.push("actualResult".to_string());
-        params.push(self.actual_result.to_string());
-
-        params.push("description".to_string());
-        params.push(self.description.to_string());
-
-        // Skipping session in query parameter serialization
-
-        // Skipping log in query parameter serialization
-
         params.join(",").to_string()
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatus value
+/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatusContainer value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for MonitorStatus {
+impl std::str::FromStr for MonitorStatusContainer {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         #[derive(Default)]
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
-            pub status_id: Vec<String>,
-            pub monitor_type: Vec<models::MonitorType>,
-            pub monitor_name: Vec<String>,
-            pub status: Vec<models::MonitorStatusIndicator>,
-            pub timestamp: Vec<chrono::DateTime<chrono::Utc>>,
-            pub expires_at: Vec<chrono::DateTime<chrono::Utc>>,
-            pub expected_result: Vec<String>,
-            pub actual_result: Vec<String>,
-            pub description: Vec<String>,
-            pub session: Vec<models::Session>,
-            pub log: Vec<Vec<models::MonitorStatusLogEntry>>,
+            pub status: Vec<models::MonitorStatus>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -4229,62 +5399,20 @@ impl std::str::FromStr for MonitorStatus {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing MonitorStatus".to_string(),
+                        "Missing value while parsing MonitorStatusContainer".to_string(),
                     )
                 }
             };
 
             if let Some(key) = key_result {
                 match key {
-                    "statusId" => intermediate_rep.status_id.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "monitorType" => intermediate_rep.monitor_type.push(
-                        <models::MonitorType as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "monitorName" => intermediate_rep.monitor_name.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
                     "status" => intermediate_rep.status.push(
-                        <models::MonitorStatusIndicator as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "timestamp" => intermediate_rep.timestamp.push(
-                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "expiresAt" => intermediate_rep.expires_at.push(
-                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "expectedResult" => intermediate_rep.expected_result.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "actualResult" => intermediate_rep.actual_result.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "description" => intermediate_rep.description.push(
-                        <String as std::str::FromStr>::from_str(val)
-                            .map_err(|x| format!("{}", x))?,
-                    ),
-                    "session" => intermediate_rep.session.push(
-                        <models::Session as std::str::FromStr>::from_str(val)
+                        <models::MonitorStatus as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "log" => {
-                        return std::result::Result::Err(
-                            "Parsing a container in this style is not supported in MonitorStatus"
-                                .to_string(),
-                        )
-                    }
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing MonitorStatus".to_string(),
+                            "Unexpected key while parsing MonitorStatusContainer".to_string(),
                         )
                     }
                 }
@@ -4295,76 +5423,32 @@ impl std::str::FromStr for MonitorStatus {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(MonitorStatus {
-            status_id: intermediate_rep
-                .status_id
-                .into_iter()
-                .next()
-                .ok_or("statusId missing in MonitorStatus".to_string())?,
-            monitor_type: intermediate_rep
-                .monitor_type
-                .into_iter()
-                .next()
-                .ok_or("monitorType missing in MonitorStatus".to_string())?,
-            monitor_name: intermediate_rep
-                .monitor_name
-                .into_iter()
-                .next()
-                .ok_or("monitorName missing in MonitorStatus".to_string())?,
+        std::result::Result::Ok(MonitorStatusContainer {
             status: intermediate_rep
                 .status
                 .into_iter()
                 .next()
-                .ok_or("status missing in MonitorStatus".to_string())?,
-            timestamp: intermediate_rep
-                .timestamp
-                .into_iter()
-                .next()
-                .ok_or("timestamp missing in MonitorStatus".to_string())?,
-            expires_at: intermediate_rep
-                .expires_at
-                .into_iter()
-                .next()
-                .ok_or("expiresAt missing in MonitorStatus".to_string())?,
-            expected_result: intermediate_rep
-                .expected_result
-                .into_iter()
-                .next()
-                .ok_or("expectedResult missing in MonitorStatus".to_string())?,
-            actual_result: intermediate_rep
-                .actual_result
-                .into_iter()
-                .next()
-                .ok_or("actualResult missing in MonitorStatus".to_string())?,
-            description: intermediate_rep
-                .description
-                .into_iter()
-                .next()
-                .ok_or("description missing in MonitorStatus".to_string())?,
-            session: intermediate_rep.session.into_iter().next(),
-            log: intermediate_rep
-                .log
-                .into_iter()
-                .next()
-                .ok_or("log missing in MonitorStatus".to_string())?,
+                .ok_or("status missing in MonitorStatusContainer".to_string())?,
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<MonitorStatus> and hyper::header::HeaderValue
+// Methods for converting between header::IntoHeaderValue<MonitorStatusContainer> and hyper::header::HeaderValue
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatus>> for hyper::header::HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusContainer>>
+    for hyper::header::HeaderValue
+{
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<MonitorStatus>,
+        hdr_value: header::IntoHeaderValue<MonitorStatusContainer>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match hyper::header::HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for MonitorStatus - value: {} is invalid {}",
+                "Invalid header value for MonitorStatusContainer - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -4372,18 +5456,20 @@ impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatus>> for hyper::he
 }
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<MonitorStatus> {
+impl std::convert::TryFrom<hyper::header::HeaderValue>
+    for header::IntoHeaderValue<MonitorStatusContainer>
+{
     type Error = String;
 
     fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <MonitorStatus as std::str::FromStr>::from_str(value) {
+                match <MonitorStatusContainer as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        "Unable to convert header value '{}' into MonitorStatus - {}",
+                        "Unable to convert header value '{}' into MonitorStatusContainer - {}",
                         value, err
                     )),
                 }
@@ -4396,42 +5482,90 @@ impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderVal
     }
 }
 
+/// Enumeration of values.
+/// Since this enum's variants do not hold data, we can easily define them them as `#[repr(C)]`
+/// which helps with FFI.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[cfg_attr(feature = "conversion", derive(frunk_enum_derive::LabelledGenericEnum))]
+pub enum MonitorStatusIndicator {
+    #[serde(rename = "ok")]
+    OK,
+    #[serde(rename = "down")]
+    DOWN,
+}
+
+impl std::fmt::Display for MonitorStatusIndicator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            MonitorStatusIndicator::OK => write!(f, "{}", "ok"),
+            MonitorStatusIndicator::DOWN => write!(f, "{}", "down"),
+        }
+    }
+}
+
+impl std::str::FromStr for MonitorStatusIndicator {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ok" => std::result::Result::Ok(MonitorStatusIndicator::OK),
+            "down" => std::result::Result::Ok(MonitorStatusIndicator::DOWN),
+            _ => std::result::Result::Err(format!("Value not valid: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct MonitorStatusArray {
-    #[serde(rename = "statuses")]
-    pub statuses: Vec<models::MonitorStatus>,
+pub struct MonitorStatusLogEntry {
+    /// UTC UNIX timestamp in with fractional offset.
+    #[serde(rename = "timestamp")]
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+
+    #[serde(rename = "value")]
+    pub value: String,
 }
 
-impl MonitorStatusArray {
-    pub fn new(statuses: Vec<models::MonitorStatus>) -> MonitorStatusArray {
-        MonitorStatusArray { statuses: statuses }
+impl MonitorStatusLogEntry {
+    pub fn new(timestamp: chrono::DateTime<chrono::Utc>, value: String) -> MonitorStatusLogEntry {
+        MonitorStatusLogEntry {
+            timestamp: timestamp,
+            value: value,
+        }
     }
 }
 
-/// Converts the MonitorStatusArray value to the Query Parameters representation (style=form, explode=false)
+/// Converts the MonitorStatusLogEntry value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::string::ToString for MonitorStatusArray {
+impl std::string::ToString for MonitorStatusLogEntry {
     fn to_string(&self) -> String {
         let mut params: Vec<String> = vec![];
-        // Skipping statuses in query parameter serialization
+        // Skipping timestamp in query parameter serialization
+
+        params.push("value".to_string());
+        params.push(self.value.to_string());
 
         params.join(",").to_string()
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatusArray value
+/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatusLogEntry value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for MonitorStatusArray {
+impl std::str::FromStr for MonitorStatusLogEntry {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         #[derive(Default)]
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
-            pub statuses: Vec<Vec<models::MonitorStatus>>,
+            pub timestamp: Vec<chrono::DateTime<chrono::Utc>>,
+            pub value: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -4445,20 +5579,24 @@ impl std::str::FromStr for MonitorStatusArray {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing MonitorStatusArray".to_string(),
+                        "Missing value while parsing MonitorStatusLogEntry".to_string(),
                     )
                 }
             };
 
             if let Some(key) = key_result {
                 match key {
-                    "statuses" => return std::result::Result::Err(
-                        "Parsing a container in this style is not supported in MonitorStatusArray"
-                            .to_string(),
+                    "timestamp" => intermediate_rep.timestamp.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "value" => intermediate_rep.value.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
                     ),
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing MonitorStatusArray".to_string(),
+                            "Unexpected key while parsing MonitorStatusLogEntry".to_string(),
                         )
                     }
                 }
@@ -4469,32 +5607,37 @@ impl std::str::FromStr for MonitorStatusArray {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(MonitorStatusArray {
-            statuses: intermediate_rep
-                .statuses
+        std::result::Result::Ok(MonitorStatusLogEntry {
+            timestamp: intermediate_rep
+                .timestamp
                 .into_iter()
                 .next()
-                .ok_or("statuses missing in MonitorStatusArray".to_string())?,
+                .ok_or("timestamp missing in MonitorStatusLogEntry".to_string())?,
+            value: intermediate_rep
+                .value
+                .into_iter()
+                .next()
+                .ok_or("value missing in MonitorStatusLogEntry".to_string())?,
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<MonitorStatusArray> and hyper::header::HeaderValue
+// Methods for converting between header::IntoHeaderValue<MonitorStatusLogEntry> and hyper::header::HeaderValue
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusArray>>
+impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusLogEntry>>
     for hyper::header::HeaderValue
 {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<MonitorStatusArray>,
+        hdr_value: header::IntoHeaderValue<MonitorStatusLogEntry>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match hyper::header::HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for MonitorStatusArray - value: {} is invalid {}",
+                "Invalid header value for MonitorStatusLogEntry - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -4503,19 +5646,19 @@ impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusArray>>
 
 #[cfg(any(feature = "client", feature = "server"))]
 impl std::convert::TryFrom<hyper::header::HeaderValue>
-    for header::IntoHeaderValue<MonitorStatusArray>
+    for header::IntoHeaderValue<MonitorStatusLogEntry>
 {
     type Error = String;
 
     fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <MonitorStatusArray as std::str::FromStr>::from_str(value) {
+                match <MonitorStatusLogEntry as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        "Unable to convert header value '{}' into MonitorStatusArray - {}",
+                        "Unable to convert header value '{}' into MonitorStatusLogEntry - {}",
                         value, err
                     )),
                 }
@@ -4528,42 +5671,119 @@ impl std::convert::TryFrom<hyper::header::HeaderValue>
     }
 }
 
+/// What sort of system or entity the monitor will run a check on
+/// Enumeration of values.
+/// Since this enum's variants do not hold data, we can easily define them them as `#[repr(C)]`
+/// which helps with FFI.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[cfg_attr(feature = "conversion", derive(frunk_enum_derive::LabelledGenericEnum))]
+pub enum MonitorType {
+    #[serde(rename = "http")]
+    HTTP,
+    #[serde(rename = "process")]
+    PROCESS,
+    #[serde(rename = "tcp")]
+    TCP,
+    #[serde(rename = "redis")]
+    REDIS,
+}
+
+impl std::fmt::Display for MonitorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            MonitorType::HTTP => write!(f, "{}", "http"),
+            MonitorType::PROCESS => write!(f, "{}", "process"),
+            MonitorType::TCP => write!(f, "{}", "tcp"),
+            MonitorType::REDIS => write!(f, "{}", "redis"),
+        }
+    }
+}
+
+impl std::str::FromStr for MonitorType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "http" => std::result::Result::Ok(MonitorType::HTTP),
+            "process" => std::result::Result::Ok(MonitorType::PROCESS),
+            "tcp" => std::result::Result::Ok(MonitorType::TCP),
+            "redis" => std::result::Result::Ok(MonitorType::REDIS),
+            _ => std::result::Result::Err(format!("Value not valid: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct MonitorStatusContainer {
-    #[serde(rename = "status")]
-    pub status: models::MonitorStatus,
+pub struct MsTeamsAlertBody {
+    #[serde(rename = "url")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Handlebars template for the subject or title of the message.
+    #[serde(rename = "subjectTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_template: Option<String>,
+
+    /// Handlebars template for the text of the message.
+    #[serde(rename = "bodyTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
 }
 
-impl MonitorStatusContainer {
-    pub fn new(status: models::MonitorStatus) -> MonitorStatusContainer {
-        MonitorStatusContainer { status: status }
+impl MsTeamsAlertBody {
+    pub fn new() -> MsTeamsAlertBody {
+        MsTeamsAlertBody {
+            url: None,
+            subject_template: None,
+            body_template: None,
+        }
     }
 }
 
-/// Converts the MonitorStatusContainer value to the Query Parameters representation (style=form, explode=false)
+/// Converts the MsTeamsAlertBody value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::string::ToString for MonitorStatusContainer {
+impl std::string::ToString for MsTeamsAlertBody {
     fn to_string(&self) -> String {
         let mut params: Vec<String> = vec![];
-        // Skipping status in query parameter serialization
+
+        if let Some(ref url) = self.url {
+            params.push("url".to_string());
+            params.push(url.to_string());
+        }
+
+        if let Some(ref subject_template) = self.subject_template {
+            params.push("subjectTemplate".to_string());
+            params.push(subject_template.to_string());
+        }
+
+        if let Some(ref body_template) = self.body_template {
+            params.push("bodyTemplate".to_string());
+            params.push(body_template.to_string());
+        }
 
         params.join(",").to_string()
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatusContainer value
+/// Converts Query Parameters representation (style=form, explode=false) to a MsTeamsAlertBody value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for MonitorStatusContainer {
+impl std::str::FromStr for MsTeamsAlertBody {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         #[derive(Default)]
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
-            pub status: Vec<models::MonitorStatus>,
+            pub url: Vec<String>,
+            pub subject_template: Vec<String>,
+            pub body_template: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -4577,20 +5797,28 @@ impl std::str::FromStr for MonitorStatusContainer {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing MonitorStatusContainer".to_string(),
+                        "Missing value while parsing MsTeamsAlertBody".to_string(),
                     )
                 }
             };
 
             if let Some(key) = key_result {
                 match key {
-                    "status" => intermediate_rep.status.push(
-                        <models::MonitorStatus as std::str::FromStr>::from_str(val)
+                    "url" => intermediate_rep.url.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "subjectTemplate" => intermediate_rep.subject_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "bodyTemplate" => intermediate_rep.body_template.push(
+                        <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing MonitorStatusContainer".to_string(),
+                            "Unexpected key while parsing MsTeamsAlertBody".to_string(),
                         )
                     }
                 }
@@ -4601,32 +5829,30 @@ impl std::str::FromStr for MonitorStatusContainer {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(MonitorStatusContainer {
-            status: intermediate_rep
-                .status
-                .into_iter()
-                .next()
-                .ok_or("status missing in MonitorStatusContainer".to_string())?,
+        std::result::Result::Ok(MsTeamsAlertBody {
+            url: intermediate_rep.url.into_iter().next(),
+            subject_template: intermediate_rep.subject_template.into_iter().next(),
+            body_template: intermediate_rep.body_template.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<MonitorStatusContainer> and hyper::header::HeaderValue
+// Methods for converting between header::IntoHeaderValue<MsTeamsAlertBody> and hyper::header::HeaderValue
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusContainer>>
+impl std::convert::TryFrom<header::IntoHeaderValue<MsTeamsAlertBody>>
     for hyper::header::HeaderValue
 {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<MonitorStatusContainer>,
+        hdr_value: header::IntoHeaderValue<MsTeamsAlertBody>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match hyper::header::HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for MonitorStatusContainer - value: {} is invalid {}",
+                "Invalid header value for MsTeamsAlertBody - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -4635,115 +5861,113 @@ impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusContainer>>
 
 #[cfg(any(feature = "client", feature = "server"))]
 impl std::convert::TryFrom<hyper::header::HeaderValue>
-    for header::IntoHeaderValue<MonitorStatusContainer>
-{
-    type Error = String;
-
-    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
-        match hdr_value.to_str() {
-            std::result::Result::Ok(value) => {
-                match <MonitorStatusContainer as std::str::FromStr>::from_str(value) {
-                    std::result::Result::Ok(value) => {
-                        std::result::Result::Ok(header::IntoHeaderValue(value))
-                    }
-                    std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        "Unable to convert header value '{}' into MonitorStatusContainer - {}",
-                        value, err
-                    )),
-                }
-            }
-            std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Unable to convert header: {:?} to string: {}",
-                hdr_value, e
-            )),
-        }
-    }
-}
-
-/// Enumeration of values.
-/// Since this enum's variants do not hold data, we can easily define them them as `#[repr(C)]`
-/// which helps with FFI.
-#[allow(non_camel_case_types)]
-#[repr(C)]
-#[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
-)]
-#[cfg_attr(feature = "conversion", derive(frunk_enum_derive::LabelledGenericEnum))]
-pub enum MonitorStatusIndicator {
-    #[serde(rename = "ok")]
-    OK,
-    #[serde(rename = "down")]
-    DOWN,
-}
-
-impl std::fmt::Display for MonitorStatusIndicator {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
-            MonitorStatusIndicator::OK => write!(f, "{}", "ok"),
-            MonitorStatusIndicator::DOWN => write!(f, "{}", "down"),
-        }
-    }
-}
-
-impl std::str::FromStr for MonitorStatusIndicator {
-    type Err = String;
+    for header::IntoHeaderValue<MsTeamsAlertBody>
+{
+    type Error = String;
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "ok" => std::result::Result::Ok(MonitorStatusIndicator::OK),
-            "down" => std::result::Result::Ok(MonitorStatusIndicator::DOWN),
-            _ => std::result::Result::Err(format!("Value not valid: {}", s)),
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <MsTeamsAlertBody as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into MsTeamsAlertBody - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct MonitorStatusLogEntry {
-    /// UTC UNIX timestamp in with fractional offset.
-    #[serde(rename = "timestamp")]
-    pub timestamp: chrono::DateTime<chrono::Utc>,
+pub struct NewAgent {
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
 
-    #[serde(rename = "value")]
-    pub value: String,
+    #[serde(rename = "id")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(rename = "name")]
+    pub name: String,
+
+    /// Describes what this agent is or where it will run.
+    #[serde(rename = "description")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(rename = "group")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
 }
 
-impl MonitorStatusLogEntry {
-    pub fn new(timestamp: chrono::DateTime<chrono::Utc>, value: String) -> MonitorStatusLogEntry {
-        MonitorStatusLogEntry {
-            timestamp: timestamp,
-            value: value,
+impl NewAgent {
+    pub fn new(api_key: String, name: String) -> NewAgent {
+        NewAgent {
+            api_key: api_key,
+            id: None,
+            name: name,
+            description: None,
+            group: None,
         }
     }
 }
 
-/// Converts the MonitorStatusLogEntry value to the Query Parameters representation (style=form, explode=false)
+/// Converts the NewAgent value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::string::ToString for MonitorStatusLogEntry {
+impl std::string::ToString for NewAgent {
     fn to_string(&self) -> String {
         let mut params: Vec<String> = vec![];
-        // Skipping timestamp in query parameter serialization
 
-        params.push("value".to_string());
-        params.push(self.value.to_string());
+        params.push("apiKey".to_string());
+        params.push(self.api_key.to_string());
+
+        if let Some(ref id) = self.id {
+            params.push("id".to_string());
+            params.push(id.to_string());
+        }
+
+        params.push("name".to_string());
+        params.push(self.name.to_string());
+
+        if let Some(ref description) = self.description {
+            params.push("description".to_string());
+            params.push(description.to_string());
+        }
+
+        if let Some(ref group) = self.group {
+            params.push("group".to_string());
+            params.push(group.to_string());
+        }
 
         params.join(",").to_string()
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a MonitorStatusLogEntry value
+/// Converts Query Parameters representation (style=form, explode=false) to a NewAgent value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for MonitorStatusLogEntry {
+impl std::str::FromStr for NewAgent {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         #[derive(Default)]
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
-            pub timestamp: Vec<chrono::DateTime<chrono::Utc>>,
-            pub value: Vec<String>,
+            pub api_key: Vec<String>,
+            pub id: Vec<String>,
+            pub name: Vec<String>,
+            pub description: Vec<String>,
+            pub group: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -4757,24 +5981,36 @@ impl std::str::FromStr for MonitorStatusLogEntry {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing MonitorStatusLogEntry".to_string(),
+                        "Missing value while parsing NewAgent".to_string(),
                     )
                 }
             };
 
             if let Some(key) = key_result {
                 match key {
-                    "timestamp" => intermediate_rep.timestamp.push(
-                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                    "apiKey" => intermediate_rep.api_key.push(
+                        <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "value" => intermediate_rep.value.push(
+                    "id" => intermediate_rep.id.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "name" => intermediate_rep.name.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "description" => intermediate_rep.description.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "group" => intermediate_rep.group.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing MonitorStatusLogEntry".to_string(),
+                            "Unexpected key while parsing NewAgent".to_string(),
                         )
                     }
                 }
@@ -4785,37 +6021,38 @@ impl std::str::FromStr for MonitorStatusLogEntry {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(MonitorStatusLogEntry {
-            timestamp: intermediate_rep
-                .timestamp
+        std::result::Result::Ok(NewAgent {
+            api_key: intermediate_rep
+                .api_key
                 .into_iter()
                 .next()
-                .ok_or("timestamp missing in MonitorStatusLogEntry".to_string())?,
-            value: intermediate_rep
-                .value
+                .ok_or("apiKey missing in NewAgent".to_string())?,
+            id: intermediate_rep.id.into_iter().next(),
+            name: intermediate_rep
+                .name
                 .into_iter()
                 .next()
-                .ok_or("value missing in MonitorStatusLogEntry".to_string())?,
+                .ok_or("name missing in NewAgent".to_string())?,
+            description: intermediate_rep.description.into_iter().next(),
+            group: intermediate_rep.group.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<MonitorStatusLogEntry> and hyper::header::HeaderValue
+// Methods for converting between header::IntoHeaderValue<NewAgent> and hyper::header::HeaderValue
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusLogEntry>>
-    for hyper::header::HeaderValue
-{
+impl std::convert::TryFrom<header::IntoHeaderValue<NewAgent>> for hyper::header::HeaderValue {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<MonitorStatusLogEntry>,
+        hdr_value: header::IntoHeaderValue<NewAgent>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match hyper::header::HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for MonitorStatusLogEntry - value: {} is invalid {}",
+                "Invalid header value for NewAgent - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -4823,20 +6060,18 @@ impl std::convert::TryFrom<header::IntoHeaderValue<MonitorStatusLogEntry>>
 }
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<hyper::header::HeaderValue>
-    for header::IntoHeaderValue<MonitorStatusLogEntry>
-{
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<NewAgent> {
     type Error = String;
 
     fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <MonitorStatusLogEntry as std::str::FromStr>::from_str(value) {
+                match <NewAgent as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        "Unable to convert header value '{}' into MonitorStatusLogEntry - {}",
+                        "Unable to convert header value '{}' into NewAgent - {}",
                         value, err
                     )),
                 }
@@ -4849,70 +6084,56 @@ impl std::convert::TryFrom<hyper::header::HeaderValue>
     }
 }
 
-/// What sort of system or entity the monitor will run a check on
-/// Enumeration of values.
-/// Since this enum's variants do not hold data, we can easily define them them as `#[repr(C)]`
-/// which helps with FFI.
-#[allow(non_camel_case_types)]
-#[repr(C)]
-#[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
-)]
-#[cfg_attr(feature = "conversion", derive(frunk_enum_derive::LabelledGenericEnum))]
-pub enum MonitorType {
-    #[serde(rename = "http")]
-    HTTP,
-    #[serde(rename = "process")]
-    PROCESS,
-    #[serde(rename = "tcp")]
-    TCP,
-    #[serde(rename = "redis")]
-    REDIS,
-}
-
-impl std::fmt::Display for MonitorType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
-            MonitorType::HTTP => write!(f, "{}", "http"),
-            MonitorType::PROCESS => write!(f, "{}", "process"),
-            MonitorType::TCP => write!(f, "{}", "tcp"),
-            MonitorType::REDIS => write!(f, "{}", "redis"),
-        }
-    }
-}
-
-impl std::str::FromStr for MonitorType {
-    type Err = String;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "http" => std::result::Result::Ok(MonitorType::HTTP),
-            "process" => std::result::Result::Ok(MonitorType::PROCESS),
-            "tcp" => std::result::Result::Ok(MonitorType::TCP),
-            "redis" => std::result::Result::Ok(MonitorType::REDIS),
-            _ => std::result::Result::Err(format!("Value not valid: {}", s)),
-        }
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct MsTeamsAlertBody {
+pub struct OpsgenieAlertBody {
     #[serde(rename = "url")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+
+    /// Key of an Opsgenie API integration.
+    #[serde(rename = "apiKey")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// P1 (highest) to P5 (lowest). Defaults to P3.
+    #[serde(rename = "priority")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// Teams, users, escalations or schedules to notify, like team:ops or user:jo@example.com.
+    #[serde(rename = "responders")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responders: Option<Vec<String>>,
+
+    /// Handlebars template for the subject or title of the message.
+    #[serde(rename = "subjectTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_template: Option<String>,
+
+    /// Handlebars template for the text of the message.
+    #[serde(rename = "bodyTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
 }
 
-impl MsTeamsAlertBody {
-    pub fn new() -> MsTeamsAlertBody {
-        MsTeamsAlertBody { url: None }
+impl OpsgenieAlertBody {
+    pub fn new() -> OpsgenieAlertBody {
+        OpsgenieAlertBody {
+            url: None,
+            api_key: None,
+            priority: None,
+            responders: None,
+            subject_template: None,
+            body_template: None,
+        }
     }
 }
 
-/// Converts the MsTeamsAlertBody value to the Query Parameters representation (style=form, explode=false)
+/// Converts the OpsgenieAlertBody value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::string::ToString for MsTeamsAlertBody {
+impl std::string::ToString for OpsgenieAlertBody {
     fn to_string(&self) -> String {
         let mut params: Vec<String> = vec![];
 
@@ -4921,14 +6142,36 @@ impl std::string::ToString for MsTeamsAlertBody {
             params.push(url.to_string());
         }
 
+        if let Some(ref api_key) = self.api_key {
+            params.push("apiKey".to_string());
+            params.push(api_key.to_string());
+        }
+
+        if let Some(ref priority) = self.priority {
+            params.push("priority".to_string());
+            params.push(priority.to_string());
+        }
+
+        // Skipping responders in query parameter serialization
+
+        if let Some(ref subject_template) = self.subject_template {
+            params.push("subjectTemplate".to_string());
+            params.push(subject_template.to_string());
+        }
+
+        if let Some(ref body_template) = self.body_template {
+            params.push("bodyTemplate".to_string());
+            params.push(body_template.to_string());
+        }
+
         params.join(",").to_string()
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a MsTeamsAlertBody value
+/// Converts Query Parameters representation (style=form, explode=false) to a OpsgenieAlertBody value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for MsTeamsAlertBody {
+impl std::str::FromStr for OpsgenieAlertBody {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -4936,6 +6179,11 @@ impl std::str::FromStr for MsTeamsAlertBody {
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
             pub url: Vec<String>,
+            pub api_key: Vec<String>,
+            pub priority: Vec<String>,
+            pub responders: Vec<Vec<String>>,
+            pub subject_template: Vec<String>,
+            pub body_template: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -4949,7 +6197,7 @@ impl std::str::FromStr for MsTeamsAlertBody {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing MsTeamsAlertBody".to_string(),
+                        "Missing value while parsing OpsgenieAlertBody".to_string(),
                     )
                 }
             };
@@ -4960,9 +6208,29 @@ impl std::str::FromStr for MsTeamsAlertBody {
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
+                    "apiKey" => intermediate_rep.api_key.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "priority" => intermediate_rep.priority.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "responders" => return std::result::Result::Err(
+                        "Parsing a container in this style is not supported in OpsgenieAlertBody"
+                            .to_string(),
+                    ),
+                    "subjectTemplate" => intermediate_rep.subject_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "bodyTemplate" => intermediate_rep.body_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing MsTeamsAlertBody".to_string(),
+                            "Unexpected key while parsing OpsgenieAlertBody".to_string(),
                         )
                     }
                 }
@@ -4973,28 +6241,33 @@ impl std::str::FromStr for MsTeamsAlertBody {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(MsTeamsAlertBody {
+        std::result::Result::Ok(OpsgenieAlertBody {
             url: intermediate_rep.url.into_iter().next(),
+            api_key: intermediate_rep.api_key.into_iter().next(),
+            priority: intermediate_rep.priority.into_iter().next(),
+            responders: intermediate_rep.responders.into_iter().next(),
+            subject_template: intermediate_rep.subject_template.into_iter().next(),
+            body_template: intermediate_rep.body_template.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<MsTeamsAlertBody> and hyper::header::HeaderValue
+// Methods for converting between header::IntoHeaderValue<OpsgenieAlertBody> and hyper::header::HeaderValue
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<header::IntoHeaderValue<MsTeamsAlertBody>>
+impl std::convert::TryFrom<header::IntoHeaderValue<OpsgenieAlertBody>>
     for hyper::header::HeaderValue
 {
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<MsTeamsAlertBody>,
+        hdr_value: header::IntoHeaderValue<OpsgenieAlertBody>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match hyper::header::HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for MsTeamsAlertBody - value: {} is invalid {}",
+                "Invalid header value for OpsgenieAlertBody - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -5003,19 +6276,19 @@ impl std::convert::TryFrom<header::IntoHeaderValue<MsTeamsAlertBody>>
 
 #[cfg(any(feature = "client", feature = "server"))]
 impl std::convert::TryFrom<hyper::header::HeaderValue>
-    for header::IntoHeaderValue<MsTeamsAlertBody>
+    for header::IntoHeaderValue<OpsgenieAlertBody>
 {
     type Error = String;
 
     fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <MsTeamsAlertBody as std::str::FromStr>::from_str(value) {
+                match <OpsgenieAlertBody as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        "Unable to convert header value '{}' into MsTeamsAlertBody - {}",
+                        "Unable to convert header value '{}' into OpsgenieAlertBody - {}",
                         value, err
                     )),
                 }
@@ -5030,86 +6303,95 @@ impl std::convert::TryFrom<hyper::header::HeaderValue>
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct NewAgent {
-    #[serde(rename = "apiKey")]
-    pub api_key: String,
+pub struct PagerDutyAlertBody {
+    #[serde(rename = "url")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
 
-    #[serde(rename = "id")]
+    /// Integration key of the PagerDuty service.
+    #[serde(rename = "routingKey")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
+    pub routing_key: Option<String>,
 
-    #[serde(rename = "name")]
-    pub name: String,
+    /// One of critical, error, warning or info. Defaults to critical.
+    #[serde(rename = "severity")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
 
-    /// Describes what this agent is or where it will run.
-    #[serde(rename = "description")]
+    /// Handlebars template for the subject or title of the message.
+    #[serde(rename = "subjectTemplate")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    pub subject_template: Option<String>,
 
-    #[serde(rename = "group")]
+    /// Handlebars template for the text of the message.
+    #[serde(rename = "bodyTemplate")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub group: Option<String>,
+    pub body_template: Option<String>,
 }
 
-impl NewAgent {
-    pub fn new(api_key: String, name: String) -> NewAgent {
-        NewAgent {
-            api_key: api_key,
-            id: None,
-            name: name,
-            description: None,
-            group: None,
+impl PagerDutyAlertBody {
+    pub fn new() -> PagerDutyAlertBody {
+        PagerDutyAlertBody {
+            url: None,
+            routing_key: None,
+            severity: None,
+            subject_template: None,
+            body_template: None,
         }
     }
 }
 
-/// Converts the NewAgent value to the Query Parameters representation (style=form, explode=false)
+/// Converts the PagerDutyAlertBody value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::string::ToString for NewAgent {
+impl std::string::ToString for PagerDutyAlertBody {
     fn to_string(&self) -> String {
         let mut params: Vec<String> = vec![];
 
-        params.push("apiKey".to_string());
-        params.push(self.api_key.to_string());
+        if let Some(ref url) = self.url {
+            params.push("url".to_string());
+            params.push(url.to_string());
+        }
 
-        if let Some(ref id) = self.id {
-            params.push("id".to_string());
-            params.push(id.to_string());
+        if let Some(ref routing_key) = self.routing_key {
+            params.push("routingKey".to_string());
+            params.push(routing_key.to_string());
         }
 
-        params.push("name".to_string());
-        params.push(self.name.to_string());
+        if let Some(ref severity) = self.severity {
+            params.push("severity".to_string());
+            params.push(severity.to_string());
+        }
 
-        if let Some(ref description) = self.description {
-            params.push("description".to_string());
-            params.push(description.to_string());
+        if let Some(ref subject_template) = self.subject_template {
+            params.push("subjectTemplate".to_string());
+            params.push(subject_template.to_string());
         }
 
-        if let Some(ref group) = self.group {
-            params.push("group".to_string());
-            params.push(group.to_string());
+        if let Some(ref body_template) = self.body_template {
+            params.push("bodyTemplate".to_string());
+            params.push(body_template.to_string());
         }
 
         params.join(",").to_string()
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a NewAgent value
+/// Converts Query Parameters representation (style=form, explode=false) to a PagerDutyAlertBody value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for NewAgent {
+impl std::str::FromStr for PagerDutyAlertBody {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         #[derive(Default)]
         // An intermediate representation of the struct to use for parsing.
         struct IntermediateRep {
-            pub api_key: Vec<String>,
-            pub id: Vec<String>,
-            pub name: Vec<String>,
-            pub description: Vec<String>,
-            pub group: Vec<String>,
+            pub url: Vec<String>,
+            pub routing_key: Vec<String>,
+            pub severity: Vec<String>,
+            pub subject_template: Vec<String>,
+            pub body_template: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -5123,36 +6405,36 @@ impl std::str::FromStr for NewAgent {
                 Some(x) => x,
                 None => {
                     return std::result::Result::Err(
-                        "Missing value while parsing NewAgent".to_string(),
+                        "Missing value while parsing PagerDutyAlertBody".to_string(),
                     )
                 }
             };
 
             if let Some(key) = key_result {
                 match key {
-                    "apiKey" => intermediate_rep.api_key.push(
+                    "url" => intermediate_rep.url.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "id" => intermediate_rep.id.push(
+                    "routingKey" => intermediate_rep.routing_key.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "name" => intermediate_rep.name.push(
+                    "severity" => intermediate_rep.severity.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "description" => intermediate_rep.description.push(
+                    "subjectTemplate" => intermediate_rep.subject_template.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
-                    "group" => intermediate_rep.group.push(
+                    "bodyTemplate" => intermediate_rep.body_template.push(
                         <String as std::str::FromStr>::from_str(val)
                             .map_err(|x| format!("{}", x))?,
                     ),
                     _ => {
                         return std::result::Result::Err(
-                            "Unexpected key while parsing NewAgent".to_string(),
+                            "Unexpected key while parsing PagerDutyAlertBody".to_string(),
                         )
                     }
                 }
@@ -5163,38 +6445,32 @@ impl std::str::FromStr for NewAgent {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(NewAgent {
-            api_key: intermediate_rep
-                .api_key
-                .into_iter()
-                .next()
-                .ok_or("apiKey missing in NewAgent".to_string())?,
-            id: intermediate_rep.id.into_iter().next(),
-            name: intermediate_rep
-                .name
-                .into_iter()
-                .next()
-                .ok_or("name missing in NewAgent".to_string())?,
-            description: intermediate_rep.description.into_iter().next(),
-            group: intermediate_rep.group.into_iter().next(),
+        std::result::Result::Ok(PagerDutyAlertBody {
+            url: intermediate_rep.url.into_iter().next(),
+            routing_key: intermediate_rep.routing_key.into_iter().next(),
+            severity: intermediate_rep.severity.into_iter().next(),
+            subject_template: intermediate_rep.subject_template.into_iter().next(),
+            body_template: intermediate_rep.body_template.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<NewAgent> and hyper::header::HeaderValue
+// Methods for converting between header::IntoHeaderValue<PagerDutyAlertBody> and hyper::header::HeaderValue
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<header::IntoHeaderValue<NewAgent>> for hyper::header::HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<PagerDutyAlertBody>>
+    for hyper::header::HeaderValue
+{
     type Error = String;
 
     fn try_from(
-        hdr_value: header::IntoHeaderValue<NewAgent>,
+        hdr_value: header::IntoHeaderValue<PagerDutyAlertBody>,
     ) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match hyper::header::HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for NewAgent - value: {} is invalid {}",
+                "Invalid header value for PagerDutyAlertBody - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -5202,18 +6478,20 @@ impl std::convert::TryFrom<header::IntoHeaderValue<NewAgent>> for hyper::header:
 }
 
 #[cfg(any(feature = "client", feature = "server"))]
-impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<NewAgent> {
+impl std::convert::TryFrom<hyper::header::HeaderValue>
+    for header::IntoHeaderValue<PagerDutyAlertBody>
+{
     type Error = String;
 
     fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
             std::result::Result::Ok(value) => {
-                match <NewAgent as std::str::FromStr>::from_str(value) {
+                match <PagerDutyAlertBody as std::str::FromStr>::from_str(value) {
                     std::result::Result::Ok(value) => {
                         std::result::Result::Ok(header::IntoHeaderValue(value))
                     }
                     std::result::Result::Err(err) => std::result::Result::Err(format!(
-                        "Unable to convert header value '{}' into NewAgent - {}",
+                        "Unable to convert header value '{}' into PagerDutyAlertBody - {}",
                         value, err
                     )),
                 }
@@ -7828,6 +9106,205 @@ impl std::convert::TryFrom<hyper::header::HeaderValue>
     }
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct SlackAlertBody {
+    #[serde(rename = "url")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Bot token for chat.postMessage. Without a token, url is an incoming webhook.
+    #[serde(rename = "token")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// The channel to post to with chat.postMessage.
+    #[serde(rename = "channel")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Handlebars template for the subject or title of the message.
+    #[serde(rename = "subjectTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_template: Option<String>,
+
+    /// Handlebars template for the text of the message.
+    #[serde(rename = "bodyTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
+}
+
+impl SlackAlertBody {
+    pub fn new() -> SlackAlertBody {
+        SlackAlertBody {
+            url: None,
+            token: None,
+            channel: None,
+            subject_template: None,
+            body_template: None,
+        }
+    }
+}
+
+/// Converts the SlackAlertBody value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::string::ToString for SlackAlertBody {
+    fn to_string(&self) -> String {
+        let mut params: Vec<String> = vec![];
+
+        if let Some(ref url) = self.url {
+            params.push("url".to_string());
+            params.push(url.to_string());
+        }
+
+        if let Some(ref token) = self.token {
+            params.push("token".to_string());
+            params.push(token.to_string());
+        }
+
+        if let Some(ref channel) = self.channel {
+            params.push("channel".to_string());
+            params.push(channel.to_string());
+        }
+
+        if let Some(ref subject_template) = self.subject_template {
+            params.push("subjectTemplate".to_string());
+            params.push(subject_template.to_string());
+        }
+
+        if let Some(ref body_template) = self.body_template {
+            params.push("bodyTemplate".to_string());
+            params.push(body_template.to_string());
+        }
+
+        params.join(",").to_string()
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a SlackAlertBody value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for SlackAlertBody {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        #[derive(Default)]
+        // An intermediate representation of the struct to use for parsing.
+        struct IntermediateRep {
+            pub url: Vec<String>,
+            pub token: Vec<String>,
+            pub channel: Vec<String>,
+            pub subject_template: Vec<String>,
+            pub body_template: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',').into_iter();
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => {
+                    return std::result::Result::Err(
+                        "Missing value while parsing SlackAlertBody".to_string(),
+                    )
+                }
+            };
+
+            if let Some(key) = key_result {
+                match key {
+                    "url" => intermediate_rep.url.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "token" => intermediate_rep.token.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "channel" => intermediate_rep.channel.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "subjectTemplate" => intermediate_rep.subject_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "bodyTemplate" => intermediate_rep.body_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    _ => {
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing SlackAlertBody".to_string(),
+                        )
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(SlackAlertBody {
+            url: intermediate_rep.url.into_iter().next(),
+            token: intermediate_rep.token.into_iter().next(),
+            channel: intermediate_rep.channel.into_iter().next(),
+            subject_template: intermediate_rep.subject_template.into_iter().next(),
+            body_template: intermediate_rep.body_template.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<SlackAlertBody> and hyper::header::HeaderValue
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<header::IntoHeaderValue<SlackAlertBody>> for hyper::header::HeaderValue {
+    type Error = String;
+
+    fn try_from(
+        hdr_value: header::IntoHeaderValue<SlackAlertBody>,
+    ) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match hyper::header::HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for SlackAlertBody - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+impl std::convert::TryFrom<hyper::header::HeaderValue> for header::IntoHeaderValue<SlackAlertBody> {
+    type Error = String;
+
+    fn try_from(hdr_value: hyper::header::HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => {
+                match <SlackAlertBody as std::str::FromStr>::from_str(value) {
+                    std::result::Result::Ok(value) => {
+                        std::result::Result::Ok(header::IntoHeaderValue(value))
+                    }
+                    std::result::Result::Err(err) => std::result::Result::Err(format!(
+                        "Unable to convert header value '{}' into SlackAlertBody - {}",
+                        value, err
+                    )),
+                }
+            }
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Unable to convert header: {:?} to string: {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct Subscription {
@@ -8862,6 +10339,21 @@ pub struct WebhookAlertBody {
     #[serde(rename = "headers")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<Vec<models::HttpHeader>>,
+
+    /// Handlebars template for the request body. Defaults to the JSON payload.
+    #[serde(rename = "bodyTemplate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_template: Option<String>,
+
+    /// Key for signing the request body with HMAC-SHA256.
+    #[serde(rename = "secret")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+
+    /// Header that carries the signature. Defaults to X-Schnooty-Signature.
+    #[serde(rename = "signatureHeader")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_header: Option<String>,
 }
 
 impl WebhookAlertBody {
@@ -8869,6 +10361,9 @@ impl WebhookAlertBody {
         WebhookAlertBody {
             url: None,
             headers: None,
+            body_template: None,
+            secret: None,
+            signature_header: None,
         }
     }
 }
@@ -8887,6 +10382,21 @@ impl std::string::ToString for WebhookAlertBody {
 
         // Skipping headers in query parameter serialization
 
+        if let Some(ref body_template) = self.body_template {
+            params.push("bodyTemplate".to_string());
+            params.push(body_template.to_string());
+        }
+
+        if let Some(ref secret) = self.secret {
+            params.push("secret".to_string());
+            params.push(secret.to_string());
+        }
+
+        if let Some(ref signature_header) = self.signature_header {
+            params.push("signatureHeader".to_string());
+            params.push(signature_header.to_string());
+        }
+
         params.join(",").to_string()
     }
 }
@@ -8903,6 +10413,9 @@ impl std::str::FromStr for WebhookAlertBody {
         struct IntermediateRep {
             pub url: Vec<String>,
             pub headers: Vec<Vec<models::HttpHeader>>,
+            pub body_template: Vec<String>,
+            pub secret: Vec<String>,
+            pub signature_header: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -8931,6 +10444,18 @@ impl std::str::FromStr for WebhookAlertBody {
                         "Parsing a container in this style is not supported in WebhookAlertBody"
                             .to_string(),
                     ),
+                    "bodyTemplate" => intermediate_rep.body_template.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "secret" => intermediate_rep.secret.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
+                    "signatureHeader" => intermediate_rep.signature_header.push(
+                        <String as std::str::FromStr>::from_str(val)
+                            .map_err(|x| format!("{}", x))?,
+                    ),
                     _ => {
                         return std::result::Result::Err(
                             "Unexpected key while parsing WebhookAlertBody".to_string(),
@@ -8947,6 +10472,9 @@ impl std::str::FromStr for WebhookAlertBody {
         std::result::Result::Ok(WebhookAlertBody {
             url: intermediate_rep.url.into_iter().next(),
             headers: intermediate_rep.headers.into_iter().next(),
+            body_template: intermediate_rep.body_template.into_iter().next(),
+            secret: intermediate_rep.secret.into_iter().next(),
+            signature_header: intermediate_rep.signature_header.into_iter().next(),
         })
     }
 }
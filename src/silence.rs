@@ -0,0 +1,391 @@
+use crate::duration::parse_duration;
+use crate::error::Error;
+use crate::openapi_client::models;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// Holds back the alerts of matching monitors, for example during a deploy. A silence is active
+/// between `start` and `end`, and if it has a `schedule`, only in the windows of `duration` that
+/// begin at the times the schedule matches.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Silence {
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Names or IDs of the monitors to silence
+    #[serde(default)]
+    pub monitors: Vec<String>,
+    /// Monitors with any of these tags are silenced
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub end: Option<DateTime<Utc>>,
+    /// When each recurring window begins, as `minute hour day-of-month month day-of-week` in UTC
+    #[serde(default)]
+    pub schedule: Option<Schedule>,
+    /// How long each recurring window lasts, such as `2h`
+    #[serde(default)]
+    pub duration: Option<String>,
+    pub reason: String,
+}
+
+impl Silence {
+    /// Checks that the silence has a usable window.
+    pub fn validate(&self) -> Result<(), Error> {
+        let name = self.id.as_deref().unwrap_or(&self.reason);
+
+        match (&self.schedule, &self.duration) {
+            (Some(_), Some(duration)) => {
+                if parse_duration(duration)? < std::time::Duration::from_secs(60) {
+                    return Err(Error::new(format!(
+                        "Silence {} has a duration shorter than a minute",
+                        name
+                    )));
+                }
+            }
+            (None, None) if self.end.is_some() => {}
+            (None, None) => {
+                return Err(Error::new(format!(
+                    "Silence {} needs an end, or a schedule and duration",
+                    name
+                )))
+            }
+            _ => {
+                return Err(Error::new(format!(
+                    "Silence {} needs both a schedule and a duration",
+                    name
+                )))
+            }
+        }
+
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start >= end {
+                return Err(Error::new(format!(
+                    "Silence {} ends before it starts",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the silence covers the monitor. A silence without monitors or tags covers every
+    /// monitor.
+    pub fn matches(&self, monitor: &models::Monitor) -> bool {
+        if self.monitors.is_empty() && self.tags.is_empty() {
+            return true;
+        }
+        self.monitors
+            .iter()
+            .any(|m| *m == monitor.name || monitor.id.as_deref() == Some(m.as_str()))
+            || monitor
+                .tags
+                .iter()
+                .flatten()
+                .any(|tag| self.tags.contains(tag))
+    }
+
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        if self.start.map(|start| at < start).unwrap_or(false) || self.has_ended(at) {
+            return false;
+        }
+
+        match (&self.schedule, &self.duration) {
+            (Some(schedule), Some(duration)) => match parse_duration(duration) {
+                Ok(duration) => schedule.window_contains(duration, at),
+                Err(_) => false,
+            },
+            _ => true,
+        }
+    }
+
+    /// Whether the silence is over for good.
+    pub fn has_ended(&self, at: DateTime<Utc>) -> bool {
+        self.end.map(|end| at >= end).unwrap_or(false)
+    }
+}
+
+/// A cron-like schedule of five fields: minute, hour, day of month, month and day of week
+/// (0 or 7 is Sunday). Each field is `*`, a number, a range `a-b`, a list `a,b`, or any of these
+/// with a step such as `*/15`. It is parsed when the silence is read.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Schedule {
+    /// The schedule as it was written
+    text: String,
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days: Vec<bool>,
+    months: Vec<bool>,
+    weekdays: Vec<bool>,
+    /// Whether day of month and day of week are both restricted, in which case either matches
+    day_or_weekday: bool,
+}
+
+impl Schedule {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = value.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "'{}' should have 5 fields: minute hour day-of-month month day-of-week",
+                value
+            ));
+        }
+
+        let mut weekdays = parse_field(fields[4], 0, 7)?;
+        if weekdays[7] {
+            weekdays[0] = true;
+        }
+        weekdays.truncate(7);
+
+        Ok(Self {
+            text: value.to_owned(),
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            weekdays,
+            day_or_weekday: fields[2] != "*" && fields[4] != "*",
+        })
+    }
+
+    pub fn matches(&self, at: DateTime<Utc>) -> bool {
+        self.minutes[at.minute() as usize]
+            && self.hours[at.hour() as usize]
+            && self.matches_day(at.naive_utc().date())
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = self.days[date.day() as usize];
+        let weekday = self.weekdays[date.weekday().num_days_from_sunday() as usize];
+
+        self.months[date.month() as usize]
+            && if self.day_or_weekday {
+                day || weekday
+            } else {
+                day && weekday
+            }
+    }
+
+    /// Whether a window of the given length that begins at a time matching the schedule
+    /// contains the time.
+    pub fn window_contains(&self, length: std::time::Duration, at: DateTime<Utc>) -> bool {
+        let earliest = match Duration::from_std(length) {
+            Ok(length) => at - length,
+            Err(_) => return false,
+        };
+        self.last_match(at)
+            .map(|begin| begin > earliest)
+            .unwrap_or(false)
+    }
+
+    /// The latest time at or before `at` that matches the schedule. Days are checked from the
+    /// latest back, and within a day the latest matching hour and minute are taken.
+    fn last_match(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = at.naive_utc().date();
+        let mut date = today;
+
+        // every schedule matches at least once in 8 years, as the 29th of February does
+        for _ in 0..366 * 8 {
+            if self.matches_day(date) {
+                let last_hour = if date == today { at.hour() } else { 23 };
+                for hour in (0..=last_hour).rev().filter(|h| self.hours[*h as usize]) {
+                    let last_minute = if date == today && hour == at.hour() {
+                        at.minute()
+                    } else {
+                        59
+                    };
+                    if let Some(minute) =
+                        (0..=last_minute).rev().find(|m| self.minutes[*m as usize])
+                    {
+                        return Some(Utc.from_utc_datetime(&date.and_hms_opt(hour, minute, 0)?));
+                    }
+                }
+            }
+            date = date.pred_opt()?;
+        }
+        None
+    }
+}
+
+impl TryFrom<String> for Schedule {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Schedule::parse(&value)
+    }
+}
+
+impl From<Schedule> for String {
+    fn from(schedule: Schedule) -> Self {
+        schedule.text
+    }
+}
+
+/// Parses one field of a schedule into a flag for each value from 0 to `max`.
+fn parse_field(field: &str, min: usize, max: usize) -> Result<Vec<bool>, String> {
+    let mut values = vec![false; max + 1];
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<usize>()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("'{}' has an invalid step", part))?,
+            ),
+            None => (part, 1),
+        };
+
+        let (first, last) = if range == "*" {
+            (min, max)
+        } else {
+            let parse = |v: &str| {
+                v.parse::<usize>()
+                    .ok()
+                    .filter(|v| *v >= min && *v <= max)
+                    .ok_or_else(|| format!("'{}' is not a number from {} to {}", v, min, max))
+            };
+            match range.split_once('-') {
+                Some((first, last)) => (parse(first)?, parse(last)?),
+                None if step > 1 => (parse(range)?, max),
+                None => (parse(range)?, parse(range)?),
+            }
+        };
+
+        if first > last {
+            return Err(format!("'{}' is an empty range", part));
+        }
+        for value in (first..=last).step_by(step) {
+            values[value] = true;
+        }
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::monitoring::test_monitor;
+    use chrono::TimeZone;
+
+    fn silence() -> Silence {
+        Silence {
+            id: Some("deploy".to_owned()),
+            monitors: vec![],
+            tags: vec![],
+            start: None,
+            end: None,
+            schedule: None,
+            duration: None,
+            reason: "Deploying".to_owned(),
+        }
+    }
+
+    fn monitor(name: &str, tags: &[&str]) -> models::Monitor {
+        let mut monitor = test_monitor(name);
+        monitor.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+        monitor
+    }
+
+    #[test]
+    fn matches_monitors_by_name_or_tag() {
+        let mut by_name = silence();
+        by_name.monitors = vec!["api".to_owned()];
+        let mut by_tag = silence();
+        by_tag.tags = vec!["web".to_owned()];
+
+        assert!(silence().matches(&monitor("api", &[])));
+        assert!(by_name.matches(&monitor("api", &[])));
+        assert!(!by_name.matches(&monitor("website", &["web"])));
+        assert!(by_tag.matches(&monitor("website", &["web", "public"])));
+        assert!(!by_tag.matches(&monitor("api", &["internal"])));
+    }
+
+    #[test]
+    fn is_active_between_start_and_end() {
+        let mut fixed = silence();
+        fixed.start = Some(Utc.with_ymd_and_hms(2021, 3, 1, 10, 0, 0).unwrap());
+        fixed.end = Some(Utc.with_ymd_and_hms(2021, 3, 1, 11, 0, 0).unwrap());
+
+        assert!(fixed.validate().is_ok());
+        assert!(!fixed.is_active(Utc.with_ymd_and_hms(2021, 3, 1, 9, 59, 59).unwrap()));
+        assert!(fixed.is_active(Utc.with_ymd_and_hms(2021, 3, 1, 10, 30, 0).unwrap()));
+        assert!(!fixed.is_active(Utc.with_ymd_and_hms(2021, 3, 1, 11, 0, 0).unwrap()));
+        assert!(fixed.has_ended(Utc.with_ymd_and_hms(2021, 3, 1, 11, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn is_active_in_recurring_windows() {
+        // Sundays from 02:00 for 2 hours
+        let parse = |schedule: &str| {
+            serde_json::from_value::<Silence>(serde_json::json!({
+                "schedule": schedule,
+                "duration": "2h",
+                "reason": "Backup",
+            }))
+        };
+        assert!(parse("0 2 * * SUN").is_err());
+
+        let weekly = parse("0 2 * * 0").unwrap();
+        assert!(weekly.validate().is_ok());
+
+        // 2021-03-07 is a Sunday
+        assert!(!weekly.is_active(Utc.with_ymd_and_hms(2021, 3, 7, 1, 59, 0).unwrap()));
+        assert!(weekly.is_active(Utc.with_ymd_and_hms(2021, 3, 7, 2, 0, 0).unwrap()));
+        assert!(weekly.is_active(Utc.with_ymd_and_hms(2021, 3, 7, 3, 59, 59).unwrap()));
+        assert!(!weekly.is_active(Utc.with_ymd_and_hms(2021, 3, 7, 4, 0, 0).unwrap()));
+        assert!(!weekly.is_active(Utc.with_ymd_and_hms(2021, 3, 8, 2, 30, 0).unwrap()));
+    }
+
+    #[test]
+    fn finds_windows_that_span_days() {
+        // from 22:30 on the last day of February for 2 days
+        let schedule = Schedule::parse("30 22 28 2 *").unwrap();
+        let length = std::time::Duration::from_secs(2 * 24 * 60 * 60);
+
+        assert!(!schedule.window_contains(
+            length,
+            Utc.with_ymd_and_hms(2021, 2, 28, 22, 29, 0).unwrap()
+        ));
+        assert!(schedule.window_contains(
+            length,
+            Utc.with_ymd_and_hms(2021, 2, 28, 22, 30, 0).unwrap()
+        ));
+        assert!(schedule.window_contains(
+            length,
+            Utc.with_ymd_and_hms(2021, 3, 2, 22, 29, 59).unwrap()
+        ));
+        assert!(
+            !schedule.window_contains(length, Utc.with_ymd_and_hms(2021, 3, 2, 22, 30, 0).unwrap())
+        );
+        assert!(
+            !schedule.window_contains(length, Utc.with_ymd_and_hms(2021, 6, 1, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn parses_schedule_fields() {
+        let schedule = Schedule::parse("*/15 9-17 * * 1-5").unwrap();
+
+        assert!(schedule.matches(Utc.with_ymd_and_hms(2021, 3, 1, 9, 45, 0).unwrap()));
+        assert!(!schedule.matches(Utc.with_ymd_and_hms(2021, 3, 1, 9, 50, 0).unwrap()));
+        assert!(!schedule.matches(Utc.with_ymd_and_hms(2021, 3, 1, 18, 0, 0).unwrap()));
+        assert!(!schedule.matches(Utc.with_ymd_and_hms(2021, 3, 6, 9, 45, 0).unwrap()));
+
+        // either the day of the month or the day of the week
+        let schedule = Schedule::parse("0 0 1 * 7").unwrap();
+        assert!(schedule.matches(Utc.with_ymd_and_hms(2021, 3, 1, 0, 0, 0).unwrap()));
+        assert!(schedule.matches(Utc.with_ymd_and_hms(2021, 3, 7, 0, 0, 0).unwrap()));
+        assert!(!schedule.matches(Utc.with_ymd_and_hms(2021, 3, 2, 0, 0, 0).unwrap()));
+
+        assert!(Schedule::parse("0 2 * *").is_err());
+        assert!(Schedule::parse("60 * * * *").is_err());
+        assert!(Schedule::parse("5-1 * * * *").is_err());
+    }
+}